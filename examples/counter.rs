@@ -4,11 +4,12 @@ use std::time::Duration;
 
 use embedded_graphics::pixelcolor::Rgb888;
 use embedded_graphics::prelude::*;
-use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use embedded_graphics::text::{Alignment, Baseline};
 use embedded_graphics_simulator::SimulatorEvent::Quit;
 use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
 use mplusfonts::mplus;
 use mplusfonts::style::BitmapFontStyleBuilder;
+use mplusfonts::text::{Number, NumberFormat, Radix};
 
 /// Displays a counter that renders values `0x00` through `0xFF`, looping over and over.
 pub fn main() -> Result<(), Infallible> {
@@ -21,10 +22,12 @@ pub fn main() -> Result<(), Infallible> {
         .font(&bitmap_font)
         .build();
 
-    let text_style = TextStyleBuilder::new()
-        .alignment(Alignment::Center)
-        .baseline(Baseline::Middle)
-        .build();
+    let number_format = NumberFormat {
+        radix: Radix::UpperHex,
+        min_digits: 2,
+        prefix: "0x",
+        ..NumberFormat::new()
+    };
 
     let output_settings = OutputSettingsBuilder::new()
         .scale(3)
@@ -36,16 +39,16 @@ pub fn main() -> Result<(), Infallible> {
     let mut ticks: u8 = 0;
 
     'running: loop {
-        let digits = format!("0x{ticks:02X}");
-
-        let text = Text::with_text_style(
-            &digits,
+        let mut number = Number::new(
+            ticks.into(),
+            number_format,
             Point::new(120, 120),
             character_style.clone(),
-            text_style,
         );
 
-        text.draw(&mut display)?;
+        number.alignment = Alignment::Center;
+        number.baseline = Baseline::Middle;
+        number.draw(&mut display)?;
 
         window.update(&display);
 
@@ -0,0 +1,126 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{DeriveInput, GenericParam, parse_quote};
+
+pub enum ColorModel {
+    Rgb,
+    Gray,
+}
+
+pub fn color_math_impl(input: DeriveInput, color_model: ColorModel) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let mut colormap_generics = input.generics.clone();
+    let const_param: GenericParam = parse_quote!(const N: usize);
+    colormap_generics.params.push(const_param);
+    let (colormap_impl_generics, _, _) = colormap_generics.split_for_impl();
+
+    let prefix = match color_model {
+        ColorModel::Rgb => "rgb",
+        ColorModel::Gray => "gray",
+    };
+
+    let path = |name: &str| -> Ident { format_ident!("{prefix}_{name}") };
+    let [
+        linear,
+        interpolated,
+        invert,
+        with_opacity,
+        screen,
+        weighted_avg,
+        multiply,
+        darken,
+        lighten,
+        coverage_max,
+    ] = [
+        "linear",
+        "interpolated",
+        "invert",
+        "with_opacity",
+        "screen",
+        "weighted_avg",
+        "multiply",
+        "darken",
+        "lighten",
+        "coverage_max",
+    ]
+    .map(path);
+
+    let color = quote!(#ident #type_generics);
+    let derive = quote!(::mplusfonts::color::derive);
+
+    quote! {
+        impl #colormap_impl_generics ::mplusfonts::color::Linear<#color>
+            for ::mplusfonts::color::Colormap<#color, N> #where_clause
+        {
+            fn linear(start: #color, end: #color) -> Self {
+                #derive::#linear(start, end, <#color>::new)
+            }
+
+            fn interpolated(
+                start: #color,
+                end: #color,
+                interpolation: ::mplusfonts::color::Interpolation,
+            ) -> Self {
+                #derive::#interpolated(start, end, interpolation, <#color>::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::Invert for #color #where_clause {
+            fn invert(self) -> Self {
+                #derive::#invert(self, Self::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::Opacity for #color #where_clause {
+            fn with_opacity(self, background: Self, opacity: u8) -> Self {
+                #derive::#with_opacity(self, background, opacity, Self::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::Screen for #color #where_clause {
+            fn screen(self, other: Self, start: Self, end: Self) -> Self {
+                #derive::#screen(self, other, start, end, Self::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::WeightedAvg for #color #where_clause {
+            fn weighted_avg(
+                self,
+                other: Self,
+                start: Self,
+                end: Self,
+                other_start: Self,
+                other_end: Self,
+            ) -> Self {
+                let ranges = [[start, end], [other_start, other_end]];
+
+                #derive::#weighted_avg([self, other], ranges, Self::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::Multiply for #color #where_clause {
+            fn multiply(self, other: Self) -> Self {
+                #derive::#multiply(self, other, Self::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::Darken for #color #where_clause {
+            fn darken(self, other: Self) -> Self {
+                #derive::#darken(self, other, Self::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::Lighten for #color #where_clause {
+            fn lighten(self, other: Self) -> Self {
+                #derive::#lighten(self, other, Self::new)
+            }
+        }
+
+        impl #impl_generics ::mplusfonts::color::CoverageMax for #color #where_clause {
+            fn coverage_max(self, other: Self, start: Self, end: Self) -> Self {
+                #derive::#coverage_max(self, other, start, end, Self::new)
+            }
+        }
+    }
+}
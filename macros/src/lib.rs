@@ -2,11 +2,13 @@
 //! Coji Morishita; it is a dependency of [`mplusfonts`](../mplusfonts/index.html), with font
 //! rasterization powered by [`swash`].
 
+mod color;
 mod mplus;
 mod strings;
 
+use color::ColorModel;
 use proc_macro::TokenStream;
-use syn::{meta, parse_macro_input};
+use syn::{DeriveInput, meta, parse_macro_input};
 
 /// Collects string literals for rewriting [`mplus!`] macro invocations prior to their expansion.
 ///
@@ -99,7 +101,11 @@ pub fn strings(args: TokenStream, input: TokenStream) -> TokenStream {
 ///   Specify `1` for a single image at `.0` offset. Ignored for glyphs with square bounding
 ///   boxes such as kanji, kana, and also forced to `1` for `code`.
 /// * `bit_depth` - Bit depth of glyph images. Specify `n` to use _2_<sup>`n`</sup> values of gray.
-///   Limited to `1`, `2`, `4`, `8`.
+///   Limited to `1`, `2`, `4`, `8`. Alternatively, specify `paged`, which takes a `bit_depth`
+///   parameter and packs the glyph images in the memory layout of monochrome display controllers.
+///   * `paged(bit_depth)` - Bit depth of page-packed glyph images. Limited to `1`. Every byte is a
+///     column of eight vertically adjacent pixels, with the top pixel in the least significant bit;
+///     use with `mplusfonts::page::PageBuffer` for combining bytes with the contents of a display.
 /// * `sources` - Sources of characters for feeding the glyph shaper. Enable support for rendering
 ///   the individual strings here; otherwise, this instance returns boxes (image representations of
 ///   `.notdef`) when looking up glyph data.
@@ -173,3 +179,86 @@ pub fn mplus(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as mplus::Arguments);
     mplus::mplus_impl(args).into()
 }
+
+/// Implements the color math of [`mplusfonts`](../mplusfonts/index.html) for an RGB color type.
+///
+/// A custom color type that implements
+/// [`RgbColor`](../embedded_graphics/pixelcolor/trait.RgbColor.html) and has an associated
+/// function `new(r: u8, g: u8, b: u8) -> Self`, as do the color types of `embedded-graphics`, gets
+/// the same implementations of `Linear`, `Invert`, `Opacity`, `Screen`, `WeightedAvg`, and of the
+/// other blend modes, which makes it usable in a
+/// [`BitmapFontStyle`](../mplusfonts/style/struct.BitmapFontStyle.html) if it also implements
+/// [`Default`].
+///
+/// # Examples
+///
+/// ```
+/// # use embedded_graphics::pixelcolor::raw::RawU16;
+/// # use embedded_graphics::pixelcolor::{Gray4, PixelColor, RgbColor};
+/// # use mplusfonts::RgbColorMath;
+/// # use mplusfonts::color::{Colormap, Linear};
+/// #
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, RgbColorMath)]
+/// pub struct Bgr444(u16);
+///
+/// impl Bgr444 {
+///     pub const fn new(r: u8, g: u8, b: u8) -> Self {
+///         Self((b as u16) << 8 | (g as u16) << 4 | r as u16)
+///     }
+/// }
+///
+/// impl PixelColor for Bgr444 {
+///     type Raw = RawU16;
+/// }
+///
+/// impl RgbColor for Bgr444 {
+///     fn r(&self) -> u8 {
+///         (self.0 & 0xF) as u8
+///     }
+///
+///     fn g(&self) -> u8 {
+///         (self.0 >> 4 & 0xF) as u8
+///     }
+///
+///     fn b(&self) -> u8 {
+///         (self.0 >> 8 & 0xF) as u8
+///     }
+///
+///     const MAX_R: u8 = 15;
+///     const MAX_G: u8 = 15;
+///     const MAX_B: u8 = 15;
+///     // ...
+/// #   const BLACK: Self = Self::new(0, 0, 0);
+/// #   const RED: Self = Self::new(15, 0, 0);
+/// #   const GREEN: Self = Self::new(0, 15, 0);
+/// #   const BLUE: Self = Self::new(0, 0, 15);
+/// #   const YELLOW: Self = Self::new(15, 15, 0);
+/// #   const MAGENTA: Self = Self::new(15, 0, 15);
+/// #   const CYAN: Self = Self::new(0, 15, 15);
+/// #   const WHITE: Self = Self::new(15, 15, 15);
+/// }
+///
+/// let colormap: Colormap<Bgr444, 16> = Colormap::linear(Bgr444::BLACK, Bgr444::WHITE);
+/// assert_eq!(colormap.get(Gray4::new(7)), Bgr444::new(7, 7, 7));
+/// ```
+#[proc_macro_derive(RgbColorMath)]
+pub fn rgb_color_math(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    color::color_math_impl(input, ColorModel::Rgb).into()
+}
+
+/// Implements the color math of [`mplusfonts`](../mplusfonts/index.html) for a grayscale color
+/// type.
+///
+/// A custom color type that implements
+/// [`GrayColor`](../embedded_graphics/pixelcolor/trait.GrayColor.html) and has an associated
+/// function `new(luma: u8) -> Self`, as do the color types of `embedded-graphics`, gets the same
+/// implementations of `Linear`, `Invert`, `Opacity`, `Screen`, `WeightedAvg`, and of the other
+/// blend modes, which makes it usable in a
+/// [`BitmapFontStyle`](../mplusfonts/style/struct.BitmapFontStyle.html) if it also implements
+/// [`Default`].
+#[proc_macro_derive(GrayColorMath)]
+pub fn gray_color_math(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    color::color_math_impl(input, ColorModel::Gray).into()
+}
@@ -2,8 +2,8 @@ use syn::parse::{Parse, ParseStream};
 use syn::punctuated::{Pair, Punctuated};
 use syn::{Token, parse};
 
-use super::CharSource;
 use super::font::{Font, FontSize, FontWeight};
+use super::{CharSource, ExprPathExt};
 
 pub struct Arguments {
     pub font: Pair<Font, Token![,]>,
@@ -12,6 +12,7 @@ pub struct Arguments {
     pub hint: Pair<bool, Token![,]>,
     pub positions: Pair<u8, Token![,]>,
     pub bit_depth: Pair<u8, Token![,]>,
+    pub is_paged: bool,
     pub sources: Punctuated<CharSource, Token![,]>,
 }
 
@@ -22,7 +23,7 @@ impl Parse for Arguments {
         let size = Pair::new(input.call(parse_size)?, input.parse()?);
         let hint = Pair::new(input.call(parse_bool)?, input.parse()?);
         let positions = Pair::new(input.call(parse_u8_in_range::<1, 16>)?, input.parse()?);
-        let bit_depth = input.call(parse_u8_in_set::<1, 2, 4, 8>)?;
+        let (bit_depth, is_paged) = input.call(parse_bit_depth)?;
         let lookahead = input.lookahead1();
         if lookahead.peek(Token![,]) || lookahead.peek(parse::End) {
             let bit_depth = Pair::new(bit_depth, input.parse()?);
@@ -34,6 +35,7 @@ impl Parse for Arguments {
                 hint,
                 positions,
                 bit_depth,
+                is_paged,
                 sources,
             };
 
@@ -73,6 +75,53 @@ fn parse_bool(input: ParseStream) -> syn::Result<bool> {
     Ok(value)
 }
 
+fn parse_bit_depth(input: ParseStream) -> syn::Result<(u8, bool)> {
+    if !input.peek(syn::Ident) {
+        let value = parse_u8_in_set::<1, 2, 4, 8>(input)?;
+
+        return Ok((value, false));
+    }
+
+    let expr_call: syn::ExprCall = input.parse()?;
+    let syn::Expr::Path(expr_path) = *expr_call.func else {
+        let message = "expected identifier";
+        return Err(syn::Error::new_spanned(expr_call.func, message));
+    };
+
+    let ident = expr_path.try_into_ident()?;
+    let name = ident.to_string();
+    if name != "paged" {
+        let message = format!("expected identifier `paged`, found `{name}`");
+        return Err(syn::Error::new(ident.span(), message));
+    }
+
+    let mut exprs = expr_call.args.into_iter();
+    let Some(first) = exprs.next() else {
+        let message = "expected 1 argument, found 0";
+        return Err(syn::Error::new(expr_call.paren_token.span.join(), message));
+    };
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Int(lit_int),
+        ..
+    }) = first
+    else {
+        let message = "expected integer literal";
+        return Err(syn::Error::new_spanned(first, message));
+    };
+    let value = lit_int.base10_parse()?;
+    if value != 1 {
+        let message = format!("expected `1`, found `{value}`");
+        return Err(syn::Error::new(lit_int.span(), message));
+    }
+
+    if let Some(second) = exprs.next() {
+        let message = "remove the extra argument";
+        return Err(syn::Error::new_spanned(second, message));
+    }
+
+    Ok((value, true))
+}
+
 fn parse_u8_in_range<const MIN: u8, const MAX: u8>(input: ParseStream) -> syn::Result<u8> {
     let lit_int: syn::LitInt = input.parse()?;
     let value = lit_int.base10_parse()?;
@@ -53,6 +53,25 @@ pub fn quantize(image_data: &[u8], image_width: u32, bit_depth: u8) -> Vec<u8> {
     bytes
 }
 
+pub fn paginate(image_data: &[u8], image_width: u32) -> Vec<u8> {
+    let width = image_width as usize;
+    let bytes_per_row = width.div_ceil(8);
+    let Some(height) = image_data.len().checked_div(bytes_per_row) else {
+        return Vec::new();
+    };
+
+    let mut bytes = vec![0; height.div_ceil(8) * width];
+    for (y, row_data) in image_data.chunks_exact(bytes_per_row).enumerate() {
+        let page_data = &mut bytes[y / 8 * width..][..width];
+        for (x, byte) in page_data.iter_mut().enumerate() {
+            let bit = (row_data[x / 8] >> (7 - x % 8)) & 1;
+            *byte |= bit << (y % 8);
+        }
+    }
+
+    bytes
+}
+
 const fn downsample(value: u8, divisor: u8) -> u8 {
     const SHIFT: usize = 23;
     const CONST_0_5: i32 = 1 << (SHIFT - 1);
@@ -86,6 +105,32 @@ mod tests {
         }
     }
 
+    macro_rules! test_paginate {
+        (
+            $(
+                $fn_ident:ident, $image_data:expr, $image_width:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = paginate(&$image_data, $image_width);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_paginate! {
+        paginate_empty, [], 0, [],
+        paginate_single_pixel, [0b1000_0000], 1, [0b0000_0001],
+        paginate_single_row, [0b1010_0000], 3, [0b1, 0b0, 0b1],
+        paginate_single_column,
+            [0x80, 0x00, 0x80, 0x80, 0x00, 0x00, 0x00, 0x80, 0x80], 1, [0b1000_1101, 0b1],
+        paginate_partial_page, [0b1100_0000, 0b0100_0000, 0b1000_0000], 2, [0b101, 0b011],
+        paginate_wide_row, [0xFF, 0x80], 9, [0b1; 9],
+    }
+
     test_downsample! {
         downsample_255_to_4bpp, 255, 17, 15,
         downsample_128_to_4bpp, 128, 17, 8,
@@ -2,13 +2,16 @@ use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 
 use crate::mplus::charmap::{Charmap, CharmapEntry};
+use crate::mplus::scalar::Scalar;
 
 pub struct BitmapFont {
     pub charmap: Charmap,
     pub notdef: CharmapEntry,
     pub positions: u8,
     pub bit_depth: u8,
+    pub is_paged: bool,
     pub size: f32,
+    pub halfwidth: f32,
     pub is_code: bool,
 }
 
@@ -19,12 +22,15 @@ impl ToTokens for BitmapFont {
             notdef,
             positions,
             bit_depth,
+            is_paged,
             size,
+            halfwidth,
             is_code,
         } = self;
 
         let positions = *positions as usize;
         let params = match bit_depth {
+            1 if *is_paged => quote!(::mplusfonts::page::PageSegment, #positions),
             1 => quote!(::embedded_graphics::pixelcolor::BinaryColor, #positions),
             2 => quote!(::embedded_graphics::pixelcolor::Gray2, #positions),
             4 => quote!(::embedded_graphics::pixelcolor::Gray4, #positions),
@@ -32,7 +38,7 @@ impl ToTokens for BitmapFont {
             x => panic!("expected one of: `1`, `2`, `4`, `8`; found: `{x}`"),
         };
         let charmap = charmap_tokens(charmap, notdef, &params);
-        let metrics = metrics_tokens(*size, *is_code);
+        let metrics = metrics_tokens(*size, *halfwidth, *is_code);
         let underline = underline_tokens(*size);
         let strikethrough = strikethrough_tokens(*size);
         let font = quote! {
@@ -83,7 +89,7 @@ fn charmap_tokens(charmap: &Charmap, notdef: &CharmapEntry, params: &impl ToToke
     branch
 }
 
-fn metrics_tokens(size: f32, is_code: bool) -> TokenStream {
+fn metrics_tokens(size: f32, halfwidth: f32, is_code: bool) -> TokenStream {
     let top = size * if is_code { 1.235 } else { 1.16 };
     let ascender = size * if is_code { 1.0 } else { 0.88 };
     let cap_height = size * 0.73;
@@ -91,24 +97,38 @@ fn metrics_tokens(size: f32, is_code: bool) -> TokenStream {
     let baseline = 0f32;
     let descender = size * if is_code { -0.235 } else { -0.12 };
     let bottom = size * if is_code { -0.27 } else { -0.288 };
+    let [
+        top,
+        ascender,
+        cap_height,
+        x_height,
+        baseline,
+        descender,
+        bottom,
+        halfwidth,
+    ] = [
+        top, ascender, cap_height, x_height, baseline, descender, bottom, halfwidth,
+    ]
+    .map(Scalar);
     let metrics = quote! {
-        ::mplusfonts::BitmapFontMetrics {
-            top: #top,
-            ascender: #ascender,
-            cap_height: #cap_height,
-            x_height: #x_height,
-            baseline: #baseline,
-            descender: #descender,
-            bottom: #bottom,
-        }
+        ::mplusfonts::BitmapFontMetrics::new(
+            #top,
+            #ascender,
+            #cap_height,
+            #x_height,
+            #baseline,
+            #descender,
+            #bottom,
+            #halfwidth,
+        )
     };
 
     metrics
 }
 
 fn underline_tokens(size: f32) -> TokenStream {
-    let offset = size * -0.1;
-    let height = size * 0.05;
+    let offset = Scalar(size * -0.1);
+    let height = Scalar(size * 0.05);
     let dimensions = quote! {
         ::mplusfonts::DecorationDimensions {
             offset: #offset,
@@ -120,8 +140,8 @@ fn underline_tokens(size: f32) -> TokenStream {
 }
 
 fn strikethrough_tokens(size: f32) -> TokenStream {
-    let offset = size * 0.312;
-    let height = size * 0.05;
+    let offset = Scalar(size * 0.312);
+    let height = Scalar(size * 0.05);
     let dimensions = quote! {
         ::mplusfonts::DecorationDimensions {
             offset: #offset,
@@ -96,6 +96,7 @@ impl GlyphOffsets {
             y_offset: y_offset - y_offset.fract(),
             positions,
             bit_depth,
+            is_paged: false,
             id: self.id,
             advance_width: new_advance_width,
             images: ImageList(images),
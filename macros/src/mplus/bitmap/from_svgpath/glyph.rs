@@ -70,6 +70,7 @@ impl GlyphDrawing {
             y_offset: 0.0,
             positions,
             bit_depth,
+            is_paged: false,
             id,
             advance_width,
             images: ImageList(images),
@@ -81,6 +82,7 @@ impl GlyphDrawing {
             y_offset: 0.0,
             positions,
             bit_depth,
+            is_paged: false,
             id,
             advance_width: 0.0,
             images: ImageList(images),
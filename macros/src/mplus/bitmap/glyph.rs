@@ -3,12 +3,14 @@ use quote::{ToTokens, quote};
 use swash::GlyphId;
 
 use crate::mplus::bitmap::ImageList;
+use crate::mplus::scalar::Scalar;
 
 pub struct Glyph {
     pub x_offset: f32,
     pub y_offset: f32,
     pub positions: u8,
     pub bit_depth: u8,
+    pub is_paged: bool,
     pub id: GlyphId,
     pub advance_width: f32,
     pub images: ImageList,
@@ -16,6 +18,22 @@ pub struct Glyph {
 
 pub struct GlyphList(pub Vec<Glyph>);
 
+impl Glyph {
+    pub fn paginate(&mut self) {
+        debug_assert_eq!(1, self.bit_depth, "expected glyph with 1 bit per pixel");
+
+        self.is_paged = true;
+        self.images.paginate();
+    }
+}
+
+impl GlyphList {
+    pub fn paginate(&mut self) {
+        let Self(vec) = self;
+        vec.iter_mut().for_each(Glyph::paginate);
+    }
+}
+
 impl ToTokens for GlyphList {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self(vec) = self;
@@ -26,6 +44,7 @@ impl ToTokens for GlyphList {
                 y_offset,
                 positions,
                 bit_depth,
+                is_paged,
                 id,
                 advance_width: _,
                 images,
@@ -33,7 +52,8 @@ impl ToTokens for GlyphList {
 
             let next_glyph = match previous_glyph.take() {
                 Some((x_offset, y_offset, glyph)) => {
-                    let value = next_glyph(x_offset, y_offset, *positions, *bit_depth, glyph);
+                    let value =
+                        next_glyph(x_offset, y_offset, *positions, *bit_depth, *is_paged, glyph);
 
                     quote!(Some(#value))
                 }
@@ -72,16 +92,20 @@ fn next_glyph(
     y_offset: f32,
     positions: u8,
     bit_depth: u8,
+    is_paged: bool,
     glyph: impl ToTokens,
 ) -> impl ToTokens {
     let positions = positions as usize;
     let params = match bit_depth {
+        1 if is_paged => quote!(::mplusfonts::page::PageSegment, #positions),
         1 => quote!(::embedded_graphics::pixelcolor::BinaryColor, #positions),
         2 => quote!(::embedded_graphics::pixelcolor::Gray2, #positions),
         4 => quote!(::embedded_graphics::pixelcolor::Gray4, #positions),
         8 => quote!(::embedded_graphics::pixelcolor::Gray8, #positions),
         x => panic!("expected one of: `1`, `2`, `4`, `8`; found: `{x}`"),
     };
+    let x_offset = Scalar(x_offset);
+    let y_offset = Scalar(y_offset);
     let next_glyph = quote! {
         ::mplusfonts::glyph::NextGlyph {
             x_offset: #x_offset,
@@ -1,6 +1,8 @@
 use proc_macro2::{Literal, TokenStream};
 use quote::{ToTokens, quote};
 
+use crate::mplus::bitmap::color;
+
 pub struct Image {
     pub left: i32,
     pub top: i32,
@@ -10,6 +12,19 @@ pub struct Image {
 
 pub struct ImageList(pub Vec<Image>);
 
+impl Image {
+    pub fn paginate(&mut self) {
+        self.data = color::paginate(&self.data, self.width);
+    }
+}
+
+impl ImageList {
+    pub fn paginate(&mut self) {
+        let Self(vec) = self;
+        vec.iter_mut().for_each(Image::paginate);
+    }
+}
+
 impl ToTokens for Image {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
@@ -12,3 +12,4 @@ pub use dict::{CharDictionary, CharDictionaryKey};
 pub use font::BitmapFont;
 pub use glyph::{Glyph, GlyphList};
 pub use image::{Image, ImageList};
+pub use units::Halfwidth;
@@ -4,6 +4,7 @@ use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 
 use crate::mplus::bitmap::GlyphList;
+use crate::mplus::scalar::Scalar;
 
 pub struct CharmapEntry {
     pub key: String,
@@ -23,7 +24,11 @@ impl ToTokens for CharmapEntry {
             glyphs,
         } = self;
 
-        let map = map.iter().map(|(key, value)| quote!(#key => #value));
+        let map = map.iter().map(|(key, value)| {
+            let value = Scalar(*value);
+            quote!(#key => #value)
+        });
+        let default = Scalar(*default);
         let advance_width_to = quote! {
             |key| match key {
                 #(#map,)*
@@ -3,11 +3,12 @@ mod bitmap;
 mod charmap;
 mod expr;
 mod font;
+mod scalar;
 mod source;
 
 use std::collections::BTreeMap;
 
-use bitmap::BitmapFont;
+use bitmap::{BitmapFont, Halfwidth};
 use charmap::Charmap;
 use expr::ExprPathExt;
 use font::Font;
@@ -26,18 +27,33 @@ pub fn mplus_impl(mut args: Arguments) -> TokenStream {
     entries.extend(bitmap::from_outline::render(&args, false));
     entries.extend(bitmap::from_svgpath::render(&args));
 
+    let is_paged = args.is_paged;
+    if is_paged {
+        for entry in entries.values_mut() {
+            entry.glyphs.paginate();
+        }
+    }
+
     let notdef = entries.remove("\u{FFFD}").expect("expected `\u{FFFD}`");
     let charmap = Charmap::from_iter(entries);
     let positions = args.positions.into_value();
     let bit_depth = args.bit_depth.into_value();
     let size = args.size.into_value();
-    let is_code = matches!(args.font.into_value(), Font::MPLUSCode { .. });
+    let font = args.font.value();
+    let is_code = matches!(font, Font::MPLUSCode { .. });
+    let halfwidth = match Halfwidth::from_font(font, size) {
+        Halfwidth::Floor(halfwidth) => halfwidth,
+        Halfwidth::Ceil => 1.0,
+        Halfwidth::Zero => 0.0,
+    };
     let font = BitmapFont {
         charmap,
         notdef,
         positions,
         bit_depth,
+        is_paged,
         size,
+        halfwidth,
         is_code,
     };
 
@@ -0,0 +1,19 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::{ToTokens, quote};
+
+pub struct Scalar(pub f32);
+
+impl ToTokens for Scalar {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self(value) = *self;
+        let scalar = if cfg!(feature = "fixed-point") {
+            let bits = Literal::i32_suffixed((value * 65536.0).round() as i32);
+
+            quote!(::mplusfonts::Scalar::from_bits(#bits))
+        } else {
+            quote!(::mplusfonts::Scalar::from_f32(#value))
+        };
+
+        scalar.to_tokens(tokens);
+    }
+}
@@ -5,8 +5,8 @@ use std::fs::File;
 use std::io::Write;
 
 use embedded_graphics::prelude::*;
-use mplusfonts::CharmapEntry;
 use mplusfonts::image::Colors;
+use mplusfonts::{CharmapEntry, Scalar};
 use mplusfonts_macros::mplus;
 use seq_macro::seq;
 
@@ -31,7 +31,7 @@ macro_rules! bless_render_glyphs {
                     let CharmapEntry {
                         advance_width_to, ..
                     } = *entry;
-                    let advance_widths = text_fragments.map(advance_width_to);
+                    let advance_widths = text_fragments.map(advance_width_to).map(Scalar::to_f32);
                     write!(expected_data, "\n        // {slice:?}")?;
                     write!(expected_data, "\n        &[")?;
 
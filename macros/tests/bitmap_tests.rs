@@ -1,9 +1,9 @@
 #![cfg(not(feature = "bless-tests"))]
 
 use embedded_graphics::prelude::*;
-use mplusfonts::CharmapEntry;
 use mplusfonts::glyph::Glyph;
 use mplusfonts::image::Colors;
+use mplusfonts::{CharmapEntry, Scalar};
 use mplusfonts_macros::mplus;
 use seq_macro::seq;
 
@@ -45,9 +45,14 @@ macro_rules! test_render_glyphs {
                     let (advance_widths, gray_values) = tuple;
                     for (slice, expected) in text_fragments.into_iter().zip(advance_widths) {
                         let result = advance_width_to(slice);
+                        let expected = if cfg!(feature = "fixed-point") {
+                            Scalar::from_bits((*expected * 65536.0).round() as i32)
+                        } else {
+                            Scalar::from_f32(*expected)
+                        };
                         assert_eq!(
                             result,
-                            *expected,
+                            expected,
                             "\n slice: {slice:?}",
                             slice = key.to_owned() + slice
                         );
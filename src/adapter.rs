@@ -1,27 +1,37 @@
+use core::iter;
+
 use embedded_graphics::Pixel;
 use embedded_graphics::draw_target::DrawTarget;
-use embedded_graphics::geometry::Dimensions;
-use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8};
-use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::geometry::{Dimensions, Point};
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, GrayColor};
+use embedded_graphics::primitives::{PointsIter, Rectangle};
+
+use crate::color::{Colormap, Dithering};
 
-use crate::color::Colormap;
+/// The thresholds of ordered dithering, as a 4×4 Bayer matrix.
+const BAYER_MATRIX: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
 
 /// Adapter draw target using a colormap.
 ///
 /// This draw target uses a lookup table to get colors of the type that is expected by another draw
-/// target.
+/// target, optionally dithering the gray values in between the distinct colors of the colormap.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ColormapAdapter<'a, D: DrawTarget, const N: usize> {
     parent: &'a mut D,
     colormap: &'a Colormap<D::Color, N>,
+    dithering: Dithering,
+    levels: usize,
+    error: Option<(Point, i32)>,
 }
 
 /// Extension trait for draw targets.
 pub trait DrawTargetExt: DrawTarget + Sized {
-    /// Returns an adapter for the draw target with the specified colormap, for mapping gray values.
+    /// Returns an adapter for the draw target with the specified colormap, for mapping gray values,
+    /// and the specified dithering.
     fn value_mapped<'a, const N: usize>(
         &'a mut self,
         colormap: &'a Colormap<Self::Color, N>,
+        dithering: Dithering,
     ) -> ColormapAdapter<'a, Self, N>;
 }
 
@@ -29,18 +39,121 @@ impl<D: DrawTarget> DrawTargetExt for D {
     fn value_mapped<'a, const N: usize>(
         &'a mut self,
         colormap: &'a Colormap<D::Color, N>,
+        dithering: Dithering,
     ) -> ColormapAdapter<'a, D, N> {
-        ColormapAdapter::new(self, colormap)
+        ColormapAdapter::new(self, colormap).with_dithering(dithering)
     }
 }
 
 impl<'a, D: DrawTarget, const N: usize> ColormapAdapter<'a, D, N> {
     /// Creates a new adapter draw target with the specified parent draw target and colormap.
     pub const fn new(parent: &'a mut D, colormap: &'a Colormap<D::Color, N>) -> Self {
-        Self { parent, colormap }
+        Self {
+            parent,
+            colormap,
+            dithering: Dithering::None,
+            levels: N,
+            error: None,
+        }
+    }
+
+    /// Returns the adapter draw target with the specified dithering, which has no effect if the
+    /// colormap has as many distinct colors as there are gray values.
+    pub fn with_dithering(mut self, dithering: Dithering) -> Self {
+        self.levels = self.colormap.levels();
+        self.dithering = if self.levels < N {
+            dithering
+        } else {
+            Dithering::None
+        };
+
+        self
     }
 }
 
+/// Returns the color for the specified gray value, as an index into the colormap, at the specified
+/// point, with the specified dithering, carrying over the error to the next point.
+fn dithered<T: Copy + PartialEq, const N: usize>(
+    colormap: &Colormap<T, N>,
+    dithering: Dithering,
+    levels: usize,
+    error: &mut Option<(Point, i32)>,
+    index: usize,
+    point: Point,
+) -> T {
+    let level = match dithering {
+        Dithering::None => return colormap.get_level(index, N),
+        Dithering::Ordered => {
+            let row = BAYER_MATRIX[point.y.rem_euclid(4) as usize];
+            let threshold = row[point.x.rem_euclid(4) as usize];
+
+            ordered_level::<N>(index, levels, threshold)
+        }
+        Dithering::ErrorDiffusion => {
+            let carried_error = match *error {
+                Some((next_point, error)) if next_point == point => error,
+                _ => 0,
+            };
+
+            let (level, remaining_error) = diffused_level::<N>(index, levels, carried_error);
+            *error = Some((point + Point::new(1, 0), remaining_error));
+
+            level
+        }
+    };
+
+    colormap.get_level(level, levels)
+}
+
+/// Returns the level for the specified gray value, out of the specified number of levels, rounding
+/// up if the remainder exceeds the specified threshold from a 4×4 Bayer matrix.
+const fn ordered_level<const N: usize>(index: usize, levels: usize, threshold: u8) -> usize {
+    if N < 2 || levels < 2 {
+        return 0;
+    }
+
+    let value = index * (levels - 1);
+    let level = value / (N - 1);
+    let remainder = value % (N - 1);
+    if remainder * 32 > (threshold as usize * 2 + 1) * (N - 1) {
+        level + 1
+    } else {
+        level
+    }
+}
+
+/// Returns the level nearest to the specified gray value plus the specified error, out of the
+/// specified number of levels, along with the error that remains, in units of the gray value
+/// times the number of levels minus one.
+const fn diffused_level<const N: usize>(index: usize, levels: usize, error: i32) -> (usize, i32) {
+    if N < 2 || levels < 2 {
+        return (0, 0);
+    }
+
+    let step = (N - 1) as i32;
+    let max_level = (levels - 1) as i32;
+    let value = index as i32 * max_level + error;
+    let level = (value + step / 2).div_euclid(step);
+    let level = if level < 0 {
+        0
+    } else if level > max_level {
+        max_level
+    } else {
+        level
+    };
+
+    let error = value - level * step;
+    let error = if error < -step {
+        -step
+    } else if error > step {
+        step
+    } else {
+        error
+    };
+
+    (level as usize, error)
+}
+
 impl<D: DrawTarget, const N: usize> Dimensions for ColormapAdapter<'_, D, N> {
     fn bounding_box(&self) -> Rectangle {
         self.parent.bounding_box()
@@ -50,7 +163,7 @@ impl<D: DrawTarget, const N: usize> Dimensions for ColormapAdapter<'_, D, N> {
 macro_rules! impl_draw_target {
     (
         $(
-            $color_type:ty, $array_length:literal,
+            $color_ident:ident, $color_type:ty, $array_length:literal, $into_index:expr,
         )*
     ) => {
         $(
@@ -63,28 +176,62 @@ macro_rules! impl_draw_target {
                 where
                     I: IntoIterator<Item = Pixel<Self::Color>>
                 {
-                    let pixels = pixels.into_iter().map(|Pixel(pixel, color)| {
-                        Pixel(pixel, self.colormap.get(color))
+                    if self.dithering == Dithering::None {
+                        let pixels = pixels.into_iter().map(|Pixel(pixel, color)| {
+                            Pixel(pixel, self.colormap.get(color))
+                        });
+
+                        return self.parent.draw_iter(pixels);
+                    }
+
+                    let Self {
+                        parent,
+                        colormap,
+                        dithering,
+                        levels,
+                        error,
+                    } = self;
+
+                    let pixels = pixels.into_iter().map(|Pixel(point, $color_ident)| {
+                        let index: usize = $into_index.into();
+                        let color = dithered(colormap, *dithering, *levels, error, index, point);
+
+                        Pixel(point, color)
                     });
 
-                    self.parent.draw_iter(pixels)
+                    parent.draw_iter(pixels)
                 }
 
                 fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I)-> Result<(), Self::Error>
                 where
                     I: IntoIterator<Item = Self::Color>
                 {
-                    let colors = colors.into_iter().map(|color| self.colormap.get(color));
+                    if self.dithering == Dithering::None {
+                        let colors = colors.into_iter().map(|color| self.colormap.get(color));
 
-                    self.parent.fill_contiguous(area, colors)
+                        return self.parent.fill_contiguous(area, colors);
+                    }
+
+                    let pixels = area.points().zip(colors);
+                    let pixels = pixels.map(|(point, color)| Pixel(point, color));
+
+                    self.draw_iter(pixels)
                 }
 
                 fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
-                    self.parent.fill_solid(area, self.colormap.get(color))
+                    if self.dithering == Dithering::None {
+                        return self.parent.fill_solid(area, self.colormap.get(color));
+                    }
+
+                    self.fill_contiguous(area, iter::repeat(color))
                 }
 
                 fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
-                    self.parent.clear(self.colormap.get(color))
+                    if self.dithering == Dithering::None {
+                        return self.parent.clear(self.colormap.get(color));
+                    }
+
+                    self.fill_solid(&self.bounding_box(), color)
                 }
             }
         )*
@@ -92,8 +239,79 @@ macro_rules! impl_draw_target {
 }
 
 impl_draw_target! {
-    BinaryColor, 2,
-    Gray2, 4,
-    Gray4, 16,
-    Gray8, 256,
+    color, BinaryColor, 2, color.is_on(),
+    color, Gray2, 4, color.luma(),
+    color, Gray4, 16, color.luma(),
+    color, Gray8, 256, color.luma(),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_ordered_level {
+        (
+            $(
+                $fn_ident:ident,
+                $n:expr,
+                $index:expr,
+                $levels:expr,
+                $threshold:expr,
+                $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = ordered_level::<$n>($index, $levels, $threshold);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_ordered_level! {
+        ordered_level_4bpp_0_on_2_levels, 16, 0, 2, 0, 0,
+        ordered_level_4bpp_15_on_2_levels, 16, 15, 2, 15, 1,
+        ordered_level_4bpp_1_on_2_levels_below_threshold, 16, 1, 2, 1, 0,
+        ordered_level_4bpp_1_on_2_levels_above_threshold, 16, 1, 2, 0, 1,
+        ordered_level_4bpp_8_on_2_levels_below_threshold, 16, 8, 2, 8, 1,
+        ordered_level_4bpp_8_on_2_levels_above_threshold, 16, 8, 2, 9, 0,
+        ordered_level_8bpp_128_on_4_levels_below_threshold, 256, 128, 4, 7, 2,
+        ordered_level_8bpp_128_on_4_levels_above_threshold, 256, 128, 4, 8, 1,
+        ordered_level_8bpp_255_on_4_levels, 256, 255, 4, 15, 3,
+        ordered_level_4bpp_8_on_1_level, 16, 8, 1, 0, 0,
+    }
+
+    macro_rules! test_diffused_level {
+        (
+            $(
+                $fn_ident:ident, $n:expr, $indices:expr, $levels:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut error = 0;
+                    let result = $indices.map(|index| {
+                        let (level, remaining_error) = diffused_level::<$n>(index, $levels, error);
+                        error = remaining_error;
+
+                        level
+                    });
+
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_diffused_level! {
+        diffused_level_4bpp_0_on_2_levels, 16, [0; 4], 2, [0; 4],
+        diffused_level_4bpp_15_on_2_levels, 16, [15; 4], 2, [1; 4],
+        diffused_level_4bpp_8_on_2_levels, 16, [8; 6], 2, [1, 0, 1, 0, 1, 0],
+        diffused_level_4bpp_4_on_2_levels, 16, [4; 8], 2, [0, 1, 0, 0, 0, 1, 0, 0],
+        diffused_level_8bpp_64_on_4_levels, 256, [64; 4], 4, [1, 1, 0, 1],
+        diffused_level_4bpp_8_on_1_level, 16, [8; 2], 1, [0; 2],
+    }
 }
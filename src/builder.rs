@@ -3,7 +3,7 @@ use embedded_graphics::pixelcolor::raw::BigEndian;
 use embedded_graphics::pixelcolor::{BinaryColor, PixelColor};
 use embedded_graphics::text::DecorationColor;
 
-use crate::color::{Invert, Screen, WeightedAvg};
+use crate::color::{BlendMode, Dithering, Gradient, Interpolation, Invert, Screen, WeightedAvg};
 use crate::font::BitmapFont;
 use crate::style::BitmapFontStyle;
 
@@ -49,6 +49,36 @@ where
         self
     }
 
+    /// Resets the opacity factor, making the text and decoration colors fully opaque.
+    pub const fn reset_opacity(mut self) -> Self {
+        self.style.opacity = u8::MAX;
+        self
+    }
+
+    /// Resets the interpolation, interpolating the encoded color components.
+    pub const fn reset_interpolation(mut self) -> Self {
+        self.style.interpolation = Interpolation::Encoded;
+        self
+    }
+
+    /// Removes the gradient, using the gradient from the background color to the text color.
+    pub const fn reset_gradient(mut self) -> Self {
+        self.style.gradient = None;
+        self
+    }
+
+    /// Resets the blend mode, mixing the colors of overlapping glyph images in screen blend mode.
+    pub const fn reset_blend_mode(mut self) -> Self {
+        self.style.blend_mode = BlendMode::Screen;
+        self
+    }
+
+    /// Resets the dithering, mapping every gray value to the nearest color.
+    pub const fn reset_dithering(mut self) -> Self {
+        self.style.dithering = Dithering::None;
+        self
+    }
+
     /// Enables underline using the text color.
     pub const fn underline(mut self) -> Self {
         self.style.underline_color = DecorationColor::TextColor;
@@ -85,12 +115,47 @@ where
         self
     }
 
+    /// Sets the opacity factor to the specified value, fading the text and decoration colors
+    /// towards the background color.
+    pub const fn opacity(mut self, opacity: u8) -> Self {
+        self.style.opacity = opacity;
+        self
+    }
+
+    /// Sets the interpolation of the colors in between the background color and the text color to
+    /// the specified value.
+    pub const fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.style.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the gradient for the gray values of glyph images to the specified value, in place of
+    /// the gradient from the background color to the text color.
+    pub const fn gradient(mut self, gradient: Gradient<'b, T>) -> Self {
+        self.style.gradient = Some(gradient);
+        self
+    }
+
+    /// Sets the blend mode for mixing the colors of overlapping glyph images to the specified
+    /// value.
+    pub const fn blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.style.blend_mode = blend_mode;
+        self
+    }
+
+    /// Sets the dithering of the gray values of glyph images to the specified value.
+    pub const fn dithering(mut self, dithering: Dithering) -> Self {
+        self.style.dithering = dithering;
+        self
+    }
+
     /// Consumes the builder, returning a new one that is using the specified bitmap font.
     pub const fn font<'z, D, const M: usize>(
         self,
         font: &'z BitmapFont<'a, D, M>,
     ) -> BitmapFontStyleBuilder<'a, 'z, T, D, M>
     where
+        'b: 'z,
         D: PixelColor + From<D::Raw>,
         RawDataSlice<'a, D::Raw, BigEndian>: IntoIterator<Item = D::Raw>,
     {
@@ -100,6 +165,11 @@ where
         style.background_color = self.style.background_color;
         style.underline_color = self.style.underline_color;
         style.strikethrough_color = self.style.strikethrough_color;
+        style.opacity = self.style.opacity;
+        style.interpolation = self.style.interpolation;
+        style.gradient = self.style.gradient;
+        style.blend_mode = self.style.blend_mode;
+        style.dithering = self.style.dithering;
 
         BitmapFontStyleBuilder { style }
     }
@@ -3,6 +3,7 @@ use embedded_graphics::pixelcolor::PixelColor;
 use embedded_graphics::pixelcolor::raw::BigEndian;
 
 use crate::glyph::Glyph;
+use crate::scalar::Scalar;
 
 /// Key that is unique to a charmap entry in a bitmap font.
 pub type CharmapEntryKey<'a> = &'a str;
@@ -22,7 +23,7 @@ where
     /// A function that takes the key for the next charmap entry, returning the value for advancing
     /// the position along the _x_-axis for the charmap entry in order to get to the position of
     /// the next charmap entry.
-    pub advance_width_to: fn(CharmapEntryKey<'a>) -> f32,
+    pub advance_width_to: fn(CharmapEntryKey<'a>) -> Scalar,
     /// The glyph.
     pub glyph: Glyph<'a, C, N>,
 }
@@ -51,7 +52,7 @@ where
     pub const NULL: Self = Self {
         key: "",
         advance_chars: 0,
-        advance_width_to: |_| 0.0,
+        advance_width_to: |_| Scalar::ZERO,
         glyph: Glyph::NULL,
     };
 }
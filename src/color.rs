@@ -4,12 +4,32 @@
 //! [`BinaryColor`], [`Gray2`], [`Gray4`], or [`Gray8`]. This module provides the functions that
 //! enable downsampling, color conversion from any [`GrayColor`] to any other color type that a
 //! [`DrawTarget`](../../embedded_graphics_core/draw_target/trait.DrawTarget.html) expects,
-//! applying color settings, and mixing colors in [`Screen`] or [`WeightedAvg`] blend mode.
+//! applying color settings, fading colors with an [`Opacity`] factor, and mixing colors in
+//! [`Screen`] or [`WeightedAvg`] blend mode, or in any of the other modes that a [`BlendMode`] can
+//! select. The colors of a gradient can be interpolated in the encoding of the color type, in
+//! linear light, or in a perceptual color space; see [`Interpolation`]. A [`Gradient`] with more
+//! than two color stops, or with colors given by a lookup function, can take the place of the
+//! gradient from the background color to the text color. Where a colormap has fewer distinct
+//! colors than there are gray values, the gray values can be approximated by [`Dithering`].
+//! Indexed-color types, such as those of multi-color e-paper displays, implement [`Palette`] to
+//! have their colors mapped onto the nearest palette entries, while custom RGB and grayscale color
+//! types get the same color math as those of `embedded-graphics` by deriving
+//! [`RgbColorMath`](crate::RgbColorMath) or [`GrayColorMath`](crate::GrayColorMath).
+
+#[doc(hidden)]
+pub mod derive;
+mod palette;
+#[cfg(feature = "epd-waveshare")]
+mod waveshare;
 
 use core::array;
 
 use embedded_graphics::pixelcolor::*;
 
+pub use palette::{Palette, PaletteEntry};
+#[cfg(feature = "epd-waveshare")]
+pub use waveshare::WaveshareColor;
+
 /// Array of colors having type `T`, for lookup-table-based color conversion.
 ///
 /// The length of the array is equal to the number of gray values that can be converted.
@@ -25,6 +45,64 @@ pub struct Colormap<T: Copy, const N: usize>([T; N]);
 pub trait Linear<T> {
     /// Returns a linear gradient with the specified start and end colors.
     fn linear(start: T, end: T) -> Self;
+
+    /// Returns a gradient with the specified start and end colors, with the colors in between
+    /// interpolated as specified. Colormaps for color types that have no color components to
+    /// interpolate fall back to a linear gradient.
+    fn interpolated(start: T, end: T, interpolation: Interpolation) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = interpolation;
+
+        Self::linear(start, end)
+    }
+}
+
+/// Color interpolation, for how the colors in between the start and end colors of a gradient are
+/// calculated.
+///
+/// Interpolating the encoded color components makes anti-aliased light text on a dark background
+/// look thinner than it is, and mixing two saturated colors results in midtones that are darker
+/// and duller than either color. Interpolating in linear light or in the **OKLab** color space
+/// avoids this, at the cost of floating-point math whenever a colormap is created.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Interpolation {
+    /// Interpolation of the encoded color components, which is what [`Linear::linear`] does.
+    #[default]
+    Encoded,
+    /// Interpolation in linear light, with the color components raised to the power of the
+    /// specified gamma before interpolating, and to the power of its reciprocal afterwards; a
+    /// gamma of `2.2` is close to the transfer function of sRGB. A gamma that is not positive and
+    /// finite is taken to be `1.0`, which is the same as interpolating the encoded color
+    /// components; see [`Interpolation::gamma`] for a constructor that rejects such a gamma.
+    Gamma(f32),
+    /// Interpolation in the **OKLab** perceptual color space, with the colors taken to be in sRGB;
+    /// gray values are interpolated by their lightness.
+    Oklab,
+}
+
+/// Dithering, for how the gray values of glyph images are mapped to colors when the colormap has
+/// fewer distinct colors than there are gray values, such as when drawing a font with four bits
+/// per pixel on a monochrome display.
+///
+/// Without dithering, every gray value is mapped to the color nearest to it, which throws away the
+/// anti-aliasing. With dithering, the gray values in between two distinct colors of the colormap
+/// are approximated by a pattern of both colors, based on the absolute position of each pixel on
+/// the display; the distinct colors are taken to be evenly spaced across the gray values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Dithering {
+    /// No dithering.
+    #[default]
+    None,
+    /// Ordered dithering, with thresholds from a 4×4 Bayer matrix that is tiled across the display.
+    Ordered,
+    /// Error diffusion, with the quantization error of each pixel carried over to the pixel to its
+    /// right; the error starts over at the left edge of every row of pixels that is drawn, which is
+    /// at least once per row of each glyph image.
+    ErrorDiffusion,
 }
 
 /// Color inversion.
@@ -35,6 +113,25 @@ pub trait Invert {
     fn invert(self) -> Self;
 }
 
+/// Opacity factor.
+///
+/// A color that implements this trait can be faded towards a background color, interpolating
+/// between the two colors linearly.
+///
+/// The default implementation does not interpolate; it picks the color for opacities of `128` and
+/// up, and the background color otherwise, which is all that a color type without color components
+/// to interpolate can do.
+pub trait Opacity {
+    /// Returns the color faded towards the specified background color, where an opacity of `0`
+    /// results in the background color, and an opacity of `255` leaves the color unchanged.
+    fn with_opacity(self, background: Self, opacity: u8) -> Self
+    where
+        Self: Sized,
+    {
+        if opacity < 128 { background } else { self }
+    }
+}
+
 /// Screen blend mode.
 ///
 /// A color that implements this trait can be mixed with another color of the same type,
@@ -66,6 +163,153 @@ pub trait WeightedAvg {
     ) -> Self;
 }
 
+/// Multiply blend mode.
+///
+/// A color that implements this trait can be mixed with another color of the same type,
+/// multiplying their color components, which may only shift a given color towards black.
+///
+/// The default implementation does not mix; it leaves the color unchanged, which is all that a
+/// color type without color components to multiply can do.
+pub trait Multiply {
+    /// Mixes the color with the specified other color. White leaves the other color unchanged;
+    /// black results in black.
+    fn multiply(self, other: Self) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = other;
+
+        self
+    }
+}
+
+/// Darken blend mode.
+///
+/// A color that implements this trait can be mixed with another color of the same type, taking
+/// the darker of the two values of each color component.
+///
+/// The default implementation does not mix; it leaves the color unchanged, which is all that a
+/// color type without color components to compare can do.
+pub trait Darken {
+    /// Mixes the color with the specified other color.
+    fn darken(self, other: Self) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = other;
+
+        self
+    }
+}
+
+/// Lighten blend mode.
+///
+/// A color that implements this trait can be mixed with another color of the same type, taking
+/// the lighter of the two values of each color component.
+///
+/// The default implementation does not mix; it leaves the color unchanged, which is all that a
+/// color type without color components to compare can do.
+pub trait Lighten {
+    /// Mixes the color with the specified other color.
+    fn lighten(self, other: Self) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = other;
+
+        self
+    }
+}
+
+/// Coverage maximum blend mode.
+///
+/// A color that implements this trait can be mixed with another color of the same type, taking
+/// the value of each color component that is further away from the start color and towards the
+/// end color, as if the coverage of the two pixels were combined by taking the maximum.
+///
+/// The default implementation does not mix; it leaves the color unchanged, which is all that a
+/// color type without color components to compare can do.
+pub trait CoverageMax {
+    /// Mixes the color with the specified other color. Unlike in [`Screen`] blend mode, two
+    /// partially covered pixels never produce a color that is further towards the end color than
+    /// either color is.
+    fn coverage_max(self, other: Self, start: Self, end: Self) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = (other, start, end);
+
+        self
+    }
+}
+
+/// Blend mode, for how the colors of overlapping glyph images are mixed.
+///
+/// The [`Screen`] blend mode, which is the default, combines the coverage of two pixels the way
+/// that drawing one glyph image over the other would. [`Multiply`] and [`Darken`] suit dark text
+/// on a light background, [`Lighten`] suits light text on a dark background, and [`CoverageMax`]
+/// keeps overlapping combining marks from darkening the glyph images that they are placed on,
+/// regardless of the text and background colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BlendMode {
+    /// Mixing colors in [`Screen`] blend mode.
+    #[default]
+    Screen,
+    /// Mixing colors in [`Multiply`] blend mode.
+    Multiply,
+    /// Mixing colors in [`Darken`] blend mode.
+    Darken,
+    /// Mixing colors in [`Lighten`] blend mode.
+    Lighten,
+    /// Mixing colors in [`CoverageMax`] blend mode.
+    CoverageMax,
+}
+
+/// Selectable blend mode.
+///
+/// This trait is implemented for every color type that implements all of the traits for the
+/// blend modes that a [`BlendMode`] can select. Other than [`Screen`], these traits have default
+/// implementations, so for a color type that implements [`Screen`], empty `impl`s of the others
+/// are enough.
+pub trait Blend {
+    /// Mixes the color with the specified other color in the specified blend mode, where start and
+    /// end are the colors for no coverage and for full coverage.
+    fn blend(self, other: Self, start: Self, end: Self, blend_mode: BlendMode) -> Self;
+}
+
+impl<T: Screen + Multiply + Darken + Lighten + CoverageMax> Blend for T {
+    fn blend(self, other: Self, start: Self, end: Self, blend_mode: BlendMode) -> Self {
+        match blend_mode {
+            BlendMode::Screen => self.screen(other, start, end),
+            BlendMode::Multiply => self.multiply(other),
+            BlendMode::Darken => self.darken(other),
+            BlendMode::Lighten => self.lighten(other),
+            BlendMode::CoverageMax => self.coverage_max(other, start, end),
+        }
+    }
+}
+
+/// Color stop, a color at a position in a gradient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ColorStop<T> {
+    /// The position, as a gray value from `0`, no coverage, to `255`, full coverage.
+    pub position: u8,
+    /// The color.
+    pub color: T,
+}
+
+/// Gradient definition, for a colormap that takes the place of the gradient from the background
+/// color to the text color.
+#[derive(Debug, Clone, Copy)]
+pub enum Gradient<'s, T> {
+    /// A gradient with the specified color stops; see [`Colormap::from_stops`].
+    Stops(&'s [ColorStop<T>]),
+    /// A gradient with the colors returned by the specified function; see [`Colormap::from_fn`].
+    Lookup(fn(u8) -> T),
+}
+
 impl<T: Copy, const N: usize> Colormap<T, N> {
     /// Returns the first element of the colormap.
     pub const fn first(&self) -> T {
@@ -80,6 +324,117 @@ impl<T: Copy, const N: usize> Colormap<T, N> {
 
         array[N - 1]
     }
+
+    /// Returns a colormap with the colors returned by the specified function, which takes a gray
+    /// value that is scaled to the range from `0`, no coverage, to `255`, full coverage, so that
+    /// the same function can be used for any number of gray values.
+    pub fn from_fn<F: FnMut(u8) -> T>(mut f: F) -> Self {
+        let colors = array::from_fn(|index| f(coverage::<N>(index)));
+
+        Self(colors)
+    }
+}
+
+impl<T: Copy, const N: usize> Colormap<T, N>
+where
+    Self: Linear<T>,
+{
+    /// Returns a colormap with the specified color stops, which are expected to be in order of
+    /// their positions, or `None` if there are no color stops.
+    ///
+    /// The colors in between two color stops are interpolated as specified, the colors before the
+    /// first color stop are the color of the first color stop, and the colors after the last color
+    /// stop are the color of the last color stop. Two color stops at the same position make for a
+    /// hard transition from one color to the other.
+    pub fn from_stops(stops: &[ColorStop<T>], interpolation: Interpolation) -> Option<Self> {
+        let first = stops.first()?;
+        let last = stops.last()?;
+        let mut colors = [first.color; N];
+        for pair in stops.windows(2) {
+            let [start, end] = [pair[0], pair[1]];
+            if start.position >= end.position {
+                continue;
+            }
+
+            let Colormap(segment) = Self::interpolated(start.color, end.color, interpolation);
+            let range = (end.position - start.position) as usize;
+            for (index, color) in colors.iter_mut().enumerate() {
+                let value = coverage::<N>(index);
+                if (start.position..=end.position).contains(&value) {
+                    let offset = (value - start.position) as usize * (N - 1);
+                    *color = segment[(offset + range / 2) / range];
+                }
+            }
+        }
+
+        for (index, color) in colors.iter_mut().enumerate() {
+            if coverage::<N>(index) >= last.position {
+                *color = last.color;
+            }
+        }
+
+        Some(Self(colors))
+    }
+}
+
+impl<T: Copy> Gradient<'_, T> {
+    /// Returns the colormap for this gradient, with the colors in between color stops interpolated
+    /// as specified, or `None` if there are no color stops.
+    pub fn colormap<const N: usize>(&self, interpolation: Interpolation) -> Option<Colormap<T, N>>
+    where
+        Colormap<T, N>: Linear<T>,
+    {
+        match self {
+            Self::Stops(stops) => Colormap::from_stops(stops, interpolation),
+            Self::Lookup(f) => Some(Colormap::from_fn(f)),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format> defmt::Format for Gradient<'_, T> {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Self::Stops(stops) => defmt::write!(f, "Stops({})", stops),
+            Self::Lookup(_) => defmt::write!(f, "Lookup(..)"),
+        }
+    }
+}
+
+impl<T: Copy + PartialEq, const N: usize> Colormap<T, N> {
+    /// Returns the number of distinct colors in the colormap, counting each run of equal colors
+    /// as one.
+    pub(crate) fn levels(&self) -> usize {
+        let Colormap(array) = self;
+        let changes = array.windows(2).filter(|pair| pair[0] != pair[1]).count();
+
+        changes + 1
+    }
+
+    /// Returns the color of the specified level, out of the specified number of levels that are
+    /// taken to be evenly spaced across the colormap.
+    pub(crate) fn get_level(&self, level: usize, levels: usize) -> T {
+        let Colormap(array) = self;
+        if levels < 2 {
+            return array[0];
+        }
+
+        let index = (level * (N - 1) + (levels - 1) / 2) / (levels - 1);
+
+        array[index.min(N - 1)]
+    }
+}
+
+impl<T: Copy + Opacity, const N: usize> Colormap<T, N> {
+    /// Returns the colormap with every element faded towards the first element, the background
+    /// color, by the specified opacity factor.
+    pub fn with_opacity(&self, opacity: u8) -> Self {
+        let Colormap(array) = self;
+        let background = self.first();
+        let colors = array.map(|color| color.with_opacity(background, opacity));
+
+        Self(colors)
+    }
 }
 
 macro_rules! impl_colormap {
@@ -109,6 +464,16 @@ impl_colormap! {
     color, Gray8, 256, color.luma(),
 }
 
+/// Returns the gray value at the specified index, scaled from the range of `N` gray values to the
+/// range from `0` to `255`.
+const fn coverage<const N: usize>(index: usize) -> u8 {
+    if N < 2 {
+        return u8::MAX;
+    }
+
+    ((index * 255 + (N - 1) / 2) / (N - 1)) as u8
+}
+
 const fn convert_channel<const N: usize>(value: u8, start: u8, end: u8) -> u8 {
     const SHIFT: usize = 23;
     const CONST_0_5: i32 = 1 << (SHIFT - 1);
@@ -121,20 +486,177 @@ const fn convert_channel<const N: usize>(value: u8, start: u8, end: u8) -> u8 {
     (result >> SHIFT) as u8
 }
 
+/// Returns the color components at the specified index of a gradient with the specified start and
+/// end colors, each color component having the specified maximum value.
+fn interpolate_channels<const N: usize>(
+    index: usize,
+    start: [u8; 3],
+    end: [u8; 3],
+    max: [u8; 3],
+    interpolation: Interpolation,
+) -> [u8; 3] {
+    if index == 0 || N < 2 {
+        return start;
+    }
+
+    if index >= N - 1 {
+        return end;
+    }
+
+    let t = index as f32 / (N - 1) as f32;
+    let normalize = |channels: [u8; 3]| array::from_fn(|i| channels[i] as f32 / max[i] as f32);
+    let channels = interpolate(t, normalize(start), normalize(end), interpolation);
+
+    array::from_fn(|i| (channels[i].clamp(0.0, 1.0) * max[i] as f32 + 0.5) as u8)
+}
+
+impl Interpolation {
+    /// Returns the interpolation in linear light with the specified gamma, or `None` if the gamma
+    /// is not positive and finite.
+    pub const fn gamma(gamma: f32) -> Option<Self> {
+        if gamma > 0.0 && gamma.is_finite() {
+            Some(Self::Gamma(gamma))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the color components at the specified position of a gradient from `0.0` to `1.0`, with
+/// the color components normalized to the range from `0.0` to `1.0`.
+fn interpolate(t: f32, start: [f32; 3], end: [f32; 3], interpolation: Interpolation) -> [f32; 3] {
+    let lerp = |start: [f32; 3], end: [f32; 3]| -> [f32; 3] {
+        array::from_fn(|i| start[i] + (end[i] - start[i]) * t)
+    };
+
+    match interpolation {
+        Interpolation::Encoded => lerp(start, end),
+        Interpolation::Gamma(gamma) if !(gamma > 0.0 && gamma.is_finite()) => lerp(start, end),
+        Interpolation::Gamma(gamma) => {
+            let decode = |channels: [f32; 3]| channels.map(|c| pow(c, gamma));
+            let channels = lerp(decode(start), decode(end));
+
+            channels.map(|c| pow(c, gamma.recip()))
+        }
+        Interpolation::Oklab => {
+            let to_oklab = |channels: [f32; 3]| oklab_from_linear(channels.map(linear_from_srgb));
+            let lab = lerp(to_oklab(start), to_oklab(end));
+
+            linear_from_oklab(lab).map(|c| srgb_from_linear(c.clamp(0.0, 1.0)))
+        }
+    }
+}
+
+/// Returns the specified non-negative value raised to the specified power.
+fn pow(value: f32, power: f32) -> f32 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+
+    exp(power * ln(value))
+}
+
+/// Returns the natural logarithm of the specified positive, finite value.
+fn ln(value: f32) -> f32 {
+    const LN_2: f32 = core::f32::consts::LN_2;
+    const SQRT_2: f32 = core::f32::consts::SQRT_2;
+
+    let bits = value.to_bits();
+    let mut exponent = ((bits >> 23) & 0xFF) as i32 - 127;
+    let mut mantissa = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+    if mantissa > SQRT_2 {
+        mantissa /= 2.0;
+        exponent += 1;
+    }
+
+    let z = (mantissa - 1.0) / (mantissa + 1.0);
+    let z2 = z * z;
+    let series =
+        z * (2.0 + z2 * (2.0 / 3.0 + z2 * (2.0 / 5.0 + z2 * (2.0 / 7.0 + z2 * 2.0 / 9.0))));
+
+    exponent as f32 * LN_2 + series
+}
+
+/// Returns _e_ raised to the power of the specified value, which is expected to be in the range of
+/// normal numbers once raised.
+fn exp(value: f32) -> f32 {
+    const LN_2: f32 = core::f32::consts::LN_2;
+
+    let k = value / LN_2;
+    let k = if k < 0.0 { k - 0.5 } else { k + 0.5 } as i32;
+    let r = value - k as f32 * LN_2;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..=8 {
+        term *= r / n as f32;
+        sum += term;
+    }
+
+    let exponent = k.clamp(-126, 127);
+
+    sum * f32::from_bits(((exponent + 127) as u32) << 23)
+}
+
+/// Returns the linear-light value of the specified sRGB-encoded color component.
+fn linear_from_srgb(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        pow((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Returns the sRGB-encoded value of the specified linear-light color component.
+fn srgb_from_linear(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * pow(c, 2.4f32.recip()) - 0.055
+    }
+}
+
+/// Returns the **OKLab** coordinates of the specified linear-light sRGB color.
+fn oklab_from_linear([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = 0.41222146 * r + 0.53633255 * g + 0.051445995 * b;
+    let m = 0.2119035 * r + 0.6806995 * g + 0.10739696 * b;
+    let s = 0.08830246 * r + 0.28171885 * g + 0.6299787 * b;
+    let [l, m, s] = [l, m, s].map(|c| pow(c, 3f32.recip()));
+
+    [
+        0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+        1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+        0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+    ]
+}
+
+/// Returns the linear-light sRGB color of the specified **OKLab** coordinates.
+fn linear_from_oklab([lightness, a, b]: [f32; 3]) -> [f32; 3] {
+    let l = lightness + 0.39633778 * a + 0.21580376 * b;
+    let m = lightness - 0.105561346 * a - 0.06385417 * b;
+    let s = lightness - 0.08948418 * a - 1.2914855 * b;
+    let [l, m, s] = [l, m, s].map(|c| c * c * c);
+
+    [
+        4.0767417 * l - 3.3077116 * m + 0.23096994 * s,
+        -1.268438 * l + 2.6097574 * m - 0.34131938 * s,
+        -0.0041960864 * l - 0.7034186 * m + 1.7076147 * s,
+    ]
+}
+
 macro_rules! impl_linear_rgb {
     ($($rgb_type:ty),+) => {
         $(
             impl<const N: usize> Linear<$rgb_type> for Colormap<$rgb_type, N> {
                 fn linear(start: $rgb_type, end: $rgb_type) -> Self {
-                    let colors = array::from_fn(|index| {
-                        let r = convert_channel::<N>(index as u8, start.r(), end.r());
-                        let g = convert_channel::<N>(index as u8, start.g(), end.g());
-                        let b = convert_channel::<N>(index as u8, start.b(), end.b());
-
-                        <$rgb_type>::new(r, g, b)
-                    });
+                    derive::rgb_linear(start, end, <$rgb_type>::new)
+                }
 
-                    Self(colors)
+                fn interpolated(
+                    start: $rgb_type,
+                    end: $rgb_type,
+                    interpolation: Interpolation,
+                ) -> Self {
+                    derive::rgb_interpolated(start, end, interpolation, <$rgb_type>::new)
                 }
             }
         )*
@@ -150,13 +672,15 @@ macro_rules! impl_linear_gray {
         $(
             impl<const N: usize> Linear<$gray_type> for Colormap<$gray_type, N> {
                 fn linear(start: $gray_type, end: $gray_type) -> Self {
-                    let colors = array::from_fn(|index| {
-                        let luma = convert_channel::<N>(index as u8, start.luma(), end.luma());
-
-                        <$gray_type>::new(luma)
-                    });
+                    derive::gray_linear(start, end, <$gray_type>::new)
+                }
 
-                    Self(colors)
+                fn interpolated(
+                    start: $gray_type,
+                    end: $gray_type,
+                    interpolation: Interpolation,
+                ) -> Self {
+                    derive::gray_interpolated(start, end, interpolation, <$gray_type>::new)
                 }
             }
         )*
@@ -191,11 +715,7 @@ macro_rules! impl_invert_rgb {
         $(
             impl Invert for $rgb_type {
                 fn invert(self) -> Self {
-                    let r = <$rgb_type>::MAX_R - self.r();
-                    let g = <$rgb_type>::MAX_G - self.g();
-                    let b = <$rgb_type>::MAX_B - self.b();
-
-                    Self::new(r, g, b)
+                    derive::rgb_invert(self, Self::new)
                 }
             }
         )*
@@ -206,23 +726,19 @@ impl_invert_rgb!(
     Rgb555, Bgr555, Rgb565, Bgr565, Rgb666, Bgr666, Rgb888, Bgr888
 );
 
-impl Invert for Gray2 {
-    fn invert(self) -> Self {
-        Self::new(0b00000011 - self.luma())
-    }
-}
-
-impl Invert for Gray4 {
-    fn invert(self) -> Self {
-        Self::new(0b00001111 - self.luma())
+macro_rules! impl_invert_gray {
+    ($($gray_type:ty),+) => {
+        $(
+            impl Invert for $gray_type {
+                fn invert(self) -> Self {
+                    derive::gray_invert(self, Self::new)
+                }
+            }
+        )*
     }
 }
 
-impl Invert for Gray8 {
-    fn invert(self) -> Self {
-        Self::new(0b11111111 - self.luma())
-    }
-}
+impl_invert_gray!(Gray2, Gray4, Gray8);
 
 impl Invert for BinaryColor {
     fn invert(self) -> Self {
@@ -253,6 +769,51 @@ impl_invert_black_and_white! {
     epd_spectra::TriColor,
 }
 
+macro_rules! impl_opacity_rgb {
+    ($($rgb_type:ty),+) => {
+        $(
+            impl Opacity for $rgb_type {
+                fn with_opacity(self, background: Self, opacity: u8) -> Self {
+                    derive::rgb_with_opacity(self, background, opacity, Self::new)
+                }
+            }
+        )*
+    }
+}
+
+impl_opacity_rgb!(
+    Rgb555, Bgr555, Rgb565, Bgr565, Rgb666, Bgr666, Rgb888, Bgr888
+);
+
+macro_rules! impl_opacity_gray {
+    ($($gray_type:ty),+) => {
+        $(
+            impl Opacity for $gray_type {
+                fn with_opacity(self, background: Self, opacity: u8) -> Self {
+                    derive::gray_with_opacity(self, background, opacity, Self::new)
+                }
+            }
+        )*
+    }
+}
+
+impl_opacity_gray!(Gray2, Gray4, Gray8);
+
+macro_rules! impl_opacity_black_and_white {
+    ($($black_and_white_type:ty),+ $(,)?) => {
+        $(
+            impl Opacity for $black_and_white_type {}
+        )*
+    }
+}
+
+impl_opacity_black_and_white!(BinaryColor);
+
+#[cfg(feature = "epd-spectra")]
+impl_opacity_black_and_white! {
+    epd_spectra::TriColor,
+}
+
 const fn screen_mix_channel(first: u8, second: u8, start: u8, end: u8) -> u8 {
     const SHIFT: usize = 15;
     const CONST_0_5: i32 = 1 << (SHIFT - 1);
@@ -276,11 +837,7 @@ macro_rules! impl_screen_mix_rgb {
         $(
             impl Screen for $rgb_type {
                 fn screen(self, other: Self, start: Self, end: Self) -> Self {
-                    let r = screen_mix_channel(self.r(), other.r(), start.r(), end.r());
-                    let g = screen_mix_channel(self.g(), other.g(), start.g(), end.g());
-                    let b = screen_mix_channel(self.b(), other.b(), start.b(), end.b());
-
-                    <$rgb_type>::new(r, g, b)
+                    derive::rgb_screen(self, other, start, end, Self::new)
                 }
             }
         )*
@@ -296,13 +853,7 @@ macro_rules! impl_screen_mix_gray {
         $(
             impl Screen for $gray_type {
                 fn screen(self, other: Self, start: Self, end: Self) -> Self {
-                    let luma = screen_mix_channel(
-                        self.luma(),
-                        other.luma(),
-                        start.luma(),
-                        end.luma());
-
-                    <$gray_type>::new(luma)
+                    derive::gray_screen(self, other, start, end, Self::new)
                 }
             }
         )*
@@ -390,18 +941,9 @@ macro_rules! impl_weighted_avg_mix_rgb {
                     other_start: Self,
                     other_end: Self,
                 ) -> Self {
-                    let [r, g, b] = [Self::r, Self::g, Self::b].map(|value_of| {
-                        weighted_avg_mix_channel(
-                            value_of(&self),
-                            value_of(&other),
-                            value_of(&start),
-                            value_of(&end),
-                            value_of(&other_start),
-                            value_of(&other_end),
-                        )
-                    });
+                    let ranges = [[start, end], [other_start, other_end]];
 
-                    <$rgb_type>::new(r, g, b)
+                    derive::rgb_weighted_avg([self, other], ranges, Self::new)
                 }
             }
         )*
@@ -424,16 +966,9 @@ macro_rules! impl_weighted_avg_mix_gray {
                     other_start: Self,
                     other_end: Self,
                 ) -> Self {
-                    let luma = weighted_avg_mix_channel(
-                        self.luma(),
-                        other.luma(),
-                        start.luma(),
-                        end.luma(),
-                        other_start.luma(),
-                        other_end.luma(),
-                    );
+                    let ranges = [[start, end], [other_start, other_end]];
 
-                    <$gray_type>::new(luma)
+                    derive::gray_weighted_avg([self, other], ranges, Self::new)
                 }
             }
         )*
@@ -476,6 +1011,136 @@ impl_weighted_avg_mix_black_and_white! {
     epd_spectra::TriColor,
 }
 
+const fn multiply_mix_channel(first: u8, second: u8, max: u8) -> u8 {
+    if max == 0 {
+        return 0;
+    }
+
+    let product = first as u32 * second as u32;
+    let result = (product + max as u32 / 2) / max as u32;
+
+    result as u8
+}
+
+const fn coverage_max_mix_channel(first: u8, second: u8, start: u8, end: u8) -> u8 {
+    if start <= end {
+        if first > second { first } else { second }
+    } else if first < second {
+        first
+    } else {
+        second
+    }
+}
+
+macro_rules! impl_blend_mix_rgb {
+    ($($rgb_type:ty),+) => {
+        $(
+            impl Multiply for $rgb_type {
+                fn multiply(self, other: Self) -> Self {
+                    derive::rgb_multiply(self, other, Self::new)
+                }
+            }
+
+            impl Darken for $rgb_type {
+                fn darken(self, other: Self) -> Self {
+                    derive::rgb_darken(self, other, Self::new)
+                }
+            }
+
+            impl Lighten for $rgb_type {
+                fn lighten(self, other: Self) -> Self {
+                    derive::rgb_lighten(self, other, Self::new)
+                }
+            }
+
+            impl CoverageMax for $rgb_type {
+                fn coverage_max(self, other: Self, start: Self, end: Self) -> Self {
+                    derive::rgb_coverage_max(self, other, start, end, Self::new)
+                }
+            }
+        )*
+    }
+}
+
+impl_blend_mix_rgb!(
+    Rgb555, Bgr555, Rgb565, Bgr565, Rgb666, Bgr666, Rgb888, Bgr888
+);
+
+macro_rules! impl_blend_mix_gray {
+    ($($gray_type:ty),+) => {
+        $(
+            impl Multiply for $gray_type {
+                fn multiply(self, other: Self) -> Self {
+                    derive::gray_multiply(self, other, Self::new)
+                }
+            }
+
+            impl Darken for $gray_type {
+                fn darken(self, other: Self) -> Self {
+                    derive::gray_darken(self, other, Self::new)
+                }
+            }
+
+            impl Lighten for $gray_type {
+                fn lighten(self, other: Self) -> Self {
+                    derive::gray_lighten(self, other, Self::new)
+                }
+            }
+
+            impl CoverageMax for $gray_type {
+                fn coverage_max(self, other: Self, start: Self, end: Self) -> Self {
+                    derive::gray_coverage_max(self, other, start, end, Self::new)
+                }
+            }
+        )*
+    }
+}
+
+impl_blend_mix_gray!(Gray2, Gray4, Gray8);
+
+macro_rules! impl_blend_mix_black_and_white {
+    ($($black_and_white_type:ty: [$($color:ident),+]),+ $(,)?) => {
+        $(
+            impl Multiply for $black_and_white_type {
+                fn multiply(self, other: Self) -> Self {
+                    self.darken(other)
+                }
+            }
+
+            impl Darken for $black_and_white_type {
+                fn darken(self, other: Self) -> Self {
+                    let colors = [$(Self::$color),+];
+                    let position = |color| colors.iter().position(|&value| value == color);
+                    if position(self) <= position(other) { self } else { other }
+                }
+            }
+
+            impl Lighten for $black_and_white_type {
+                fn lighten(self, other: Self) -> Self {
+                    let colors = [$(Self::$color),+];
+                    let position = |color| colors.iter().position(|&value| value == color);
+                    if position(self) >= position(other) { self } else { other }
+                }
+            }
+
+            impl CoverageMax for $black_and_white_type {
+                fn coverage_max(self, other: Self, start: Self, end: Self) -> Self {
+                    let _ = start;
+
+                    if other == end { other } else { self }
+                }
+            }
+        )*
+    }
+}
+
+impl_blend_mix_black_and_white!(BinaryColor: [Off, On]);
+
+#[cfg(feature = "epd-spectra")]
+impl_blend_mix_black_and_white! {
+    epd_spectra::TriColor: [Black, Red, White],
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -516,6 +1181,111 @@ mod tests {
         convert_1bpp_1_to_0_255, { 2usize.pow(1) }, 1, 0, 255, 255,
     }
 
+    macro_rules! test_interpolate_channels {
+        (
+            $(
+                $fn_ident:ident,
+                $n:expr,
+                $index:expr,
+                $start:expr,
+                $end:expr,
+                $interpolation:expr,
+                $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let max = [255; 3];
+                    let result =
+                        interpolate_channels::<$n>($index, $start, $end, max, $interpolation);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_interpolate_channels! {
+        interpolate_encoded_middle, 3, 1, [0; 3], [255; 3], Interpolation::Encoded, [128; 3],
+        interpolate_gamma_1_0_middle, 3, 1, [0; 3], [255; 3], Interpolation::Gamma(1.0), [128; 3],
+        interpolate_gamma_2_2_middle, 3, 1, [0; 3], [255; 3], Interpolation::Gamma(2.2), [186; 3],
+        interpolate_gamma_2_2_start, 3, 0, [10, 20, 30], [255; 3], Interpolation::Gamma(2.2), [10, 20, 30],
+        interpolate_gamma_2_2_end, 3, 2, [0; 3], [40, 50, 60], Interpolation::Gamma(2.2), [40, 50, 60],
+        interpolate_gamma_0_middle, 3, 1, [0; 3], [255; 3], Interpolation::Gamma(0.0), [128; 3],
+        interpolate_gamma_negative_middle, 3, 1, [0; 3], [255; 3], Interpolation::Gamma(-2.2), [128; 3],
+        interpolate_gamma_nan_middle, 3, 1, [0; 3], [255; 3], Interpolation::Gamma(f32::NAN), [128; 3],
+        interpolate_gamma_infinite_middle, 3, 1, [0; 3], [255; 3], Interpolation::Gamma(f32::INFINITY), [128; 3],
+        interpolate_oklab_gray_middle, 3, 1, [0; 3], [255; 3], Interpolation::Oklab, [99; 3],
+        interpolate_oklab_red_blue_middle, 3, 1, [255, 0, 0], [0, 0, 255], Interpolation::Oklab, [140, 83, 162],
+        interpolate_oklab_yellow_blue_middle, 3, 1, [255, 255, 0], [0, 0, 255], Interpolation::Oklab, [108, 171, 199],
+        interpolate_oklab_same, 5, 2, [80, 0, 80], [80, 0, 80], Interpolation::Oklab, [80, 0, 80],
+    }
+
+    macro_rules! test_interpolation_gamma {
+        (
+            $(
+                $fn_ident:ident, $gamma:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = Interpolation::gamma($gamma);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_interpolation_gamma! {
+        interpolation_gamma_2_2, 2.2, Some(Interpolation::Gamma(2.2)),
+        interpolation_gamma_0, 0.0, None,
+        interpolation_gamma_negative, -2.2, None,
+        interpolation_gamma_nan, f32::NAN, None,
+        interpolation_gamma_infinite, f32::INFINITY, None,
+    }
+
+    macro_rules! test_from_stops {
+        (
+            $(
+                $fn_ident:ident, $stops:expr, $interpolation:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let stops = $stops.map(|(position, luma): (u8, u8)| ColorStop {
+                        position,
+                        color: Gray8::new(luma),
+                    });
+                    let colormap = Colormap::<Gray8, 4>::from_stops(&stops, $interpolation);
+                    let expected: Option<[u8; 4]> = $expected;
+                    let result = colormap.map(|colormap| {
+                        array::from_fn(|index| colormap.get(Gray2::new(index as u8)).luma())
+                    });
+                    assert_eq!(result, expected);
+                }
+            )*
+        }
+    }
+
+    test_from_stops! {
+        from_stops_empty, [], Interpolation::Encoded, None,
+        from_stops_two, [(0, 0), (255, 255)], Interpolation::Encoded, Some([0, 85, 170, 255]),
+        from_stops_three, [(0, 0), (85, 200), (255, 255)], Interpolation::Encoded, Some([0, 200, 237, 255]),
+        from_stops_hard, [(0, 0), (128, 0), (128, 255), (255, 255)], Interpolation::Encoded, Some([0, 0, 255, 255]),
+        from_stops_inset, [(85, 100), (170, 150)], Interpolation::Encoded, Some([100, 100, 150, 150]),
+        from_stops_single, [(128, 60)], Interpolation::Encoded, Some([60; 4]),
+        from_stops_gamma, [(0, 0), (255, 255)], Interpolation::Gamma(1.0), Some([0, 85, 170, 255]),
+    }
+
+    #[test]
+    fn from_fn_coverage() {
+        let colormap = Colormap::<Gray8, 4>::from_fn(Gray8::new);
+        let result: [u8; 4] = array::from_fn(|index| colormap.get(Gray2::new(index as u8)).luma());
+        assert_eq!(result, [0, 85, 170, 255]);
+    }
+
     macro_rules! test_screen_mix_channel {
         (
             $(
@@ -642,4 +1412,171 @@ mod tests {
         weighted_avg_mix_channel_0_128_on_0_0_and_128_128, 0, 128, 0, 0, 128, 128, 64,
         weighted_avg_mix_channel_0_0_on_0_0_and_0_0, 0, 0, 0, 0, 0, 0, 0,
     }
+
+    macro_rules! test_with_opacity {
+        (
+            $(
+                $fn_ident:ident, $color:expr, $background:expr, $opacity:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = $color.with_opacity($background, $opacity);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_with_opacity! {
+        with_opacity_rgb888_opaque, Rgb888::new(255, 128, 0), Rgb888::BLACK, 255, Rgb888::new(255, 128, 0),
+        with_opacity_rgb888_transparent, Rgb888::new(255, 128, 0), Rgb888::BLACK, 0, Rgb888::BLACK,
+        with_opacity_rgb888_half, Rgb888::new(255, 128, 0), Rgb888::BLACK, 128, Rgb888::new(128, 64, 0),
+        with_opacity_rgb888_half_on_white, Rgb888::BLACK, Rgb888::WHITE, 128, Rgb888::new(127, 127, 127),
+        with_opacity_gray8_quarter, Gray8::new(200), Gray8::new(100), 64, Gray8::new(125),
+        with_opacity_gray4_opaque, Gray4::new(15), Gray4::new(0), 255, Gray4::new(15),
+        with_opacity_gray4_half, Gray4::new(15), Gray4::new(0), 128, Gray4::new(8),
+        with_opacity_gray2_transparent, Gray2::new(3), Gray2::new(1), 0, Gray2::new(1),
+        with_opacity_binary_color_half, BinaryColor::On, BinaryColor::Off, 128, BinaryColor::On,
+        with_opacity_binary_color_below_half, BinaryColor::On, BinaryColor::Off, 127, BinaryColor::Off,
+    }
+
+    macro_rules! test_colormap_levels {
+        (
+            $(
+                $fn_ident:ident, $array:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = Colormap($array).levels();
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_colormap_levels! {
+        colormap_levels_binary_color, array::from_fn::<_, 16, _>(|index| BinaryColor::from(index >= 8)), 2,
+        colormap_levels_uniform, [Gray4::new(7); 16], 1,
+        colormap_levels_gray2, array::from_fn::<_, 16, _>(|index| Gray2::new(index as u8 / 4)), 4,
+        colormap_levels_identity, array::from_fn::<_, 16, _>(|index| Gray4::new(index as u8)), 16,
+        colormap_levels_runs, [BinaryColor::Off, BinaryColor::On, BinaryColor::Off, BinaryColor::On], 4,
+    }
+
+    macro_rules! test_colormap_get_level {
+        (
+            $(
+                $fn_ident:ident, $level:expr, $levels:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let colormap = Colormap(array::from_fn::<_, 16, _>(|index| Gray4::new(index as u8)));
+                    let result = colormap.get_level($level, $levels);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_colormap_get_level! {
+        colormap_get_level_0_of_2, 0, 2, Gray4::new(0),
+        colormap_get_level_1_of_2, 1, 2, Gray4::new(15),
+        colormap_get_level_1_of_4, 1, 4, Gray4::new(5),
+        colormap_get_level_2_of_4, 2, 4, Gray4::new(10),
+        colormap_get_level_3_of_4, 3, 4, Gray4::new(15),
+        colormap_get_level_1_of_3, 1, 3, Gray4::new(8),
+        colormap_get_level_0_of_1, 0, 1, Gray4::new(0),
+        colormap_get_level_out_of_range, 5, 4, Gray4::new(15),
+    }
+
+    macro_rules! test_blend {
+        (
+            $(
+                $fn_ident:ident,
+                $color:expr,
+                $other:expr,
+                $start:expr,
+                $end:expr,
+                $blend_mode:expr,
+                $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = $color.blend($other, $start, $end, $blend_mode);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_blend! {
+        blend_rgb888_multiply, Rgb888::new(255, 128, 64), Rgb888::new(128, 128, 255),
+        Rgb888::WHITE, Rgb888::BLACK, BlendMode::Multiply, Rgb888::new(128, 64, 64),
+        blend_rgb888_darken, Rgb888::new(255, 128, 64), Rgb888::new(128, 200, 255),
+        Rgb888::WHITE, Rgb888::BLACK, BlendMode::Darken, Rgb888::new(128, 128, 64),
+        blend_rgb888_lighten, Rgb888::new(255, 128, 64), Rgb888::new(128, 200, 255),
+        Rgb888::BLACK, Rgb888::WHITE, BlendMode::Lighten, Rgb888::new(255, 200, 255),
+        blend_rgb888_coverage_max_on_black, Rgb888::new(255, 128, 64), Rgb888::new(128, 200, 255),
+        Rgb888::BLACK, Rgb888::WHITE, BlendMode::CoverageMax, Rgb888::new(255, 200, 255),
+        blend_rgb888_coverage_max_on_white, Rgb888::new(255, 128, 64), Rgb888::new(128, 200, 255),
+        Rgb888::WHITE, Rgb888::BLACK, BlendMode::CoverageMax, Rgb888::new(128, 128, 64),
+        blend_rgb888_screen, Rgb888::new(128, 128, 128), Rgb888::new(128, 128, 128),
+        Rgb888::BLACK, Rgb888::WHITE, BlendMode::Screen, Rgb888::new(192, 192, 192),
+        blend_rgb565_multiply, Rgb565::new(31, 63, 16), Rgb565::new(16, 32, 31),
+        Rgb565::WHITE, Rgb565::BLACK, BlendMode::Multiply, Rgb565::new(16, 32, 16),
+        blend_gray8_multiply, Gray8::new(128), Gray8::new(128),
+        Gray8::WHITE, Gray8::BLACK, BlendMode::Multiply, Gray8::new(64),
+        blend_gray4_multiply, Gray4::new(15), Gray4::new(5),
+        Gray4::WHITE, Gray4::BLACK, BlendMode::Multiply, Gray4::new(5),
+        blend_gray2_multiply, Gray2::new(2), Gray2::new(2),
+        Gray2::WHITE, Gray2::BLACK, BlendMode::Multiply, Gray2::new(1),
+        blend_gray8_coverage_max_on_white, Gray8::new(100), Gray8::new(50),
+        Gray8::WHITE, Gray8::BLACK, BlendMode::CoverageMax, Gray8::new(50),
+        blend_gray8_coverage_max_on_black, Gray8::new(100), Gray8::new(50),
+        Gray8::BLACK, Gray8::WHITE, BlendMode::CoverageMax, Gray8::new(100),
+        blend_binary_color_multiply, BinaryColor::On, BinaryColor::Off,
+        BinaryColor::On, BinaryColor::Off, BlendMode::Multiply, BinaryColor::Off,
+        blend_binary_color_lighten, BinaryColor::On, BinaryColor::Off,
+        BinaryColor::Off, BinaryColor::On, BlendMode::Lighten, BinaryColor::On,
+        blend_binary_color_coverage_max, BinaryColor::Off, BinaryColor::On,
+        BinaryColor::Off, BinaryColor::On, BlendMode::CoverageMax, BinaryColor::On,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Ink(u8);
+
+    impl Screen for Ink {
+        fn screen(self, other: Self, _start: Self, _end: Self) -> Self {
+            Self(self.0.max(other.0))
+        }
+    }
+
+    impl Multiply for Ink {}
+
+    impl Darken for Ink {}
+
+    impl Lighten for Ink {}
+
+    impl CoverageMax for Ink {}
+
+    #[test]
+    fn blend_default_implementations() {
+        let (color, other, start, end) = (Ink(1), Ink(2), Ink(0), Ink(3));
+        assert_eq!(color.blend(other, start, end, BlendMode::Screen), other);
+        for blend_mode in [
+            BlendMode::Multiply,
+            BlendMode::Darken,
+            BlendMode::Lighten,
+            BlendMode::CoverageMax,
+        ] {
+            assert_eq!(color.blend(other, start, end, blend_mode), color);
+        }
+    }
 }
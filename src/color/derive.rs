@@ -0,0 +1,277 @@
+//! Color math shared by the implementations for the color types of `embedded-graphics` and those
+//! generated by [`RgbColorMath`](crate::RgbColorMath) and [`GrayColorMath`](crate::GrayColorMath).
+//!
+//! Every function takes the constructor of the color type, which is passed the channel values.
+
+use core::array;
+
+use embedded_graphics::pixelcolor::{GrayColor, RgbColor};
+
+use super::{
+    Colormap, Interpolation, convert_channel, coverage_max_mix_channel, interpolate_channels,
+    multiply_mix_channel, screen_mix_channel, weighted_avg_mix_channel,
+};
+
+/// Returns a linear gradient with the specified start and end colors.
+pub fn rgb_linear<T: RgbColor, const N: usize>(
+    start: T,
+    end: T,
+    new: impl Fn(u8, u8, u8) -> T,
+) -> Colormap<T, N> {
+    let colors = array::from_fn(|index| {
+        let r = convert_channel::<N>(index as u8, start.r(), end.r());
+        let g = convert_channel::<N>(index as u8, start.g(), end.g());
+        let b = convert_channel::<N>(index as u8, start.b(), end.b());
+
+        new(r, g, b)
+    });
+
+    Colormap(colors)
+}
+
+/// Returns a gradient with the specified start and end colors, with the colors in between
+/// interpolated as specified.
+pub fn rgb_interpolated<T: RgbColor, const N: usize>(
+    start: T,
+    end: T,
+    interpolation: Interpolation,
+    new: impl Fn(u8, u8, u8) -> T,
+) -> Colormap<T, N> {
+    if interpolation == Interpolation::Encoded {
+        return rgb_linear(start, end, new);
+    }
+
+    let max = [T::MAX_R, T::MAX_G, T::MAX_B];
+    let start_channels = [start.r(), start.g(), start.b()];
+    let end_channels = [end.r(), end.g(), end.b()];
+    let colors = array::from_fn(|index| {
+        let [r, g, b] =
+            interpolate_channels::<N>(index, start_channels, end_channels, max, interpolation);
+
+        new(r, g, b)
+    });
+
+    Colormap(colors)
+}
+
+/// Returns the inverted color.
+pub fn rgb_invert<T: RgbColor>(color: T, new: impl Fn(u8, u8, u8) -> T) -> T {
+    let r = T::MAX_R - color.r();
+    let g = T::MAX_G - color.g();
+    let b = T::MAX_B - color.b();
+
+    new(r, g, b)
+}
+
+/// Returns the color faded into the background color by the specified opacity factor.
+pub fn rgb_with_opacity<T: RgbColor>(
+    color: T,
+    background: T,
+    opacity: u8,
+    new: impl Fn(u8, u8, u8) -> T,
+) -> T {
+    let r = convert_channel::<256>(opacity, background.r(), color.r());
+    let g = convert_channel::<256>(opacity, background.g(), color.g());
+    let b = convert_channel::<256>(opacity, background.b(), color.b());
+
+    new(r, g, b)
+}
+
+/// Returns the two colors mixed in [`Screen`](super::Screen) blend mode.
+pub fn rgb_screen<T: RgbColor>(
+    color: T,
+    other: T,
+    start: T,
+    end: T,
+    new: impl Fn(u8, u8, u8) -> T,
+) -> T {
+    let r = screen_mix_channel(color.r(), other.r(), start.r(), end.r());
+    let g = screen_mix_channel(color.g(), other.g(), start.g(), end.g());
+    let b = screen_mix_channel(color.b(), other.b(), start.b(), end.b());
+
+    new(r, g, b)
+}
+
+/// Returns the two colors mixed in [`WeightedAvg`](super::WeightedAvg) blend mode.
+pub fn rgb_weighted_avg<T: RgbColor>(
+    [color, other]: [T; 2],
+    [[start, end], [other_start, other_end]]: [[T; 2]; 2],
+    new: impl Fn(u8, u8, u8) -> T,
+) -> T {
+    let [r, g, b] = [T::r, T::g, T::b].map(|value_of| {
+        weighted_avg_mix_channel(
+            value_of(&color),
+            value_of(&other),
+            value_of(&start),
+            value_of(&end),
+            value_of(&other_start),
+            value_of(&other_end),
+        )
+    });
+
+    new(r, g, b)
+}
+
+/// Returns the two colors mixed in [`Multiply`](super::Multiply) blend mode.
+pub fn rgb_multiply<T: RgbColor>(color: T, other: T, new: impl Fn(u8, u8, u8) -> T) -> T {
+    let r = multiply_mix_channel(color.r(), other.r(), T::MAX_R);
+    let g = multiply_mix_channel(color.g(), other.g(), T::MAX_G);
+    let b = multiply_mix_channel(color.b(), other.b(), T::MAX_B);
+
+    new(r, g, b)
+}
+
+/// Returns the two colors mixed in [`Darken`](super::Darken) blend mode.
+pub fn rgb_darken<T: RgbColor>(color: T, other: T, new: impl Fn(u8, u8, u8) -> T) -> T {
+    let r = color.r().min(other.r());
+    let g = color.g().min(other.g());
+    let b = color.b().min(other.b());
+
+    new(r, g, b)
+}
+
+/// Returns the two colors mixed in [`Lighten`](super::Lighten) blend mode.
+pub fn rgb_lighten<T: RgbColor>(color: T, other: T, new: impl Fn(u8, u8, u8) -> T) -> T {
+    let r = color.r().max(other.r());
+    let g = color.g().max(other.g());
+    let b = color.b().max(other.b());
+
+    new(r, g, b)
+}
+
+/// Returns the two colors mixed in [`CoverageMax`](super::CoverageMax) blend mode.
+pub fn rgb_coverage_max<T: RgbColor>(
+    color: T,
+    other: T,
+    start: T,
+    end: T,
+    new: impl Fn(u8, u8, u8) -> T,
+) -> T {
+    let r = coverage_max_mix_channel(color.r(), other.r(), start.r(), end.r());
+    let g = coverage_max_mix_channel(color.g(), other.g(), start.g(), end.g());
+    let b = coverage_max_mix_channel(color.b(), other.b(), start.b(), end.b());
+
+    new(r, g, b)
+}
+
+/// Returns a linear gradient with the specified start and end colors.
+pub fn gray_linear<T: GrayColor, const N: usize>(
+    start: T,
+    end: T,
+    new: impl Fn(u8) -> T,
+) -> Colormap<T, N> {
+    let colors = array::from_fn(|index| {
+        let luma = convert_channel::<N>(index as u8, start.luma(), end.luma());
+
+        new(luma)
+    });
+
+    Colormap(colors)
+}
+
+/// Returns a gradient with the specified start and end colors, with the colors in between
+/// interpolated as specified.
+pub fn gray_interpolated<T: GrayColor, const N: usize>(
+    start: T,
+    end: T,
+    interpolation: Interpolation,
+    new: impl Fn(u8) -> T,
+) -> Colormap<T, N> {
+    if interpolation == Interpolation::Encoded {
+        return gray_linear(start, end, new);
+    }
+
+    let max = [T::WHITE.luma(); 3];
+    let start_channels = [start.luma(); 3];
+    let end_channels = [end.luma(); 3];
+    let colors = array::from_fn(|index| {
+        let [luma, _, _] =
+            interpolate_channels::<N>(index, start_channels, end_channels, max, interpolation);
+
+        new(luma)
+    });
+
+    Colormap(colors)
+}
+
+/// Returns the inverted color.
+pub fn gray_invert<T: GrayColor>(color: T, new: impl Fn(u8) -> T) -> T {
+    new(T::WHITE.luma() - color.luma())
+}
+
+/// Returns the color faded into the background color by the specified opacity factor.
+pub fn gray_with_opacity<T: GrayColor>(
+    color: T,
+    background: T,
+    opacity: u8,
+    new: impl Fn(u8) -> T,
+) -> T {
+    new(convert_channel::<256>(
+        opacity,
+        background.luma(),
+        color.luma(),
+    ))
+}
+
+/// Returns the two colors mixed in [`Screen`](super::Screen) blend mode.
+pub fn gray_screen<T: GrayColor>(color: T, other: T, start: T, end: T, new: impl Fn(u8) -> T) -> T {
+    new(screen_mix_channel(
+        color.luma(),
+        other.luma(),
+        start.luma(),
+        end.luma(),
+    ))
+}
+
+/// Returns the two colors mixed in [`WeightedAvg`](super::WeightedAvg) blend mode.
+pub fn gray_weighted_avg<T: GrayColor>(
+    [color, other]: [T; 2],
+    [[start, end], [other_start, other_end]]: [[T; 2]; 2],
+    new: impl Fn(u8) -> T,
+) -> T {
+    let luma = weighted_avg_mix_channel(
+        color.luma(),
+        other.luma(),
+        start.luma(),
+        end.luma(),
+        other_start.luma(),
+        other_end.luma(),
+    );
+
+    new(luma)
+}
+
+/// Returns the two colors mixed in [`Multiply`](super::Multiply) blend mode.
+pub fn gray_multiply<T: GrayColor>(color: T, other: T, new: impl Fn(u8) -> T) -> T {
+    new(multiply_mix_channel(
+        color.luma(),
+        other.luma(),
+        T::WHITE.luma(),
+    ))
+}
+
+/// Returns the two colors mixed in [`Darken`](super::Darken) blend mode.
+pub fn gray_darken<T: GrayColor>(color: T, other: T, new: impl Fn(u8) -> T) -> T {
+    new(color.luma().min(other.luma()))
+}
+
+/// Returns the two colors mixed in [`Lighten`](super::Lighten) blend mode.
+pub fn gray_lighten<T: GrayColor>(color: T, other: T, new: impl Fn(u8) -> T) -> T {
+    new(color.luma().max(other.luma()))
+}
+
+/// Returns the two colors mixed in [`CoverageMax`](super::CoverageMax) blend mode.
+pub fn gray_coverage_max<T: GrayColor>(
+    color: T,
+    other: T,
+    start: T,
+    end: T,
+    new: impl Fn(u8) -> T,
+) -> T {
+    new(coverage_max_mix_channel(
+        color.luma(),
+        other.luma(),
+        start.luma(),
+        end.luma(),
+    ))
+}
@@ -0,0 +1,268 @@
+use embedded_graphics::pixelcolor::{PixelColor, Rgb888, RgbColor};
+
+use super::{
+    Colormap, CoverageMax, Darken, Interpolation, Invert, Lighten, Linear, Multiply, Opacity,
+    Screen, WeightedAvg,
+};
+
+/// Palette of an indexed-color type, such as the colors of a multi-color e-paper display.
+///
+/// A color type that implements this trait gets implementations of [`Linear`], [`Invert`],
+/// [`Opacity`], [`Screen`], [`WeightedAvg`], and of the other blend modes, all of which do their
+/// math in [`Rgb888`] and then map the resulting color onto the nearest palette entry. Where two
+/// colors are mixed, or where a gradient is created between two colors, only the palette entries
+/// that lie on the line between the two colors in RGB color space are considered, so that a
+/// gradient from red to white never passes through yellow, for example; the gray values in
+/// between can then be approximated by [`Dithering`](super::Dithering).
+pub trait Palette: PixelColor + Default + 'static {
+    /// The palette entries. The default color is used in place of any color that has no entry, and
+    /// as the color of every entry of a colormap when there are no entries at all.
+    const ENTRIES: &'static [PaletteEntry<Self>];
+}
+
+/// Palette entry, a color of an indexed-color type along with its RGB equivalent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PaletteEntry<T> {
+    /// The color.
+    pub color: T,
+    /// The RGB equivalent of the color.
+    pub rgb: Rgb888,
+}
+
+impl<T> PaletteEntry<T> {
+    /// Creates a new palette entry with the specified color and its RGB equivalent.
+    pub const fn new(color: T, rgb: Rgb888) -> Self {
+        Self { color, rgb }
+    }
+}
+
+/// Returns the RGB equivalent of the specified color, or that of the default color if it has no
+/// palette entry.
+fn rgb_of<T: Palette>(color: T) -> Rgb888 {
+    let entry = T::ENTRIES.iter().find(|entry| entry.color == color);
+    let entry = entry.or_else(|| T::ENTRIES.iter().find(|entry| entry.color == T::default()));
+
+    entry.map_or(Rgb888::BLACK, |entry| entry.rgb)
+}
+
+/// Returns the components of the specified color.
+fn components(rgb: Rgb888) -> [i64; 3] {
+    [rgb.r(), rgb.g(), rgb.b()].map(i64::from)
+}
+
+/// Returns the squared distance between the specified colors in RGB color space.
+fn distance_squared(first: [i64; 3], second: [i64; 3]) -> i64 {
+    (0..3).map(|i| (first[i] - second[i]).pow(2)).sum()
+}
+
+/// Returns whether the specified color lies on the line from the specified start color to the
+/// specified end color, at a distance of no more than an eighth of the length of the line.
+fn is_between(rgb: Rgb888, start: Rgb888, end: Rgb888) -> bool {
+    let [rgb, start, end] = [rgb, start, end].map(components);
+    let length_squared = distance_squared(end, start);
+    if length_squared == 0 {
+        return rgb == start;
+    }
+
+    let dot_product: i64 = (0..3)
+        .map(|i| (end[i] - start[i]) * (rgb[i] - start[i]))
+        .sum();
+    if dot_product < 0 || dot_product > length_squared {
+        return false;
+    }
+
+    let distance_squared = distance_squared(rgb, start) * length_squared - dot_product.pow(2);
+
+    distance_squared * 64 <= length_squared.pow(2)
+}
+
+/// Returns the palette entry that is nearest to the specified color, of the palette entries for
+/// which the specified predicate returns `true`, or the default color if there are none.
+fn nearest<T: Palette>(rgb: Rgb888, mut predicate: impl FnMut(Rgb888) -> bool) -> T {
+    let target = components(rgb);
+    let entries = T::ENTRIES.iter().filter(|entry| predicate(entry.rgb));
+    let entry = entries.min_by_key(|entry| distance_squared(components(entry.rgb), target));
+
+    entry.map_or_else(T::default, |entry| entry.color)
+}
+
+/// Returns the palette entry that is nearest to the specified color, of the palette entries on the
+/// line from the specified start color to the specified end color.
+fn nearest_between<T: Palette>(rgb: Rgb888, start: T, end: T) -> T {
+    let [start, end] = [start, end].map(rgb_of);
+
+    nearest(rgb, |entry| is_between(entry, start, end))
+}
+
+impl<T: Palette, const N: usize> Linear<T> for Colormap<T, N> {
+    fn linear(start: T, end: T) -> Self {
+        Self::interpolated(start, end, Interpolation::Encoded)
+    }
+
+    fn interpolated(start: T, end: T, interpolation: Interpolation) -> Self {
+        let Colormap(colors) =
+            Colormap::<Rgb888, N>::interpolated(rgb_of(start), rgb_of(end), interpolation);
+
+        Self(colors.map(|rgb| nearest_between(rgb, start, end)))
+    }
+}
+
+impl<T: Palette> Invert for T {
+    fn invert(self) -> Self {
+        nearest(rgb_of(self).invert(), |_| true)
+    }
+}
+
+impl<T: Palette> Opacity for T {
+    fn with_opacity(self, background: Self, opacity: u8) -> Self {
+        let rgb = rgb_of(self).with_opacity(rgb_of(background), opacity);
+
+        nearest_between(rgb, background, self)
+    }
+}
+
+impl<T: Palette> Screen for T {
+    fn screen(self, other: Self, start: Self, end: Self) -> Self {
+        let rgb = rgb_of(self).screen(rgb_of(other), rgb_of(start), rgb_of(end));
+
+        nearest_between(rgb, start, end)
+    }
+}
+
+impl<T: Palette> WeightedAvg for T {
+    fn weighted_avg(
+        self,
+        other: Self,
+        start: Self,
+        end: Self,
+        other_start: Self,
+        other_end: Self,
+    ) -> Self {
+        let rgb = rgb_of(self).weighted_avg(
+            rgb_of(other),
+            rgb_of(start),
+            rgb_of(end),
+            rgb_of(other_start),
+            rgb_of(other_end),
+        );
+
+        nearest_between(rgb, self, other)
+    }
+}
+
+impl<T: Palette> Multiply for T {
+    fn multiply(self, other: Self) -> Self {
+        nearest(rgb_of(self).multiply(rgb_of(other)), |_| true)
+    }
+}
+
+impl<T: Palette> Darken for T {
+    fn darken(self, other: Self) -> Self {
+        nearest(rgb_of(self).darken(rgb_of(other)), |_| true)
+    }
+}
+
+impl<T: Palette> Lighten for T {
+    fn lighten(self, other: Self) -> Self {
+        nearest(rgb_of(self).lighten(rgb_of(other)), |_| true)
+    }
+}
+
+impl<T: Palette> CoverageMax for T {
+    fn coverage_max(self, other: Self, start: Self, end: Self) -> Self {
+        let rgb = rgb_of(self).coverage_max(rgb_of(other), rgb_of(start), rgb_of(end));
+
+        nearest_between(rgb, start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    enum Acep {
+        Black,
+        #[default]
+        White,
+        Green,
+        Blue,
+        Red,
+        Yellow,
+        Orange,
+        Gray,
+    }
+
+    impl PixelColor for Acep {
+        type Raw = ();
+    }
+
+    impl Palette for Acep {
+        const ENTRIES: &'static [PaletteEntry<Self>] = &[
+            PaletteEntry::new(Acep::Black, Rgb888::new(0, 0, 0)),
+            PaletteEntry::new(Acep::White, Rgb888::new(255, 255, 255)),
+            PaletteEntry::new(Acep::Green, Rgb888::new(0, 255, 0)),
+            PaletteEntry::new(Acep::Blue, Rgb888::new(0, 0, 255)),
+            PaletteEntry::new(Acep::Red, Rgb888::new(255, 0, 0)),
+            PaletteEntry::new(Acep::Yellow, Rgb888::new(255, 255, 0)),
+            PaletteEntry::new(Acep::Orange, Rgb888::new(255, 128, 0)),
+        ];
+    }
+
+    macro_rules! test_colormap {
+        (
+            $(
+                $fn_ident:ident, $start:expr, $end:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let Colormap(colors) = Colormap::linear($start, $end);
+                    assert_eq!(colors, $expected);
+                }
+            )*
+        }
+    }
+
+    test_colormap! {
+        colormap_black_to_white, Acep::Black, Acep::White,
+        [Acep::Black, Acep::Black, Acep::White, Acep::White],
+        colormap_white_to_red, Acep::White, Acep::Red,
+        [Acep::White, Acep::White, Acep::Red, Acep::Red],
+        colormap_red_to_yellow, Acep::Red, Acep::Yellow,
+        [Acep::Red, Acep::Orange, Acep::Orange, Acep::Yellow],
+        colormap_gray_to_gray, Acep::Gray, Acep::Gray, [Acep::White; 4],
+    }
+
+    macro_rules! test_mix {
+        (
+            $(
+                $fn_ident:ident, $result:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    assert_eq!($result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_mix! {
+        invert_black, Acep::Black.invert(), Acep::White,
+        invert_yellow, Acep::Yellow.invert(), Acep::Blue,
+        with_opacity_red_on_white_opaque, Acep::Red.with_opacity(Acep::White, 255), Acep::Red,
+        with_opacity_red_on_white_transparent, Acep::Red.with_opacity(Acep::White, 0), Acep::White,
+        screen_red_on_white, Acep::Red.screen(Acep::White, Acep::White, Acep::Red), Acep::Red,
+        screen_white_on_white, Acep::White.screen(Acep::White, Acep::White, Acep::Red), Acep::White,
+        multiply_red_yellow, Acep::Red.multiply(Acep::Yellow), Acep::Red,
+        darken_green_blue, Acep::Green.darken(Acep::Blue), Acep::Black,
+        lighten_red_green, Acep::Red.lighten(Acep::Green), Acep::Yellow,
+        coverage_max_red_on_white,
+        Acep::White.coverage_max(Acep::Red, Acep::White, Acep::Red),
+        Acep::Red,
+    }
+}
@@ -0,0 +1,114 @@
+use embedded_graphics::pixelcolor::{PixelColor, Rgb888};
+use epd_waveshare::color::{Color, OctColor, TriColor};
+
+use super::{Palette, PaletteEntry};
+
+/// Color of an e-paper display from the `epd-waveshare` crate, for use with
+/// [`BitmapFontStyle`](crate::style::BitmapFontStyle).
+///
+/// The color types of `epd-waveshare` have no default color, which is needed for the fallback
+/// text and background colors of a style; this wrapper defaults to white. Text is drawn to an
+/// `epd-waveshare` display by converting colors with
+/// [`color_converted`](embedded_graphics::draw_target::DrawTargetExt::color_converted). The colors
+/// are mapped through a [`Palette`], so that anti-aliased glyph images are drawn using only black,
+/// white, and the accent color that lie on the gradient from the background color to the text
+/// color, which is taken to be red for [`TriColor::Chromatic`]; the [`OctColor::HiZ`] color is
+/// never used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WaveshareColor<C>(pub C);
+
+impl<C: PixelColor> PixelColor for WaveshareColor<C> {
+    type Raw = C::Raw;
+}
+
+macro_rules! impl_palette {
+    (
+        $(
+            $color_type:ty, $default_color:expr, [$($color:expr, $rgb:expr,)+],
+        )*
+    ) => {
+        $(
+            impl Default for WaveshareColor<$color_type> {
+                fn default() -> Self {
+                    Self($default_color)
+                }
+            }
+
+            impl From<$color_type> for WaveshareColor<$color_type> {
+                fn from(color: $color_type) -> Self {
+                    Self(color)
+                }
+            }
+
+            impl From<WaveshareColor<$color_type>> for $color_type {
+                fn from(WaveshareColor(color): WaveshareColor<$color_type>) -> Self {
+                    color
+                }
+            }
+
+            impl Palette for WaveshareColor<$color_type> {
+                const ENTRIES: &'static [PaletteEntry<Self>] = &[
+                    $(PaletteEntry::new(WaveshareColor($color), $rgb),)+
+                ];
+            }
+        )*
+    }
+}
+
+impl_palette! {
+    Color, Color::White, [
+        Color::Black, Rgb888::new(0, 0, 0),
+        Color::White, Rgb888::new(255, 255, 255),
+    ],
+    TriColor, TriColor::White, [
+        TriColor::Black, Rgb888::new(0, 0, 0),
+        TriColor::White, Rgb888::new(255, 255, 255),
+        TriColor::Chromatic, Rgb888::new(255, 0, 0),
+    ],
+    OctColor, OctColor::White, [
+        OctColor::Black, Rgb888::new(0, 0, 0),
+        OctColor::White, Rgb888::new(255, 255, 255),
+        OctColor::Green, Rgb888::new(0, 255, 0),
+        OctColor::Blue, Rgb888::new(0, 0, 255),
+        OctColor::Red, Rgb888::new(255, 0, 0),
+        OctColor::Yellow, Rgb888::new(255, 255, 0),
+        OctColor::Orange, Rgb888::new(255, 128, 0),
+    ],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{Colormap, Linear};
+
+    macro_rules! test_colormap {
+        (
+            $(
+                $fn_ident:ident, $start:expr, $end:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let Colormap(colors) =
+                        Colormap::<_, 4>::linear(WaveshareColor($start), WaveshareColor($end));
+                    assert_eq!(colors.map(|WaveshareColor(color)| color), $expected);
+                }
+            )*
+        }
+    }
+
+    test_colormap! {
+        colormap_tri_color_white_to_black, TriColor::White, TriColor::Black,
+        [TriColor::White, TriColor::White, TriColor::Black, TriColor::Black],
+        colormap_tri_color_white_to_chromatic, TriColor::White, TriColor::Chromatic,
+        [TriColor::White, TriColor::White, TriColor::Chromatic, TriColor::Chromatic],
+        colormap_tri_color_black_to_chromatic, TriColor::Black, TriColor::Chromatic,
+        [TriColor::Black, TriColor::Black, TriColor::Chromatic, TriColor::Chromatic],
+        colormap_oct_color_red_to_yellow, OctColor::Red, OctColor::Yellow,
+        [OctColor::Red, OctColor::Orange, OctColor::Orange, OctColor::Yellow],
+        colormap_oct_color_white_to_blue, OctColor::White, OctColor::Blue,
+        [OctColor::White, OctColor::White, OctColor::Blue, OctColor::Blue],
+    }
+}
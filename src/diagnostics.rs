@@ -0,0 +1,290 @@
+//! Diagnostics for drawing text.
+//!
+//! Drawing a text run involves filling in the background around glyph images, mixing glyph images
+//! where they overlap, and carrying over what was drawn last to the next call. A [`Coverage`] is a
+//! draw target that passes everything through to another draw target, while counting how many
+//! times each pixel in an area has been drawn; with it, text runs and layouts can be checked on
+//! the host for pixels that have been drawn more than once, pixels in the line strip that have not
+//! been drawn at all, and for whether what has been drawn matches what
+//! [`measure_string`](TextRenderer::measure_string) returns.
+
+use embedded_graphics::Pixel;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Dimensions, Point, Size};
+use embedded_graphics::primitives::{PointsIter, Rectangle};
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+
+/// Coverage, a draw target that counts how many times each pixel in an area is drawn.
+///
+/// The counts are kept in the specified buffer, one byte per pixel in row-major order, saturating
+/// at `255`; pixels outside of the area are passed through without being counted, although they
+/// do extend the bounding box of the pixels that have been drawn.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Coverage<'a, D: DrawTarget> {
+    parent: &'a mut D,
+    area: Rectangle,
+    counts: &'a mut [u8],
+    drawn_box: Option<(Point, Point)>,
+}
+
+/// Report on drawing a text run, as returned by [`Coverage::check_string`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TextReport {
+    /// The bounding box of the pixels that have been drawn.
+    pub drawn_box: Rectangle,
+    /// The bounding box that is expected to be drawn, which is the bounding box returned by
+    /// [`measure_string`](TextRenderer::measure_string), widened to the next position.
+    pub measured_box: Rectangle,
+    /// The next position returned by [`draw_string`](TextRenderer::draw_string).
+    pub next_position: Point,
+    /// The next position returned by [`measure_string`](TextRenderer::measure_string).
+    pub measured_next_position: Point,
+    /// The number of pixels in the area that have been drawn more than once.
+    pub overdrawn: usize,
+    /// The number of pixels in the area and in the expected bounding box that have not been drawn.
+    pub undrawn: usize,
+}
+
+impl<'a, D: DrawTarget> Coverage<'a, D> {
+    /// Creates a new coverage with the specified parent draw target, area, and counts, which are
+    /// reset to zero; the number of counts is expected to be at least the number of pixels in the
+    /// area.
+    pub fn new(parent: &'a mut D, area: Rectangle, counts: &'a mut [u8]) -> Self {
+        counts.fill(0);
+
+        Self {
+            parent,
+            area,
+            counts,
+            drawn_box: None,
+        }
+    }
+
+    /// Returns the area in which pixels are counted.
+    pub const fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Resets the counts to zero and the bounding box of the pixels that have been drawn.
+    pub fn reset(&mut self) {
+        self.counts.fill(0);
+        self.drawn_box = None;
+    }
+
+    /// Returns the number of times that the pixel at the specified point has been drawn, or `None`
+    /// if the point is outside of the area or if there is no count for it.
+    pub fn count(&self, point: Point) -> Option<u8> {
+        self.index(point)
+            .and_then(|index| self.counts.get(index).copied())
+    }
+
+    /// Returns the bounding box of the pixels that have been drawn, which is zero-sized if no
+    /// pixels have been drawn.
+    pub fn drawn_box(&self) -> Rectangle {
+        match self.drawn_box {
+            Some((top_left, bottom_right)) => Rectangle::with_corners(top_left, bottom_right),
+            None => Rectangle::zero(),
+        }
+    }
+
+    /// Returns an iterator over the points in the area that have been drawn more than once.
+    pub fn overdrawn(&self) -> impl Iterator<Item = Point> + '_ {
+        self.area
+            .points()
+            .filter(|&point| self.count(point).is_some_and(|count| count > 1))
+    }
+
+    /// Returns an iterator over the points in the area and in the specified rectangle that have
+    /// not been drawn.
+    pub fn undrawn(&self, rectangle: &Rectangle) -> impl Iterator<Item = Point> + '_ {
+        self.area
+            .intersection(rectangle)
+            .points()
+            .filter(|&point| self.count(point) == Some(0))
+    }
+
+    /// Resets this coverage, draws the specified text run using the specified text renderer, and
+    /// returns a report comparing the pixels that have been drawn with the measurements of the
+    /// text run.
+    pub fn check_string<S>(
+        &mut self,
+        renderer: &S,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+    ) -> Result<TextReport, D::Error>
+    where
+        S: TextRenderer<Color = D::Color>,
+    {
+        self.reset();
+        let next_position = renderer.draw_string(text, position, baseline, self)?;
+        let metrics = renderer.measure_string(text, position, baseline);
+        let measured_box = metrics.bounding_box;
+        let measured_next_position = metrics.next_position;
+        let right = measured_box
+            .top_left
+            .x
+            .saturating_add_unsigned(measured_box.size.width)
+            .max(measured_next_position.x);
+
+        let width = right.saturating_sub(measured_box.top_left.x);
+        let size = Size::new(
+            width.try_into().unwrap_or_default(),
+            measured_box.size.height,
+        );
+        let measured_box = Rectangle::new(measured_box.top_left, size);
+
+        Ok(TextReport {
+            drawn_box: self.drawn_box(),
+            measured_box,
+            next_position,
+            measured_next_position,
+            overdrawn: self.overdrawn().count(),
+            undrawn: self.undrawn(&measured_box).count(),
+        })
+    }
+
+    /// Returns the index of the count for the specified point.
+    fn index(&self, point: Point) -> Option<usize> {
+        index(&self.area, point)
+    }
+}
+
+/// Returns the index of the count for the specified point in the specified area.
+fn index(area: &Rectangle, point: Point) -> Option<usize> {
+    if !area.contains(point) {
+        return None;
+    }
+
+    let offset = point - area.top_left;
+    let x = offset.x as usize;
+    let y = offset.y as usize;
+
+    Some(y * area.size.width as usize + x)
+}
+
+impl TextReport {
+    /// Returns whether no pixels have been drawn more than once, no pixels have been left undrawn,
+    /// and the bounding box and the next position are as measured; an empty bounding box matches
+    /// any other empty bounding box.
+    pub fn is_ok(&self) -> bool {
+        self.overdrawn == 0
+            && self.undrawn == 0
+            && (self.drawn_box == self.measured_box
+                || self.drawn_box.is_zero_sized() && self.measured_box.is_zero_sized())
+            && self.next_position == self.measured_next_position
+    }
+}
+
+impl<D: DrawTarget> Dimensions for Coverage<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.parent.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Coverage<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let Self {
+            parent,
+            area,
+            counts,
+            drawn_box,
+        } = self;
+
+        let pixels = pixels.into_iter().inspect(|&Pixel(point, _)| {
+            if let Some(count) = index(area, point).and_then(|index| counts.get_mut(index)) {
+                *count = count.saturating_add(1);
+            }
+
+            *drawn_box = match *drawn_box {
+                Some((top_left, bottom_right)) => Some((
+                    top_left.component_min(point),
+                    bottom_right.component_max(point),
+                )),
+                None => Some((point, point)),
+            };
+        });
+
+        parent.draw_iter(pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::Drawable;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::primitives::{Primitive, PrimitiveStyle};
+
+    use super::*;
+
+    macro_rules! test_coverage {
+        (
+            $(
+                $fn_ident:ident, $rectangles:expr, $expected_overdrawn:expr,
+                $expected_undrawn:expr, $expected_drawn_box:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut display = MockDisplay::<BinaryColor>::new();
+                    display.set_allow_overdraw(true);
+                    display.set_allow_out_of_bounds_drawing(true);
+                    let area = Rectangle::new(Point::new(1, 1), Size::new(4, 3));
+                    let mut counts = [0; 12];
+                    let mut coverage = Coverage::new(&mut display, area, &mut counts);
+                    let style = PrimitiveStyle::with_fill(BinaryColor::On);
+                    let rectangles: &[Rectangle] = &$rectangles;
+                    for rectangle in rectangles {
+                        rectangle.into_styled(style).draw(&mut coverage).unwrap();
+                    }
+
+                    assert_eq!(coverage.overdrawn().count(), $expected_overdrawn);
+                    assert_eq!(coverage.undrawn(&area).count(), $expected_undrawn);
+                    assert_eq!(coverage.drawn_box(), $expected_drawn_box);
+                }
+            )*
+        }
+    }
+
+    test_coverage! {
+        coverage_none,
+        [],
+        0,
+        12,
+        Rectangle::zero(),
+        coverage_exact,
+        [Rectangle::new(Point::new(1, 1), Size::new(4, 3))],
+        0,
+        0,
+        Rectangle::new(Point::new(1, 1), Size::new(4, 3)),
+        coverage_overlapping,
+        [
+            Rectangle::new(Point::new(1, 1), Size::new(3, 3)),
+            Rectangle::new(Point::new(2, 1), Size::new(3, 3)),
+        ],
+        6,
+        0,
+        Rectangle::new(Point::new(1, 1), Size::new(4, 3)),
+        coverage_partial,
+        [Rectangle::new(Point::new(0, 0), Size::new(3, 2))],
+        0,
+        10,
+        Rectangle::new(Point::new(0, 0), Size::new(3, 2)),
+        coverage_outside,
+        [Rectangle::new(Point::new(6, 5), Size::new(2, 2))],
+        0,
+        12,
+        Rectangle::new(Point::new(6, 5), Size::new(2, 2)),
+    }
+}
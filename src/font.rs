@@ -4,6 +4,7 @@ use embedded_graphics::pixelcolor::raw::BigEndian;
 
 use crate::charmap::{Charmap, CharmapEntry};
 use crate::metrics::{BitmapFontMetrics, DecorationDimensions};
+use crate::scalar::{self, Scalar};
 
 /// Bitmap font.
 #[derive(Debug)]
@@ -35,4 +36,93 @@ where
         underline: DecorationDimensions::NULL,
         strikethrough: DecorationDimensions::NULL,
     };
+
+    /// Returns the number of cells that the specified text takes up in a terminal grid, where
+    /// every cell has the width of a halfwidth character.
+    ///
+    /// The advance width of every glyph cluster is converted to a number of cells on its own, so
+    /// that in **M<sup>+</sup> Code**, kanji and fullwidth forms take up two cells and halfwidth
+    /// katakana take up one cell, without the need for Unicode width tables. Glyph clusters that do
+    /// not advance the position take up no cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mplusfonts::mplus;
+    /// #
+    /// let bitmap_font = mplus!(code(100), 500, 12, false, 1, 8, 'A'..='Z', ["漢字", "ｶﾅ"]);
+    ///
+    /// assert_eq!(bitmap_font.cell_width("ABC"), 3);
+    /// assert_eq!(bitmap_font.cell_width("漢字"), 4);
+    /// assert_eq!(bitmap_font.cell_width("ｶﾅ"), 2);
+    /// ```
+    pub fn cell_width(&self, text: &str) -> usize {
+        let mut chars = text.chars();
+        let mut previous_entry: Option<&CharmapEntry<'a, C, N>> = None;
+        let mut width = 0;
+        loop {
+            let slice = chars.as_str();
+            let entry = (!slice.is_empty()).then(|| self.charmap.get(slice));
+            if let Some(previous_entry) = previous_entry {
+                let key = entry.map(|entry| entry.key).unwrap_or_default();
+                let advance_width = (previous_entry.advance_width_to)(key);
+                width += cells(advance_width, self.metrics.halfwidth);
+            }
+
+            let Some(entry) = entry else {
+                return width;
+            };
+
+            for _ in 0..entry.advance_chars.max(1) {
+                let _ = chars.next();
+            }
+
+            previous_entry = Some(entry);
+        }
+    }
+}
+
+/// Returns the number of cells for the specified advance width, which is expected to have been
+/// rounded down from a multiple of the specified halfwidth, or zero if it is not positive.
+fn cells(advance_width: Scalar, halfwidth: Scalar) -> usize {
+    if advance_width <= Scalar::ZERO {
+        return 0;
+    }
+
+    let first_cell = Scalar::from_int(scalar::floor(halfwidth));
+    let other_cells = scalar::div_round(advance_width - first_cell, halfwidth);
+
+    other_cells.max(0) as usize + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_cells {
+        (
+            $(
+                $fn_ident:ident, $advance_width:expr, $halfwidth:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = cells($advance_width, $halfwidth);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_cells! {
+        cells_one_whole, Scalar::from_int(5), Scalar::from_int(5), 1,
+        cells_two_whole, Scalar::from_int(10), Scalar::from_int(5), 2,
+        cells_one_fraction, Scalar::from_int(4), scalar::half(Scalar::from_int(9)), 1,
+        cells_two_fraction, Scalar::from_int(8), scalar::half(Scalar::from_int(9)), 2,
+        cells_three_fraction, Scalar::from_int(13), scalar::half(Scalar::from_int(9)), 3,
+        cells_zero, Scalar::ZERO, Scalar::from_int(5), 0,
+        cells_negative, Scalar::from_int(-5), Scalar::from_int(5), 0,
+        cells_zero_halfwidth, Scalar::from_int(1), Scalar::ZERO, 1,
+    }
 }
@@ -11,6 +11,7 @@ use embedded_graphics::pixelcolor::PixelColor;
 use embedded_graphics::pixelcolor::raw::BigEndian;
 
 use crate::image::{Image, ImageSet};
+use crate::scalar::Scalar;
 
 /// Glyph identifier.
 pub type GlyphId = u16;
@@ -40,9 +41,9 @@ where
     RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
 {
     /// The _x_-offset from the position of the next glyph cluster.
-    pub x_offset: f32,
+    pub x_offset: Scalar,
     /// The _y_-offset from the position of the next glyph cluster.
-    pub y_offset: f32,
+    pub y_offset: Scalar,
     /// The glyph.
     pub glyph: Glyph<'a, C, N>,
 }
@@ -7,13 +7,13 @@ use embedded_graphics::image::ImageDrawable;
 use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
 use embedded_graphics::primitives::Rectangle;
 
-use crate::color::{Colormap, Invert, Screen, WeightedAvg};
+use crate::color::{Blend, BlendMode, Colormap, Invert, Screen, WeightedAvg};
 use crate::image::{Colors, Image, SubImage};
 
 /// Image with references to two overlapping image drawables and a colormap.
 ///
 /// While also performing color conversion, drawing this image drawable involves mixing the colors
-/// that form pairs of pixels in [`Screen`] blend mode.
+/// that form pairs of pixels in [`Screen`] blend mode, unless another [`BlendMode`] is selected.
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ImageMix<'a, 'b, 'c, U, V, T, const N: usize>
@@ -26,6 +26,7 @@ where
     second: Image<SubImage<'b, V>>,
     colormap: &'c Colormap<T, N>,
     area: Rectangle,
+    blend_mode: BlendMode,
 }
 
 /// Image drawable color-mixed with another image drawable in the area that is their intersection.
@@ -71,8 +72,15 @@ where
             second,
             colormap,
             area,
+            blend_mode: BlendMode::Screen,
         }
     }
+
+    /// Returns the image drawable with the specified blend mode selected for mixing the colors.
+    pub const fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
 }
 
 impl<U, V, T, const N: usize> OriginDimensions for ImageMix<'_, '_, '_, U, V, T, N>
@@ -95,7 +103,7 @@ macro_rules! impl_drawable {
         $(
             impl<U, V, T> Drawable for ImageMix<'_, '_, '_, U, V, T, $array_length>
             where
-                T: PixelColor + Default + Invert + Screen + WeightedAvg,
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Blend,
                 U: ImageDrawable<Color = $color_type> + Colors<$color_type>,
                 V: ImageDrawable<Color = $color_type> + Colors<$color_type>,
             {
@@ -123,9 +131,10 @@ macro_rules! impl_drawable {
                     if start == end {
                         target.fill_solid(&self.area, start)
                     } else {
+                        let blend_mode = self.blend_mode;
                         let colors = first
                             .zip(second)
-                            .map(|(first, second)| first.screen(second, start, end));
+                            .map(|(first, second)| first.blend(second, start, end, blend_mode));
 
                         target.fill_contiguous(&self.area, colors)
                     }
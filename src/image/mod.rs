@@ -50,6 +50,12 @@ where
         image_drawable: ImageRaw::new(&[], 0),
         offset: Point::zero(),
     };
+
+    /// Returns the color of the pixel at the specified point, or `None` if the point is outside of
+    /// the image.
+    pub(crate) fn color_at(&self, point: Point) -> Option<C> {
+        self.image_drawable.color_at(point - self.offset)
+    }
 }
 
 impl<T: ImageDrawable> Image<T> {
@@ -2,7 +2,7 @@ use core::iter;
 use core::marker::PhantomData;
 
 use embedded_graphics::draw_target::DrawTarget;
-use embedded_graphics::geometry::{OriginDimensions, Size};
+use embedded_graphics::geometry::{OriginDimensions, Point, Size};
 use embedded_graphics::image;
 use embedded_graphics::image::ImageDrawable;
 use embedded_graphics::iterator::raw::RawDataSlice;
@@ -46,6 +46,30 @@ where
             phantom: PhantomData,
         }
     }
+
+    /// Returns the color of the pixel at the specified point, or `None` if the point is outside of
+    /// the image drawable.
+    pub(crate) fn color_at(&self, point: Point) -> Option<C> {
+        let x = u32::try_from(point.x)
+            .ok()
+            .filter(|&x| x < self.size.width)?;
+        let y = u32::try_from(point.y)
+            .ok()
+            .filter(|&y| y < self.size.height)?;
+        let bits_per_pixel = C::Raw::BITS_PER_PIXEL;
+        let bytes_per_row = (bits_per_pixel * self.size.width as usize).div_ceil(8);
+        let bit = x as usize * bits_per_pixel;
+        let start = y as usize * bytes_per_row + bit / 8;
+        let end = start + bits_per_pixel.div_ceil(8);
+        let bytes = self.data.get(start..end)?;
+        let value = bytes
+            .iter()
+            .fold(0, |value, &byte| (value << 8) | u32::from(byte));
+        let shift = (8 - (bit + bits_per_pixel) % 8) % 8;
+        let mask = u32::MAX >> (32 - bits_per_pixel);
+
+        Some(C::Raw::from_u32((value >> shift) & mask).into())
+    }
 }
 
 impl<C> OriginDimensions for ImageRaw<'_, C>
@@ -82,22 +82,34 @@
 #![warn(missing_debug_implementations)]
 #![warn(missing_copy_implementations)]
 
+// Lets the `mplus!` macro, which refers to this crate by name, be used in unit tests.
+#[cfg(test)]
+extern crate self as mplusfonts;
+
 mod adapter;
 mod builder;
 mod charmap;
 mod font;
 mod metrics;
 mod rect;
+mod scalar;
 
 pub mod color;
+pub mod diagnostics;
 pub mod glyph;
 pub mod image;
+pub mod page;
+pub mod plane;
 pub mod style;
+pub mod terminal;
+pub mod text;
 
 pub use font::BitmapFont;
 
 pub use charmap::*;
 pub use metrics::*;
+pub use scalar::Scalar;
 
 pub use mplusfonts_macros::mplus;
 pub use mplusfonts_macros::strings;
+pub use mplusfonts_macros::{GrayColorMath, RgbColorMath};
@@ -1,29 +1,40 @@
 use embedded_graphics::text::Baseline;
 
+use crate::scalar::{self, Scalar};
+
 /// Metrics of a bitmap font.
+///
+/// This struct is marked as non-exhaustive, so that metrics can be added without breaking
+/// dependents; the metrics of a bitmap font are generated by [`mplus!`](crate::mplus).
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub struct BitmapFontMetrics {
     /// The top of the line, defined as _1160/1000 em_-size for **M<sup>+</sup> 1/2** and
     /// _1235/1000 em_-size for **M<sup>+</sup> Code**.
-    pub top: f32,
+    pub top: Scalar,
     /// Typographic ascender, defined as _880/1000 em_-size for **M<sup>+</sup> 1/2** and
     /// _1000/1000 em_-size for **M<sup>+</sup> Code**.
-    pub ascender: f32,
+    pub ascender: Scalar,
     /// The top of capital letters, defined as _730/1000 em_-size for both **M<sup>+</sup> 1/2**
     /// and **M<sup>+</sup> Code**.
-    pub cap_height: f32,
+    pub cap_height: Scalar,
     /// The top of the small letter _x_, defined as _520/1000 em_-size for both **M<sup>+</sup>
     /// 1/2** and **M<sup>+</sup> Code**.
-    pub x_height: f32,
+    pub x_height: Scalar,
     /// The baseline.
-    pub baseline: f32,
+    pub baseline: Scalar,
     /// Typographic descender, defined as _-120/1000 em_-size for **M<sup>+</sup> 1/2** and
     /// _-235/1000 em_-size for **M<sup>+</sup> Code**.
-    pub descender: f32,
+    pub descender: Scalar,
     /// The bottom of the line, defined as _-288/1000 em_-size for **M<sup>+</sup> 1/2** and
     /// _-270/1000 em_-size for **M<sup>+</sup> Code**.
-    pub bottom: f32,
+    pub bottom: Scalar,
+    /// The width of a halfwidth character, defined as _500/1000 em_-size for **M<sup>+</sup> 1/2**
+    /// and as _100/1000 em_-size plus _4/1000 em_-size per percent of the width-axis position for
+    /// **M<sup>+</sup> Code**, where the advance widths of glyphs are rounded down to multiples of
+    /// this width.
+    pub halfwidth: Scalar,
 }
 
 /// Decoration dimensions for [`mplusfonts`](../mplusfonts/index.html).
@@ -36,31 +47,58 @@ pub struct DecorationDimensions {
     ///
     /// [`underline`]: ../mplusfonts/struct.BitmapFont.html#structfield.underline
     /// [`strikethrough`]: ../mplusfonts/struct.BitmapFont.html#structfield.strikethrough
-    pub offset: f32,
+    pub offset: Scalar,
     /// The height of the decoration, defined as _50/1000 em_-size for both **M<sup>+</sup> 1/2**
     /// and **M<sup>+</sup> Code**.
-    pub height: f32,
+    pub height: Scalar,
 }
 
 impl BitmapFontMetrics {
     /// Metrics of the invisible bitmap font.
     pub const NULL: Self = Self {
-        top: 0.0,
-        ascender: 0.0,
-        cap_height: 0.0,
-        x_height: 0.0,
-        baseline: 0.0,
-        descender: 0.0,
-        bottom: 0.0,
+        top: Scalar::ZERO,
+        ascender: Scalar::ZERO,
+        cap_height: Scalar::ZERO,
+        x_height: Scalar::ZERO,
+        baseline: Scalar::ZERO,
+        descender: Scalar::ZERO,
+        bottom: Scalar::ZERO,
+        halfwidth: Scalar::ZERO,
     };
 
+    /// Creates new metrics with the specified values; for use in the code generated by
+    /// [`mplus!`](crate::mplus) only.
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        top: Scalar,
+        ascender: Scalar,
+        cap_height: Scalar,
+        x_height: Scalar,
+        baseline: Scalar,
+        descender: Scalar,
+        bottom: Scalar,
+        halfwidth: Scalar,
+    ) -> Self {
+        Self {
+            top,
+            ascender,
+            cap_height,
+            x_height,
+            baseline,
+            descender,
+            bottom,
+            halfwidth,
+        }
+    }
+
     /// Returns the _y_-offset for the specified text baseline in pixels.
     pub const fn y_offset(&self, baseline: Baseline) -> i32 {
         match baseline {
-            Baseline::Top => self.top as i32 + (self.top % 1.0 > 0.0) as i32,
-            Baseline::Bottom => self.bottom as i32 + (self.bottom % 1.0 > 0.0) as i32,
-            Baseline::Middle => (self.x_height / 2.0) as i32 + (self.x_height % 2.0 > 0.0) as i32,
-            Baseline::Alphabetic => self.baseline as i32 + (self.baseline % 1.0 > 0.0) as i32,
+            Baseline::Top => scalar::div_ceil(self.top, 1),
+            Baseline::Bottom => scalar::div_ceil(self.bottom, 1),
+            Baseline::Middle => scalar::div_ceil(self.x_height, 2),
+            Baseline::Alphabetic => scalar::div_ceil(self.baseline, 1),
         }
     }
 
@@ -75,18 +113,18 @@ impl BitmapFontMetrics {
 impl DecorationDimensions {
     /// The dimensions of the invisible decoration.
     pub const NULL: Self = Self {
-        offset: 0.0,
-        height: 0.0,
+        offset: Scalar::ZERO,
+        height: Scalar::ZERO,
     };
 
     /// Returns the _y_-offset for a line primitive in pixels.
     pub const fn y_offset(&self) -> i32 {
-        self.offset as i32 + (self.offset % 1.0 > 0.0) as i32
+        scalar::div_ceil(self.offset, 1)
     }
 
     /// Returns the stroke width for a line primitive in pixels.
     pub const fn stroke_width(&self) -> u32 {
-        let height = self.height as i32 + (self.height % 1.0 > 0.0) as i32;
+        let height = scalar::div_ceil(self.height, 1);
 
         height as u32
     }
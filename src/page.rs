@@ -0,0 +1,280 @@
+//! Page-packed glyph data and page buffers.
+//!
+//! Monochrome display controllers such as the SSD1306 and the SH1106 organize their memory in
+//! pages, where every byte is a column of eight vertically adjacent pixels, with the top pixel in
+//! the least significant bit. Bitmap fonts created with `paged(1)` as the quantization level for
+//! gray values have their glyph images packed in this format already; see
+//! [`mplus!`](mplusfonts_macros::mplus). These fonts cannot be used with
+//! [`BitmapFontStyle`](crate::style::BitmapFontStyle); instead, their glyphs are combined with the
+//! contents of a [`PageBuffer`], one byte at a time, which is then sent to the display as it is.
+
+use embedded_graphics::Pixel;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Dimensions, OriginDimensions, Point, Size};
+use embedded_graphics::pixelcolor::raw::{RawData, RawU8};
+use embedded_graphics::pixelcolor::{BinaryColor, PixelColor};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Baseline;
+
+use crate::font::BitmapFont;
+use crate::image::Colors;
+use crate::scalar::{self, Scalar};
+use crate::style;
+
+/// Color type for page-packed glyph images, in which every pixel is a segment of a page.
+///
+/// A page segment is a column of eight vertically adjacent monochrome pixels, with the top pixel
+/// in the least significant bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PageSegment(u8);
+
+/// Blit mode, for how glyph images are combined with the contents of a page buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BlitMode {
+    /// Pixels that are on in the glyph images are turned on; all other pixels are left alone.
+    #[default]
+    Or,
+    /// The line strip is cleared from top to bottom, for the length of the text run, and then the
+    /// glyph images are combined as with [`BlitMode::Or`].
+    Copy,
+}
+
+/// Page buffer, holding the memory contents of a display that is organized in pages.
+///
+/// The buffer has one byte per column for every page, in page-major order.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PageBuffer<'a> {
+    data: &'a mut [u8],
+    width: u32,
+}
+
+impl PageSegment {
+    /// Creates a new page segment with the specified bits.
+    pub const fn new(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the bits of this page segment.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl PixelColor for PageSegment {
+    type Raw = RawU8;
+}
+
+impl From<RawU8> for PageSegment {
+    fn from(raw: RawU8) -> Self {
+        Self(raw.into_inner())
+    }
+}
+
+impl<'a> PageBuffer<'a> {
+    /// Creates a new page buffer with the specified data and display width.
+    pub const fn new(data: &'a mut [u8], width: u32) -> Self {
+        Self { data, width }
+    }
+
+    /// Returns the data in this page buffer.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// Returns the number of pages in this page buffer.
+    pub const fn pages(&self) -> u32 {
+        match self.data.len().checked_div(self.width as usize) {
+            Some(pages) => pages as u32,
+            None => 0,
+        }
+    }
+
+    /// Combines the glyph images of the specified text with the contents of this page buffer,
+    /// returning the position of the next character.
+    pub fn blit_string<const N: usize>(
+        &mut self,
+        font: &BitmapFont<'_, PageSegment, N>,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        mode: BlitMode,
+    ) -> Point {
+        let x = Scalar::from_int(position.x);
+        let y = position.y.saturating_add(font.metrics.y_offset(baseline));
+        if mode == BlitMode::Copy {
+            let mut right = position.x;
+            let mut images = style::images_of_chars(&font.charmap, text, x, Scalar::from_int(y));
+            for (image, _) in images.by_ref() {
+                let image_box = image.bounding_box();
+                let x = image_box
+                    .top_left
+                    .x
+                    .saturating_add_unsigned(image_box.size.width);
+                if x > right {
+                    right = x;
+                }
+            }
+
+            let right = right.max(scalar::trunc(images.x));
+            let top = y.saturating_sub(font.metrics.y_offset(Baseline::Top));
+            let bottom = y.saturating_sub(font.metrics.y_offset(Baseline::Bottom));
+            let line_piece = Rectangle::with_corners(
+                Point::new(position.x, top),
+                Point::new(right.saturating_sub(1), bottom.saturating_sub(1)),
+            );
+            if right > position.x && bottom > top {
+                self.fill_rectangle(&line_piece, false);
+            }
+        }
+
+        let mut images = style::images_of_chars(&font.charmap, text, x, Scalar::from_int(y));
+        for (image, _) in images.by_ref() {
+            let Rectangle { top_left, size } = image.bounding_box();
+            let mut colors = image.colors().into_iter();
+            for page in 0..size.height as i32 {
+                let y = top_left.y.saturating_add(page * 8);
+                for column in 0..size.width as i32 {
+                    let Some(segment) = colors.next() else {
+                        break;
+                    };
+
+                    self.blit_segment(top_left.x.saturating_add(column), y, segment);
+                }
+            }
+        }
+
+        Point::new(scalar::trunc(images.x), position.y)
+    }
+
+    fn blit_segment(&mut self, x: i32, y: i32, segment: PageSegment) {
+        if segment.bits() == 0 || x < 0 || x as u32 >= self.width {
+            return;
+        }
+
+        let page = y.div_euclid(8);
+        let [upper, lower] = split_segment(segment.bits(), y.rem_euclid(8) as u32);
+        for (page, bits) in [(page, upper), (page + 1, lower)] {
+            if bits != 0 && page >= 0 && (page as u32) < self.pages() {
+                let index = page as usize * self.width as usize + x as usize;
+                self.data[index] |= bits;
+            }
+        }
+    }
+
+    fn fill_rectangle(&mut self, area: &Rectangle, on: bool) {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+
+        let first_page = area.top_left.y / 8;
+        let last_page = bottom_right.y / 8;
+        for page in first_page..=last_page {
+            let top = (area.top_left.y - page * 8).max(0) as u32;
+            let bottom = (bottom_right.y - page * 8).min(7) as u32;
+            let mask = (0xFFu8 >> (7 - bottom)) & (0xFFu8 << top);
+            let offset = page as usize * self.width as usize;
+            let range = offset + area.top_left.x as usize..=offset + bottom_right.x as usize;
+            for byte in &mut self.data[range] {
+                if on {
+                    *byte |= mask;
+                } else {
+                    *byte &= !mask;
+                }
+            }
+        }
+    }
+}
+
+impl OriginDimensions for PageBuffer<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.pages() * 8)
+    }
+}
+
+impl DrawTarget for PageBuffer<'_> {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let size = self.size();
+        for Pixel(point, color) in pixels {
+            if point.x < 0 || point.y < 0 {
+                continue;
+            }
+
+            let (x, y) = (point.x as u32, point.y as u32);
+            if x >= size.width || y >= size.height {
+                continue;
+            }
+
+            let index = (y / 8 * self.width + x) as usize;
+            let mask = 1 << (y % 8);
+            if color.is_on() {
+                self.data[index] |= mask;
+            } else {
+                self.data[index] &= !mask;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_rectangle(area, color.is_on());
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.data.fill(if color.is_on() { 0xFF } else { 0x00 });
+
+        Ok(())
+    }
+}
+
+/// Splits a page segment that is shifted down by the specified number of pixels into the bits
+/// that end up on the page it starts on and the bits that end up on the page after.
+const fn split_segment(bits: u8, shift: u32) -> [u8; 2] {
+    if shift == 0 {
+        return [bits, 0];
+    }
+
+    [bits << shift, bits >> (8 - shift)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_split_segment {
+        (
+            $(
+                $fn_ident:ident, $bits:expr, $shift:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = split_segment($bits, $shift);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_split_segment! {
+        split_segment_no_shift, 0b1010_0101, 0, [0b1010_0101, 0b0000_0000],
+        split_segment_shift_by_1, 0b1010_0101, 1, [0b0100_1010, 0b0000_0001],
+        split_segment_shift_by_4, 0b1010_0101, 4, [0b0101_0000, 0b0000_1010],
+        split_segment_shift_by_7, 0b1010_0101, 7, [0b1000_0000, 0b0101_0010],
+        split_segment_empty, 0b0000_0000, 3, [0b0000_0000, 0b0000_0000],
+        split_segment_full, 0b1111_1111, 5, [0b1110_0000, 0b0001_1111],
+    }
+}
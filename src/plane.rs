@@ -0,0 +1,222 @@
+//! Bit planes for grayscale e-paper displays.
+//!
+//! E-paper display controllers that show four or sixteen gray levels commonly do so by having
+//! every bit of the gray values loaded into a separate 1-bit memory plane, with the gray level of
+//! each pixel selected by the waveform that the bits across all planes refer to. A [`BitPlanes`]
+//! is a draw target for [`Gray2`] or [`Gray4`] colors that splits every pixel into its bits, each
+//! of which is drawn as a [`BinaryColor`] pixel onto its own plane, so that anti-aliased text can
+//! be drawn without having a grayscale framebuffer.
+
+use embedded_graphics::Pixel;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Dimensions, Point};
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, GrayColor};
+use embedded_graphics::primitives::{PointsIter, Rectangle};
+
+/// The number of pixels that are passed on to the planes at a time.
+const CHUNK_SIZE: usize = 64;
+
+/// Bit order, for which bit of the gray values is drawn onto the first plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BitOrder {
+    /// The most significant bit is drawn onto the first plane, and the least significant bit onto
+    /// the last plane.
+    #[default]
+    MsbFirst,
+    /// The least significant bit is drawn onto the first plane, and the most significant bit onto
+    /// the last plane.
+    LsbFirst,
+}
+
+/// Bit planes, a draw target that splits gray values into one [`BinaryColor`] plane per bit.
+///
+/// Every plane is a draw target of its own, such as a buffer for one of the memory planes of a
+/// display controller; a bit that is set is drawn as [`BinaryColor::On`]. The bounding box is that
+/// of the first plane.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BitPlanes<'a, D: DrawTarget<Color = BinaryColor>, const B: usize> {
+    planes: [&'a mut D; B],
+    bit_order: BitOrder,
+}
+
+impl<'a, D: DrawTarget<Color = BinaryColor>, const B: usize> BitPlanes<'a, D, B> {
+    /// Creates new bit planes with the specified planes and bit order.
+    pub const fn new(planes: [&'a mut D; B], bit_order: BitOrder) -> Self {
+        Self { planes, bit_order }
+    }
+
+    /// Returns the bit order.
+    pub const fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    /// Draws the specified pixels onto every plane, a chunk at a time.
+    fn draw_chunks<I>(&mut self, pixels: I) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = (Point, u8)>,
+    {
+        let bit_order = self.bit_order;
+        let mut pixels = pixels.into_iter();
+        let mut chunk = [(Point::zero(), 0); CHUNK_SIZE];
+        loop {
+            let mut count = 0;
+            for (pixel, next_pixel) in chunk.iter_mut().zip(pixels.by_ref()) {
+                *pixel = next_pixel;
+                count += 1;
+            }
+
+            for (plane_index, plane) in self.planes.iter_mut().enumerate() {
+                let pixels = chunk[..count].iter().map(|&(point, luma)| {
+                    let bit = plane_bit(luma, plane_index, B, bit_order);
+
+                    Pixel(point, BinaryColor::from(bit))
+                });
+
+                plane.draw_iter(pixels)?;
+            }
+
+            if count < CHUNK_SIZE {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Returns whether the bit of the specified gray value that is drawn onto the specified plane,
+/// out of the specified number of planes, is set.
+const fn plane_bit(luma: u8, plane_index: usize, planes: usize, bit_order: BitOrder) -> bool {
+    let bit_index = match bit_order {
+        BitOrder::MsbFirst => planes - 1 - plane_index,
+        BitOrder::LsbFirst => plane_index,
+    };
+
+    luma >> bit_index & 1 == 1
+}
+
+impl<D: DrawTarget<Color = BinaryColor>, const B: usize> Dimensions for BitPlanes<'_, D, B> {
+    fn bounding_box(&self) -> Rectangle {
+        self.planes
+            .first()
+            .map_or_else(Rectangle::zero, |plane| plane.bounding_box())
+    }
+}
+
+macro_rules! impl_draw_target {
+    (
+        $(
+            $color_type:ty, $planes:literal,
+        )*
+    ) => {
+        $(
+            impl<D: DrawTarget<Color = BinaryColor>> DrawTarget for BitPlanes<'_, D, $planes> {
+                type Color = $color_type;
+                type Error = D::Error;
+
+                fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+                where
+                    I: IntoIterator<Item = Pixel<Self::Color>>,
+                {
+                    let pixels = pixels.into_iter();
+                    let pixels = pixels.map(|Pixel(point, color)| (point, color.luma()));
+
+                    self.draw_chunks(pixels)
+                }
+
+                fn fill_contiguous<I>(
+                    &mut self,
+                    area: &Rectangle,
+                    colors: I,
+                ) -> Result<(), Self::Error>
+                where
+                    I: IntoIterator<Item = Self::Color>,
+                {
+                    let colors = colors.into_iter().map(|color| color.luma());
+                    let pixels = area.points().zip(colors);
+
+                    self.draw_chunks(pixels)
+                }
+
+                fn fill_solid(
+                    &mut self,
+                    area: &Rectangle,
+                    color: Self::Color,
+                ) -> Result<(), Self::Error> {
+                    let bit_order = self.bit_order;
+                    for (plane_index, plane) in self.planes.iter_mut().enumerate() {
+                        let bit = plane_bit(color.luma(), plane_index, $planes, bit_order);
+                        plane.fill_solid(area, BinaryColor::from(bit))?;
+                    }
+
+                    Ok(())
+                }
+
+                fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+                    let bit_order = self.bit_order;
+                    for (plane_index, plane) in self.planes.iter_mut().enumerate() {
+                        let bit = plane_bit(color.luma(), plane_index, $planes, bit_order);
+                        plane.clear(BinaryColor::from(bit))?;
+                    }
+
+                    Ok(())
+                }
+            }
+        )*
+    }
+}
+
+impl_draw_target! {
+    Gray2, 2,
+    Gray4, 4,
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::geometry::Size;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    use super::*;
+
+    macro_rules! test_plane_bit {
+        (
+            $(
+                $fn_ident:ident, $luma:expr, $planes:expr, $bit_order:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result: [bool; $planes] =
+                        core::array::from_fn(|index| plane_bit($luma, index, $planes, $bit_order));
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_plane_bit! {
+        plane_bit_2bpp_0_msb_first, 0b00, 2, BitOrder::MsbFirst, [false, false],
+        plane_bit_2bpp_1_msb_first, 0b01, 2, BitOrder::MsbFirst, [false, true],
+        plane_bit_2bpp_2_msb_first, 0b10, 2, BitOrder::MsbFirst, [true, false],
+        plane_bit_2bpp_3_msb_first, 0b11, 2, BitOrder::MsbFirst, [true, true],
+        plane_bit_2bpp_1_lsb_first, 0b01, 2, BitOrder::LsbFirst, [true, false],
+        plane_bit_2bpp_2_lsb_first, 0b10, 2, BitOrder::LsbFirst, [false, true],
+        plane_bit_4bpp_9_msb_first, 0b1001, 4, BitOrder::MsbFirst, [true, false, false, true],
+        plane_bit_4bpp_12_msb_first, 0b1100, 4, BitOrder::MsbFirst, [true, true, false, false],
+        plane_bit_4bpp_12_lsb_first, 0b1100, 4, BitOrder::LsbFirst, [false, false, true, true],
+    }
+
+    #[test]
+    fn bit_planes_fill_contiguous() {
+        let mut first = MockDisplay::new();
+        let mut second = MockDisplay::new();
+        let mut planes = BitPlanes::new([&mut first, &mut second], BitOrder::MsbFirst);
+        let area = Rectangle::new(Point::new(1, 0), Size::new(2, 2));
+        let colors = [0, 1, 2, 3].map(Gray2::new);
+        planes.fill_contiguous(&area, colors).unwrap();
+
+        first.assert_pattern(&[" ..", " ##"]);
+        second.assert_pattern(&[" .#", " .#"]);
+    }
+}
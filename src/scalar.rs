@@ -0,0 +1,422 @@
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// Scalar value for advance widths, glyph offsets, and font metrics, in pixels.
+///
+/// By default, values are stored as floating-point numbers. With the `fixed-point` feature enabled,
+/// values are stored in 16.16 fixed-point format instead, that is, in units of _1/65536_ pixels,
+/// and the layout of text runs is done using integer arithmetic only. The interface of this type
+/// is the same either way; the feature only affects precision and how values are computed.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Scalar(Repr);
+
+#[cfg(not(feature = "fixed-point"))]
+type Repr = f32;
+
+#[cfg(feature = "fixed-point")]
+type Repr = i32;
+
+/// The number of fractional bits in a fixed-point value.
+const FRACTION_BITS: u32 = 16;
+
+impl Scalar {
+    /// The scalar value for zero pixels.
+    pub const ZERO: Self = Self::from_int(0);
+
+    /// Returns the scalar value for the specified number of pixels.
+    #[cfg(not(feature = "fixed-point"))]
+    pub const fn from_int(value: i32) -> Self {
+        Self(value as f32)
+    }
+
+    /// Returns the scalar value for the specified number of pixels.
+    #[cfg(feature = "fixed-point")]
+    pub const fn from_int(value: i32) -> Self {
+        Self(value.saturating_mul(1 << FRACTION_BITS))
+    }
+
+    /// Returns the scalar value for the specified floating-point number of pixels.
+    #[cfg(not(feature = "fixed-point"))]
+    pub const fn from_f32(value: f32) -> Self {
+        Self(value)
+    }
+
+    /// Returns the scalar value for the specified floating-point number of pixels.
+    #[cfg(feature = "fixed-point")]
+    pub const fn from_f32(value: f32) -> Self {
+        Self(round(value * (1 << FRACTION_BITS) as f32))
+    }
+
+    /// Returns the scalar value for the specified 16.16 fixed-point number of pixels.
+    #[cfg(not(feature = "fixed-point"))]
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits as f32 / (1 << FRACTION_BITS) as f32)
+    }
+
+    /// Returns the scalar value for the specified 16.16 fixed-point number of pixels.
+    #[cfg(feature = "fixed-point")]
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns this scalar value as a floating-point number of pixels.
+    #[cfg(not(feature = "fixed-point"))]
+    pub const fn to_f32(self) -> f32 {
+        self.0
+    }
+
+    /// Returns this scalar value as a floating-point number of pixels.
+    #[cfg(feature = "fixed-point")]
+    pub const fn to_f32(self) -> f32 {
+        self.0 as f32 / (1 << FRACTION_BITS) as f32
+    }
+
+    /// Returns this scalar value as a 16.16 fixed-point number of pixels, rounded to the nearest
+    /// _1/65536_ pixels.
+    #[cfg(not(feature = "fixed-point"))]
+    pub const fn to_bits(self) -> i32 {
+        round(self.0 * (1 << FRACTION_BITS) as f32)
+    }
+
+    /// Returns this scalar value as a 16.16 fixed-point number of pixels, rounded to the nearest
+    /// _1/65536_ pixels.
+    #[cfg(feature = "fixed-point")]
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+}
+
+impl Add for Scalar {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(add(self.0, rhs.0))
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(sub(self.0, rhs.0))
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(neg(self.0))
+    }
+}
+
+impl AddAssign for Scalar {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 = add(self.0, rhs.0);
+    }
+}
+
+impl SubAssign for Scalar {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 = sub(self.0, rhs.0);
+    }
+}
+
+/// Returns the sum of the specified values.
+#[cfg(not(feature = "fixed-point"))]
+const fn add(value: Repr, other: Repr) -> Repr {
+    value + other
+}
+
+/// Returns the sum of the specified values, saturating at the numeric bounds instead of
+/// overflowing.
+#[cfg(feature = "fixed-point")]
+const fn add(value: Repr, other: Repr) -> Repr {
+    value.saturating_add(other)
+}
+
+/// Returns the difference of the specified values.
+#[cfg(not(feature = "fixed-point"))]
+const fn sub(value: Repr, other: Repr) -> Repr {
+    value - other
+}
+
+/// Returns the difference of the specified values, saturating at the numeric bounds instead of
+/// overflowing.
+#[cfg(feature = "fixed-point")]
+const fn sub(value: Repr, other: Repr) -> Repr {
+    value.saturating_sub(other)
+}
+
+/// Returns the negation of the specified value.
+#[cfg(not(feature = "fixed-point"))]
+const fn neg(value: Repr) -> Repr {
+    -value
+}
+
+/// Returns the negation of the specified value, saturating at the numeric bounds instead of
+/// overflowing.
+#[cfg(feature = "fixed-point")]
+const fn neg(value: Repr) -> Repr {
+    value.saturating_neg()
+}
+
+/// Returns the specified floating-point value rounded to the nearest integer, with halfway cases
+/// rounded away from zero.
+const fn round(value: f32) -> i32 {
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+/// Returns the number of whole pixels in the specified scalar value, rounding towards zero.
+#[cfg(not(feature = "fixed-point"))]
+pub(crate) const fn trunc(value: Scalar) -> i32 {
+    value.0 as i32
+}
+
+/// Returns the number of whole pixels in the specified scalar value, rounding towards zero.
+#[cfg(feature = "fixed-point")]
+pub(crate) const fn trunc(value: Scalar) -> i32 {
+    value.0 / (1 << FRACTION_BITS)
+}
+
+/// Returns the number of whole pixels in the specified scalar value, rounding down.
+#[cfg(not(feature = "fixed-point"))]
+pub(crate) const fn floor(value: Scalar) -> i32 {
+    let result = value.0 as i32;
+
+    result.saturating_sub(((result as f32) > value.0) as i32)
+}
+
+/// Returns the number of whole pixels in the specified scalar value, rounding down.
+#[cfg(feature = "fixed-point")]
+pub(crate) const fn floor(value: Scalar) -> i32 {
+    value.0 >> FRACTION_BITS
+}
+
+/// Returns half of the specified scalar value.
+#[cfg(not(feature = "fixed-point"))]
+pub(crate) const fn half(value: Scalar) -> Scalar {
+    Scalar(value.0 / 2.0)
+}
+
+/// Returns half of the specified scalar value.
+#[cfg(feature = "fixed-point")]
+pub(crate) const fn half(value: Scalar) -> Scalar {
+    Scalar(value.0 >> 1)
+}
+
+/// Returns the remainder of the specified scalar value divided by `divisor`, which is never
+/// negative for a positive divisor.
+#[cfg(not(feature = "fixed-point"))]
+pub(crate) fn rem_euclid(value: Scalar, divisor: Scalar) -> Scalar {
+    let result = value.0 % divisor.0;
+
+    Scalar(if result < 0.0 {
+        result + divisor.0
+    } else {
+        result
+    })
+}
+
+/// Returns the remainder of the specified scalar value divided by `divisor`, which is never
+/// negative for a positive divisor.
+#[cfg(feature = "fixed-point")]
+pub(crate) fn rem_euclid(value: Scalar, divisor: Scalar) -> Scalar {
+    Scalar(value.0.rem_euclid(divisor.0))
+}
+
+/// Returns the specified scalar value divided by `divisor`, rounded up to the nearest pixel.
+#[cfg(not(feature = "fixed-point"))]
+pub(crate) const fn div_ceil(value: Scalar, divisor: u8) -> i32 {
+    let value = value.0;
+    let divisor = divisor as f32;
+
+    ((value / divisor) as i32).saturating_add((value % divisor > 0.0) as i32)
+}
+
+/// Returns the specified scalar value divided by `divisor`, rounded up to the nearest pixel.
+///
+/// The divisor is expected to be a power of two.
+#[cfg(feature = "fixed-point")]
+pub(crate) const fn div_ceil(value: Scalar, divisor: u8) -> i32 {
+    let shift = FRACTION_BITS + divisor.trailing_zeros();
+
+    -(-(value.0 as i64) >> shift) as i32
+}
+
+/// Returns the specified scalar value divided by `divisor`, rounded to the nearest integer, or
+/// zero if the divisor is not positive.
+#[cfg(not(feature = "fixed-point"))]
+pub(crate) const fn div_round(value: Scalar, divisor: Scalar) -> i32 {
+    if divisor.0 <= 0.0 {
+        return 0;
+    }
+
+    floor(Scalar(value.0 / divisor.0 + 0.5))
+}
+
+/// Returns the specified scalar value divided by `divisor`, rounded to the nearest integer, or
+/// zero if the divisor is not positive.
+#[cfg(feature = "fixed-point")]
+pub(crate) const fn div_round(value: Scalar, divisor: Scalar) -> i32 {
+    if divisor.0 <= 0 {
+        return 0;
+    }
+
+    let divisor = divisor.0 as i64 * 2;
+
+    (value.0 as i64 * 2 + divisor / 2).div_euclid(divisor) as i32
+}
+
+/// Returns the index of the sub-pixel offset for the specified position along the _x_-axis, with
+/// `n` offsets per pixel.
+#[cfg(not(feature = "fixed-point"))]
+pub(crate) const fn sub_pixel_index(value: Scalar, n: usize) -> usize {
+    floor(Scalar(value.0 * n as f32)).rem_euclid(n as i32) as usize
+}
+
+/// Returns the index of the sub-pixel offset for the specified position along the _x_-axis, with
+/// `n` offsets per pixel.
+#[cfg(feature = "fixed-point")]
+pub(crate) const fn sub_pixel_index(value: Scalar, n: usize) -> usize {
+    let fraction = (value.0 & ((1 << FRACTION_BITS) - 1)) as u32;
+
+    ((fraction as u64 * n as u64) >> FRACTION_BITS) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_half {
+        (
+            $(
+                $fn_ident:ident, $value:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = half($value);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_div_ceil {
+        (
+            $(
+                $fn_ident:ident, $value:expr, $divisor:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = div_ceil($value, $divisor);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_div_round {
+        (
+            $(
+                $fn_ident:ident, $value:expr, $divisor:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = div_round($value, $divisor);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_floor {
+        (
+            $(
+                $fn_ident:ident, $value:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = floor($value);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_sub_pixel_index {
+        (
+            $(
+                $fn_ident:ident, $value:expr, $n:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = sub_pixel_index($value, $n);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    const fn sixteenths(value: i32) -> Scalar {
+        Scalar::from_bits(value << (FRACTION_BITS - 4))
+    }
+
+    test_half! {
+        half_even, sixteenths(96), sixteenths(48),
+        half_fraction, sixteenths(100), sixteenths(50),
+        half_negative, sixteenths(-40), sixteenths(-20),
+        half_zero, sixteenths(0), sixteenths(0),
+    }
+
+    test_div_ceil! {
+        div_ceil_whole, sixteenths(192), 1, 12,
+        div_ceil_fraction, sixteenths(193), 1, 13,
+        div_ceil_negative_whole, sixteenths(-48), 1, -3,
+        div_ceil_negative_fraction, sixteenths(-40), 1, -2,
+        div_ceil_half_even, sixteenths(96), 2, 3,
+        div_ceil_half_odd, sixteenths(112), 2, 4,
+        div_ceil_half_fraction, sixteenths(100), 2, 4,
+    }
+
+    test_div_round! {
+        div_round_whole, sixteenths(144), sixteenths(72), 2,
+        div_round_fraction, sixteenths(208), sixteenths(72), 3,
+        div_round_half, sixteenths(36), sixteenths(72), 1,
+        div_round_below_one, sixteenths(64), sixteenths(72), 1,
+        div_round_negative, sixteenths(-144), sixteenths(72), -2,
+        div_round_zero_divisor, sixteenths(144), sixteenths(0), 0,
+    }
+
+    test_floor! {
+        floor_whole, sixteenths(80), 5,
+        floor_fraction, sixteenths(95), 5,
+        floor_negative_whole, sixteenths(-80), -5,
+        floor_negative_fraction, sixteenths(-81), -6,
+        floor_zero, sixteenths(0), 0,
+    }
+
+    test_sub_pixel_index! {
+        sub_pixel_index_whole, sixteenths(80), 4, 0,
+        sub_pixel_index_quarter, sixteenths(84), 4, 1,
+        sub_pixel_index_below_quarter, sixteenths(83), 4, 0,
+        sub_pixel_index_three_quarters, sixteenths(92), 4, 3,
+        sub_pixel_index_single_position, sixteenths(95), 1, 0,
+        sub_pixel_index_negative_half, sixteenths(-8), 4, 2,
+        sub_pixel_index_negative_quarter, sixteenths(-4), 4, 3,
+    }
+}
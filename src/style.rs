@@ -11,8 +11,10 @@
 //!   defaults to black; this color is filled in from top to bottom, for the length of the text run.
 //! </div>
 
+mod resumable;
+
 use core::cell::RefCell;
-use core::iter;
+use core::str::Chars;
 
 use embedded_graphics::Drawable;
 use embedded_graphics::draw_target::DrawTarget;
@@ -26,14 +28,19 @@ use embedded_graphics::text::{Baseline, DecorationColor};
 
 use crate::adapter::DrawTargetExt;
 use crate::charmap::{Charmap, CharmapEntry};
-use crate::color::{Colormap, Invert, Linear, Screen, WeightedAvg};
+use crate::color::{
+    Blend, BlendMode, Colormap, Dithering, Gradient, Interpolation, Invert, Linear, Opacity,
+    Screen, WeightedAvg,
+};
 use crate::font::BitmapFont;
 use crate::glyph::NextGlyph;
 use crate::image::{Image, ImageRaw, Mixed, WithColormap};
 use crate::metrics::DecorationDimensions;
 use crate::rect::RectangleExt;
+use crate::scalar::{self, Scalar};
 
 pub use crate::builder::BitmapFontStyleBuilder;
+pub use resumable::{DrawLimit, ResumableString};
 
 type StyledRectangle<T> = Styled<Rectangle, PrimitiveStyle<T>>;
 
@@ -56,27 +63,44 @@ where
     pub underline_color: DecorationColor<T>,
     /// The strikethrough color.
     pub strikethrough_color: DecorationColor<T>,
+    /// The opacity factor, by which the text and decoration colors are faded towards the
+    /// background color; `255` is fully opaque.
+    pub opacity: u8,
+    /// The interpolation of the colors in between the background color and the text color, for
+    /// the gray values of glyph images.
+    pub interpolation: Interpolation,
+    /// The optional gradient for the gray values of glyph images, which, when set to a value,
+    /// takes the place of the gradient from the background color to the text color.
+    pub gradient: Option<Gradient<'b, T>>,
+    /// The blend mode for mixing the colors where glyph images overlap within a text run; where a
+    /// glyph image overlaps with one carried over from a call with different colors, the colors
+    /// are mixed in [`WeightedAvg`] blend mode instead.
+    pub blend_mode: BlendMode,
+    /// The dithering of the gray values of glyph images, for when the colormap has fewer distinct
+    /// colors than there are gray values; where glyph images overlap, the colors are mixed without
+    /// dithering.
+    pub dithering: Dithering,
     /// The carryover from a previous call to either the [draw_string](Self::draw_string) method or
     /// the [draw_whitespace](Self::draw_whitespace) method.
-    carryover: RefCell<Option<Carryover<'a, T, C, 2>>>,
+    carryover: RefCell<Option<Carryover<'a, 'b, T, C, 2>>>,
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-struct Carryover<'a, T, C, const N: usize>
+struct Carryover<'a, 'b, T, C, const N: usize>
 where
     C: PixelColor + From<C::Raw>,
     T: PixelColor + Default + Invert + Screen + WeightedAvg,
     RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
 {
-    previous_image_colorable: Option<PreviousImageColorable<'a, T, C>>,
+    previous_image_colorable: Option<PreviousImageColorable<'a, 'b, T, C>>,
     decorations: [Option<StyledRectangle<T>>; N],
     line_piece: Rectangle,
 }
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-struct PreviousImageColorable<'a, T, C>
+struct PreviousImageColorable<'a, 'b, T, C>
 where
     C: PixelColor + From<C::Raw>,
     T: PixelColor + Default + Invert + Screen + WeightedAvg,
@@ -85,6 +109,54 @@ where
     previous_image: Image<ImageRaw<'a, C>>,
     text_color: T,
     background_color: T,
+    opacity: u8,
+    interpolation: Interpolation,
+    gradient: Option<Gradient<'b, T>>,
+}
+
+impl<'a, T, C> PreviousImageColorable<'a, '_, T, C>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Returns the colormap for the gray values of the previous glyph image, which is given by the
+    /// gradient of the style it was drawn with, if there is one, or else is the gradient from the
+    /// specified background color to the text color it was drawn with.
+    fn colormap<const M: usize>(&self, background_color: T) -> Colormap<T, M>
+    where
+        Colormap<T, M>: Linear<T>,
+    {
+        let gradient = self.gradient.as_ref();
+        let colormap = gradient.and_then(|gradient| gradient.colormap(self.interpolation));
+
+        colormap.unwrap_or_else(|| {
+            Colormap::interpolated(background_color, self.text_color, self.interpolation)
+        })
+    }
+}
+
+/// The state of drawing a text run, holding the glyph images that are yet to be drawn as well as
+/// the parts of the glyph images that have been drawn so far.
+#[derive(Debug, Clone)]
+pub(crate) struct LineState<'a, 'b, 't, T, C, const N: usize, const M: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: Copy,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    images: ImagesOfChars<'a, 'b, 't, C, N>,
+    colormap: Colormap<T, M>,
+    pending_image: Option<(Image<ImageRaw<'a, C>>, bool)>,
+    position: Point,
+    y: i32,
+    right: i32,
+    previous_right: i32,
+    previous_image: Option<Image<ImageRaw<'a, C>>>,
+    image_before_overlays: Option<Image<ImageRaw<'a, C>>>,
+    visible_left: i32,
+    visible_right: i32,
+    is_past_visible: bool,
 }
 
 impl<'a, 'b, T, C, const N: usize> BitmapFontStyle<'a, 'b, T, C, N>
@@ -110,19 +182,24 @@ where
             background_color: None,
             underline_color: DecorationColor::None,
             strikethrough_color: DecorationColor::None,
+            opacity: u8::MAX,
+            interpolation: Interpolation::Encoded,
+            gradient: None,
+            blend_mode: BlendMode::Screen,
+            dithering: Dithering::None,
             carryover: RefCell::new(None),
         }
     }
 
     /// Returns the text color, falling back to the inverse of the default value for type `T` when
     /// not set to a value.
-    fn text_color(&self) -> T {
+    pub(crate) fn text_color(&self) -> T {
         self.text_color.unwrap_or(T::default().invert())
     }
 
     /// Returns the background color, falling back to the default value for type `T` when not set
     /// to a value.
-    fn background_color(&self) -> T {
+    pub(crate) fn background_color(&self) -> T {
         self.background_color.unwrap_or_default()
     }
 
@@ -146,6 +223,49 @@ where
         }
     }
 
+    /// Returns the colormap for the gray values of glyph images, which is given by the gradient, if
+    /// there is one, or else is the gradient from the specified background color to the specified
+    /// text color, using the specified interpolation.
+    pub(crate) fn colormap<const M: usize>(
+        &self,
+        background_color: T,
+        text_color: T,
+        interpolation: Interpolation,
+    ) -> Colormap<T, M>
+    where
+        Colormap<T, M>: Linear<T>,
+    {
+        let gradient = self.gradient.as_ref();
+        let colormap = gradient.and_then(|gradient| gradient.colormap(interpolation));
+
+        colormap
+            .unwrap_or_else(|| Colormap::interpolated(background_color, text_color, interpolation))
+    }
+
+    /// Returns the colormap for the gray values of glyph images drawn with this style, which is
+    /// faded towards the background color unless the style is fully opaque.
+    pub(crate) fn faded_colormap<const M: usize>(&self) -> Colormap<T, M>
+    where
+        T: Opacity,
+        Colormap<T, M>: Linear<T>,
+    {
+        let colormap = self.colormap(
+            self.background_color(),
+            self.text_color(),
+            self.interpolation,
+        );
+
+        match self.opacity {
+            u8::MAX => colormap,
+            opacity => colormap.with_opacity(opacity),
+        }
+    }
+
+    /// Discards the carryover, so that the next text run is drawn as if it were the first.
+    pub(crate) fn discard_carryover(&self) {
+        self.carryover.take();
+    }
+
     fn decorations_drawn<D>(
         &self,
         left: i32,
@@ -154,10 +274,12 @@ where
         target: &mut D,
     ) -> Result<[Option<StyledRectangle<T>>; 2], D::Error>
     where
+        T: Opacity,
         D: DrawTarget<Color = T>,
     {
         let stroke = |dimensions: DecorationDimensions, stroke_color: Option<_>| {
-            stroke_color.map(|stroke_color| {
+            stroke_color.map(|stroke_color: T| {
+                let stroke_color = stroke_color.with_opacity(self.background_color(), self.opacity);
                 let top = baseline.saturating_sub(dimensions.y_offset());
                 let height = dimensions.stroke_width();
                 let stroke_style = PrimitiveStyle::with_fill(stroke_color);
@@ -190,9 +312,9 @@ macro_rules! impl_carryover {
         )*
     ) => {
         $(
-            impl<'a, 'b, T, const N: usize> Carryover<'a, T, $color_type, N>
+            impl<'a, 'b, T, const N: usize> Carryover<'a, 'b, T, $color_type, N>
             where
-                T: PixelColor + Default + Invert + Screen + WeightedAvg,
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
                 Colormap<T, $array_length>: Linear<T>,
             {
                 fn redraw_whitespace<D, const M: usize>(
@@ -212,9 +334,9 @@ macro_rules! impl_carryover {
                             let previous_image = &previous_image_colorable.previous_image;
                             let previous_image_box = previous_image.bounding_box();
                             if background_color != previous_image_colorable.background_color {
-                                let text_color = previous_image_colorable.text_color;
-                                let colormap = Colormap::linear(background_color, text_color);
-                                let mut adapter = target.value_mapped(&colormap);
+                                let colormap = previous_image_colorable.colormap(background_color);
+                                let colormap = colormap.with_opacity(previous_image_colorable.opacity);
+                                let mut adapter = target.value_mapped(&colormap, style.dithering);
                                 previous_image.clipped(&intersection).draw(&mut adapter)?;
 
                                 let above = intersection.above(&previous_image_box);
@@ -253,6 +375,71 @@ macro_rules! impl_carryover {
     }
 }
 
+impl<'a, 'b, 't, T, C, const N: usize, const M: usize> LineState<'a, 'b, 't, T, C, N, M>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    Colormap<T, M>: Linear<T>,
+{
+    /// Creates a new state for drawing the specified text run with the specified style.
+    pub(crate) fn new(
+        style: &BitmapFontStyle<'a, 'b, T, C, N>,
+        text: &'t str,
+        position: Point,
+        baseline: Baseline,
+    ) -> Self {
+        Self::with_scalar_x(
+            style,
+            text,
+            Scalar::from_int(position.x),
+            position.y,
+            baseline,
+            style.faded_colormap(),
+        )
+    }
+
+    /// Creates a new state for drawing the specified text run with the specified style, starting
+    /// at a position along the _x_-axis that can be in between pixels, and with the specified
+    /// colormap, which callers drawing several text runs with the same style can build only once.
+    pub(crate) fn with_scalar_x(
+        style: &BitmapFontStyle<'a, 'b, T, C, N>,
+        text: &'t str,
+        x: Scalar,
+        y: i32,
+        baseline: Baseline,
+        colormap: Colormap<T, M>,
+    ) -> Self {
+        let position = Point::new(scalar::floor(x), y);
+        let y = y.saturating_add(style.font.metrics.y_offset(baseline));
+        let images = images_of_chars(&style.font.charmap, text, x, Scalar::from_int(y));
+
+        Self {
+            images,
+            colormap,
+            pending_image: None,
+            position,
+            y,
+            right: position.x,
+            previous_right: position.x,
+            previous_image: None,
+            image_before_overlays: None,
+            visible_left: i32::MIN,
+            visible_right: i32::MAX,
+            is_past_visible: false,
+        }
+    }
+
+    /// Returns this state with the drawing of glyph images restricted to the glyph clusters that
+    /// are visible between the specified positions along the _x_-axis; glyph images to the left
+    /// are skipped, and drawing stops at the first glyph cluster to the right.
+    pub(crate) fn visible_between(mut self, left: i32, right: i32) -> Self {
+        self.visible_left = left;
+        self.visible_right = right;
+        self
+    }
+}
+
 impl_carryover! {
     BinaryColor, 2,
     Gray2, 4,
@@ -285,62 +472,93 @@ where
     }
 }
 
-macro_rules! impl_text_renderer {
+macro_rules! impl_line_state {
     (
         $(
             $color_type:ty, $array_length:literal,
         )*
     ) => {
         $(
-            impl<T, const N: usize> TextRenderer for BitmapFontStyle<'_, '_, T, $color_type, N>
+            impl<'a, 'b, T, const N: usize> LineState<'a, 'b, '_, T, $color_type, N, $array_length>
             where
-                T: PixelColor + Default + Invert + Screen + WeightedAvg,
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
                 Colormap<T, $array_length>: Linear<T>,
             {
-                type Color = T;
-
-                fn draw_string<D>(
-                    &self,
-                    text: &str,
-                    position: Point,
-                    baseline: Baseline,
+                /// Draws the glyph images, stopping at the start of a glyph cluster once the
+                /// specified limit has been reached, returning whether all visible images have
+                /// been drawn.
+                pub(crate) fn draw_images<D>(
+                    &mut self,
+                    style: &BitmapFontStyle<'a, 'b, T, $color_type, N>,
+                    limit: Option<DrawLimit>,
                     target: &mut D,
-                ) -> Result<Point, D::Error>
+                ) -> Result<bool, D::Error>
                 where
-                    D: DrawTarget<Color = Self::Color>,
+                    D: DrawTarget<Color = T>,
                 {
-                    let mut right = position.x;
-                    let mut x = position.x as f32;
-                    let y = position.y.saturating_add(self.font.metrics.y_offset(baseline));
-                    let top = y.saturating_sub(self.font.metrics.y_offset(Baseline::Top));
-                    let bottom = y.saturating_sub(self.font.metrics.y_offset(Baseline::Bottom));
+                    let top = self.y.saturating_sub(style.font.metrics.y_offset(Baseline::Top));
+                    let bottom = self.y.saturating_sub(style.font.metrics.y_offset(Baseline::Bottom));
                     let height = bottom.saturating_sub(top).try_into().unwrap_or_default();
-                    let background_style = PrimitiveStyle::with_fill(self.background_color());
+                    let background_style = PrimitiveStyle::with_fill(style.background_color());
                     let line_strip = Rectangle {
-                        top_left: Point::new(position.x, top),
+                        top_left: Point::new(self.position.x, top),
                         size: Size::new(u32::MAX, height),
                     };
 
-                    let colormap = Colormap::linear(self.background_color(), self.text_color());
-                    let images = images_of_chars(&self.font.charmap, text, &mut x, y as f32);
-                    let mut image_before_overlays: Option<Image<_>> = None;
-                    let mut previous_image: Option<Image<_>> = None;
-                    let mut previous_right = right;
-                    for (image, is_overlay) in images {
+                    let colormap = &self.colormap;
+                    let mut clusters = 0;
+                    let mut pixels = 0u32;
+                    while let Some((image, is_overlay)) =
+                        self.pending_image.take().or_else(|| self.images.next())
+                    {
                         let image_box = image.bounding_box();
-                        let x = image_box.top_left.x.saturating_add_unsigned(image_box.size.width);
-                        if x > right {
-                            right = x;
+                        let image_right = image_box.top_left.x.saturating_add_unsigned(image_box.size.width);
+                        if self.previous_image.is_none() && image_right <= self.visible_left {
+                            continue;
                         }
 
-                        if !is_overlay && image_before_overlays.is_some() {
-                            previous_image = image_before_overlays.take();
+                        if !is_overlay && self.is_past_visible {
+                            self.pending_image = Some((image, is_overlay));
+
+                            return Ok(true);
+                        }
+
+                        if !is_overlay && image_box.top_left.x >= self.visible_right {
+                            self.is_past_visible = true;
+                        }
+
+                        let area = image_box.size.width.saturating_mul(image_box.size.height);
+                        if !is_overlay && clusters > 0 {
+                            let is_spent = match limit {
+                                Some(DrawLimit::Clusters(max)) => clusters >= max,
+                                Some(DrawLimit::Pixels(max)) => pixels.saturating_add(area) > max,
+                                None => false,
+                            };
+                            if is_spent {
+                                self.pending_image = Some((image, is_overlay));
+
+                                return Ok(false);
+                            }
+                        }
+
+                        if !is_overlay {
+                            clusters += 1;
+                        }
+
+                        pixels = pixels.saturating_add(area);
+
+                        if image_right > self.right {
+                            self.right = image_right;
+                        }
+
+                        if !is_overlay && self.image_before_overlays.is_some() {
+                            self.previous_image = self.image_before_overlays.take();
                         }
 
                         let line_piece = line_strip.left_of(&image_box);
-                        let clip_area = if let Some(previous_image) = previous_image.as_ref() {
+                        let clip_area = if let Some(previous_image) = self.previous_image.as_ref() {
                             let previous_image_box = previous_image.bounding_box();
-                            let previous_right_half = previous_image_box.indent_to(previous_right);
+                            let previous_right_half = previous_image_box.indent_to(self.previous_right);
                             let line_piece = line_piece.right_of(&previous_right_half);
                             line_piece.draw_styled(&background_style, target)?;
 
@@ -353,7 +571,7 @@ macro_rules! impl_text_renderer {
                             let above = middle.above(&image_box);
                             let below = middle.below(&image_box);
                             for clip_area in [left, right, above, below] {
-                                let mut adapter = target.value_mapped(&colormap);
+                                let mut adapter = target.value_mapped(&colormap, style.dithering);
                                 previous_image.clipped(&clip_area).draw(&mut adapter)?;
 
                                 let above = clip_area.above(&previous_image_box);
@@ -366,7 +584,7 @@ macro_rules! impl_text_renderer {
                             let image_box = if is_overlay {
                                 let image_box = image_box.y_reduce(top, bottom);
                                 let clip_area = image_box.left_of(&previous_image_box);
-                                let mut adapter = target.value_mapped(&colormap);
+                                let mut adapter = target.value_mapped(&colormap, style.dithering);
                                 image.clipped(&clip_area).draw(&mut adapter)?;
 
                                 image_box
@@ -378,15 +596,16 @@ macro_rules! impl_text_renderer {
                             let above = column.above(&previous_image_box);
                             let below = column.below(&previous_image_box);
                             for clip_area in [above, below] {
-                                let mut adapter = target.value_mapped(&colormap);
+                                let mut adapter = target.value_mapped(&colormap, style.dithering);
                                 image.clipped(&clip_area).draw(&mut adapter)?;
                             }
 
-                            image.mixed(previous_image, &colormap).draw(target)?;
+                            let image = image.mixed(previous_image, &colormap);
+                            image.with_blend_mode(style.blend_mode).draw(target)?;
 
                             image_box.right_of(&previous_image_box)
-                        } else if let Some(carryover) = self.carryover.take() {
-                            carryover.redraw_whitespace(self, line_piece, target)?;
+                        } else if let Some(carryover) = style.carryover.take() {
+                            carryover.redraw_whitespace(style, line_piece, target)?;
 
                             let image_box = image_box.left_half();
                             let line_piece = image_box.y_extend(top, bottom);
@@ -399,18 +618,19 @@ macro_rules! impl_text_renderer {
                                     let above = line_piece.above(&previous_image_box);
                                     let below = line_piece.below(&previous_image_box);
                                     for clip_area in [above, below] {
-                                        let mut adapter = target.value_mapped(&colormap);
+                                        let mut adapter = target.value_mapped(&colormap, style.dithering);
                                         image.clipped(&clip_area).draw(&mut adapter)?;
                                     }
 
                                     let image = image.with_colormap(&colormap);
-                                    let background_color = self.background_color();
-                                    let text_color = previous_image_colorable.text_color;
-                                    let colormap = Colormap::linear(background_color, text_color);
+                                    let background_color = style.background_color();
+                                    let colormap = previous_image_colorable.colormap(background_color);
+                                    let opacity = previous_image_colorable.opacity;
+                                    let colormap = colormap.with_opacity(opacity);
                                     let above = intersection.above(&image_box);
                                     let below = intersection.below(&image_box);
                                     for clip_area in [above, below] {
-                                        let mut adapter = target.value_mapped(&colormap);
+                                        let mut adapter = target.value_mapped(&colormap, style.dithering);
                                         previous_image.clipped(&clip_area).draw(&mut adapter)?;
 
                                         let above = clip_area.above(&previous_image_box);
@@ -422,7 +642,7 @@ macro_rules! impl_text_renderer {
 
                                     image.mixed(previous_image, &colormap).draw(target)?;
                                 } else {
-                                    let mut adapter = target.value_mapped(&colormap);
+                                    let mut adapter = target.value_mapped(&colormap, style.dithering);
                                     image.clipped(&intersection).draw(&mut adapter)?;
 
                                     let above = intersection.above(&image_box);
@@ -445,7 +665,7 @@ macro_rules! impl_text_renderer {
                                 let above = column.above(&intersection);
                                 let below = column.below(&intersection);
                                 for clip_area in [above, below] {
-                                    let mut adapter = target.value_mapped(&colormap);
+                                    let mut adapter = target.value_mapped(&colormap, style.dithering);
                                     image.clipped(&clip_area).draw(&mut adapter)?;
 
                                     let above = clip_area.above(&image_box);
@@ -464,10 +684,10 @@ macro_rules! impl_text_renderer {
 
                             image_box.left_half()
                         };
-                        let mut adapter = target.value_mapped(&colormap);
+                        let mut adapter = target.value_mapped(&colormap, style.dithering);
                         image.clipped(&clip_area).draw(&mut adapter)?;
 
-                        let right = clip_area.indent_to(previous_right);
+                        let right = clip_area.indent_to(self.previous_right);
                         let column = right.y_extend(top, bottom);
                         let above = column.above(&image_box);
                         let below = column.below(&image_box);
@@ -475,18 +695,37 @@ macro_rules! impl_text_renderer {
                             fill_area.draw_styled(&background_style, target)?;
                         }
 
-                        let previous_image = previous_image.replace(image);
-                        if is_overlay && image_before_overlays.is_none() {
-                            image_before_overlays = previous_image;
+                        let previous_image = self.previous_image.replace(image);
+                        if is_overlay && self.image_before_overlays.is_none() {
+                            self.image_before_overlays = previous_image;
                         }
 
-                        previous_right = right.top_left.x.saturating_add_unsigned(right.size.width);
+                        self.previous_right = right.top_left.x.saturating_add_unsigned(right.size.width);
                     }
 
-                    if let Some(previous_image) = previous_image.as_ref() {
+                    Ok(true)
+                }
+
+                /// Draws the right half of the last glyph image, the background, and the text
+                /// decorations, returning the position of the next character.
+                pub(crate) fn finish<D>(
+                    self,
+                    style: &BitmapFontStyle<'a, 'b, T, $color_type, N>,
+                    target: &mut D,
+                ) -> Result<Point, D::Error>
+                where
+                    D: DrawTarget<Color = T>,
+                {
+                    let Self { position, y, right, .. } = self;
+                    let top = y.saturating_sub(style.font.metrics.y_offset(Baseline::Top));
+                    let bottom = y.saturating_sub(style.font.metrics.y_offset(Baseline::Bottom));
+                    let height = bottom.saturating_sub(top).try_into().unwrap_or_default();
+                    let background_style = PrimitiveStyle::with_fill(style.background_color());
+                    let colormap = &self.colormap;
+                    if let Some(previous_image) = self.previous_image.as_ref() {
                         let previous_image_box = previous_image.bounding_box();
-                        let previous_right_half = previous_image_box.indent_to(previous_right);
-                        let mut adapter = target.value_mapped(&colormap);
+                        let previous_right_half = previous_image_box.indent_to(self.previous_right);
+                        let mut adapter = target.value_mapped(&colormap, style.dithering);
                         previous_image.clipped(&previous_right_half).draw(&mut adapter)?;
 
                         let column = previous_right_half.y_extend(top, bottom);
@@ -497,15 +736,18 @@ macro_rules! impl_text_renderer {
                         }
                     }
 
-                    let previous_image_colorable = previous_image.map(|previous_image| {
+                    let previous_image_colorable = self.previous_image.map(|previous_image| {
                         PreviousImageColorable {
                             previous_image,
-                            text_color: self.text_color(),
-                            background_color: self.background_color(),
+                            text_color: style.text_color(),
+                            background_color: style.background_color(),
+                            opacity: style.opacity,
+                            interpolation: style.interpolation,
+                            gradient: style.gradient,
                         }
                     });
 
-                    let next_position = Point::new(x as i32, position.y);
+                    let next_position = Point::new(scalar::trunc(self.images.x), position.y);
                     let width = next_position.x.saturating_sub(right);
                     let width = width.try_into().unwrap_or_default();
                     let line_piece = Rectangle {
@@ -525,19 +767,59 @@ macro_rules! impl_text_renderer {
                     let right = next_position.x.max(right);
                     let width = right.saturating_sub(position.x);
                     let width = width.try_into().unwrap_or_default();
-                    let decorations = self.decorations_drawn(position.x, y, width, target)?;
+                    let decorations = style.decorations_drawn(position.x, y, width, target)?;
 
-                    if self.carryover.borrow().is_none() {
+                    if style.carryover.borrow().is_none() {
                         let carryover = Carryover {
                             previous_image_colorable,
                             decorations,
                             line_piece
                         };
-                        self.carryover.replace(Some(carryover));
+                        style.carryover.replace(Some(carryover));
                     }
 
                     Ok(next_position)
                 }
+            }
+        )*
+    }
+}
+
+impl_line_state! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
+
+macro_rules! impl_text_renderer {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<T, const N: usize> TextRenderer for BitmapFontStyle<'_, '_, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+
+                fn draw_string<D>(
+                    &self,
+                    text: &str,
+                    position: Point,
+                    baseline: Baseline,
+                    target: &mut D,
+                ) -> Result<Point, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let mut line_state = LineState::new(self, text, position, baseline);
+                    line_state.draw_images(self, None, target)?;
+                    line_state.finish(self, target)
+                }
 
                 fn draw_whitespace<D>(
                     &self,
@@ -549,7 +831,7 @@ macro_rules! impl_text_renderer {
                 where
                     D: DrawTarget<Color = Self::Color>,
                 {
-                    let x = position.x as f32 + width as f32;
+                    let x = position.x.saturating_add_unsigned(width);
                     let y = position.y.saturating_add(self.font.metrics.y_offset(baseline));
                     let top = y.saturating_sub(self.font.metrics.y_offset(Baseline::Top));
                     let bottom = y.saturating_sub(self.font.metrics.y_offset(Baseline::Bottom));
@@ -566,7 +848,7 @@ macro_rules! impl_text_renderer {
                         line_piece.draw_styled(&background_style, target)?;
                     }
 
-                    let next_position = Point::new(x as i32, position.y);
+                    let next_position = Point::new(x, position.y);
                     let decorations = self.decorations_drawn(position.x, y, width, target)?;
 
                     if self.carryover.borrow().is_none() {
@@ -588,13 +870,13 @@ macro_rules! impl_text_renderer {
                     baseline: Baseline
                 ) -> TextMetrics {
                     let mut right = position.x;
-                    let mut x = position.x as f32;
+                    let x = Scalar::from_int(position.x);
                     let y = position.y.saturating_add(self.font.metrics.y_offset(baseline));
                     let top = y.saturating_sub(self.font.metrics.y_offset(Baseline::Top));
                     let bottom = y.saturating_sub(self.font.metrics.y_offset(Baseline::Bottom));
                     let height = bottom.saturating_sub(top).try_into().unwrap_or_default();
-                    let images = images_of_chars(&self.font.charmap, text, &mut x, y as f32);
-                    for (image, _) in images {
+                    let mut images = images_of_chars(&self.font.charmap, text, x, Scalar::from_int(y));
+                    for (image, _) in images.by_ref() {
                         let image_box = image.bounding_box();
                         let x = image_box.top_left.x.saturating_add_unsigned(image_box.size.width);
                         if x > right {
@@ -602,7 +884,7 @@ macro_rules! impl_text_renderer {
                         }
                     }
 
-                    let next_position = Point::new(x as i32, position.y);
+                    let next_position = Point::new(scalar::trunc(images.x), position.y);
                     let width = right.saturating_sub(position.x).try_into().unwrap_or_default();
                     let bounding_box = Rectangle {
                         top_left: Point::new(position.x, top),
@@ -627,79 +909,137 @@ impl_text_renderer! {
     Gray8, 256,
 }
 
-fn images_of_chars<'a, C, const N: usize>(
+/// Iterator over the glyph images of the characters in a text run, with the images of glyphs that
+/// are not the first in their glyph cluster marked as overlays.
+#[derive(Debug, Clone)]
+pub(crate) struct ImagesOfChars<'a, 'b, 't, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    charmap: &'b Charmap<'a, C, N>,
+    chars: Chars<'t>,
+    next_glyph: Option<&'a NextGlyph<'a, C, N>>,
+    next_entry: Option<&'b CharmapEntry<'a, C, N>>,
+    previous_entry: Option<&'b CharmapEntry<'a, C, N>>,
+    /// The position along the _x_-axis, which, once all images have been returned, is that of the
+    /// next character.
+    pub x: Scalar,
+    /// The position of the baseline along the _y_-axis.
+    pub y: Scalar,
+}
+
+pub(crate) fn images_of_chars<'a, 'b, 't, C, const N: usize>(
+    charmap: &'b Charmap<'a, C, N>,
+    text: &'t str,
+    x: Scalar,
+    y: Scalar,
+) -> ImagesOfChars<'a, 'b, 't, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    ImagesOfChars {
+        charmap,
+        chars: text.chars(),
+        next_glyph: None,
+        next_entry: None,
+        previous_entry: None,
+        x,
+        y,
+    }
+}
+
+/// Returns the advance width of the specified text run in pixels.
+pub(crate) fn advance_width<'a, C, const N: usize>(
     charmap: &Charmap<'a, C, N>,
     text: &str,
-    x: &mut f32,
-    y: f32,
-) -> impl IntoIterator<Item = (Image<ImageRaw<'a, C>>, bool)>
+) -> Scalar
 where
     C: PixelColor + From<C::Raw>,
     RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
 {
-    let mut chars = text.chars();
-    let mut next_glyph = None;
-    let mut next_entry = None;
-    let mut previous_entry = None;
-    iter::from_fn(move || {
-        let entry = match next_entry {
+    let zero = Scalar::ZERO;
+    let mut images = images_of_chars(charmap, text, zero, zero);
+    images.by_ref().for_each(drop);
+
+    images.x
+}
+
+impl<'a, C, const N: usize> Iterator for ImagesOfChars<'a, '_, '_, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    type Item = (Image<ImageRaw<'a, C>>, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = match self.next_entry {
             Some(entry) => entry,
             None => {
-                let slice = chars.as_str();
+                let slice = self.chars.as_str();
                 if slice.is_empty() {
-                    *x += previous_entry
+                    self.x += self
+                        .previous_entry
                         .take()
-                        .map(|entry: &CharmapEntry<C, N>| entry.advance_width_to)
+                        .map(|entry| entry.advance_width_to)
                         .map(|advance_width_to| advance_width_to(Default::default()))
                         .unwrap_or_default();
 
-                    return next_glyph.map(|next: &NextGlyph<C, N>| {
-                        let x = *x + next.x_offset;
-                        let y = y - next.y_offset;
-                        let image = next.glyph.images.get((x * N as f32) as usize);
-                        let image = image.mul_offset(1, -1).add_offset(x as i32, y as i32);
-                        next_glyph = next.glyph.next;
+                    return self.next_glyph.map(|next| {
+                        let x = self.x + next.x_offset;
+                        let y = self.y - next.y_offset;
+                        let image = next.glyph.images.get(scalar::sub_pixel_index(x, N));
+                        let image = image
+                            .mul_offset(1, -1)
+                            .add_offset(scalar::floor(x), scalar::floor(y));
+                        self.next_glyph = next.glyph.next;
 
                         (image, true)
                     });
                 }
 
-                let entry = charmap.get(slice);
-                *x += previous_entry
+                let entry = self.charmap.get(slice);
+                self.x += self
+                    .previous_entry
                     .replace(entry)
                     .map(|entry| entry.advance_width_to)
                     .map(|advance_width_to| advance_width_to(entry.key))
                     .unwrap_or_default();
 
                 for _ in 0..entry.advance_chars {
-                    let _ = chars.next();
+                    let _ = self.chars.next();
                 }
 
                 entry
             }
         };
 
-        let tuple = match next_glyph {
+        let tuple = match self.next_glyph {
             Some(next) => {
-                let x = *x + next.x_offset;
-                let y = y - next.y_offset;
-                let image = next.glyph.images.get((x * N as f32) as usize);
-                let image = image.mul_offset(1, -1).add_offset(x as i32, y as i32);
-                next_glyph = next.glyph.next;
-                next_entry = Some(entry);
+                let x = self.x + next.x_offset;
+                let y = self.y - next.y_offset;
+                let image = next.glyph.images.get(scalar::sub_pixel_index(x, N));
+                let image = image
+                    .mul_offset(1, -1)
+                    .add_offset(scalar::floor(x), scalar::floor(y));
+                self.next_glyph = next.glyph.next;
+                self.next_entry = Some(entry);
 
                 (image, true)
             }
             None => {
-                let image = entry.glyph.images.get((*x * N as f32) as usize);
-                let image = image.mul_offset(1, -1).add_offset(*x as i32, y as i32);
-                next_glyph = entry.glyph.next;
-                next_entry = None;
+                let image = entry.glyph.images.get(scalar::sub_pixel_index(self.x, N));
+                let image = image
+                    .mul_offset(1, -1)
+                    .add_offset(scalar::floor(self.x), scalar::floor(self.y));
+                self.next_glyph = entry.glyph.next;
+                self.next_entry = None;
 
                 (image, false)
             }
         };
 
         Some(tuple)
-    })
+    }
 }
@@ -0,0 +1,124 @@
+use core::task::Poll;
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::Point;
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
+use embedded_graphics::text::Baseline;
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::style::{BitmapFontStyle, LineState};
+
+/// Limit on the amount of work done in a single call to [`ResumableString::draw`].
+///
+/// Drawing stops at the start of a glyph cluster, with at least one glyph cluster drawn per call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DrawLimit {
+    /// The maximum number of glyph clusters to draw.
+    Clusters(usize),
+    /// The maximum number of glyph image pixels to draw.
+    Pixels(u32),
+}
+
+/// Text run that is drawn over the course of multiple calls, each of which draws a limited number
+/// of glyph clusters.
+///
+/// The end result is the same as that of a single call to
+/// [`draw_string`](embedded_graphics::text::renderer::TextRenderer::draw_string), including the
+/// blending of overlapping glyph images and the carryover between text runs. Until drawing is
+/// complete, the style should not be used to draw other text.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ResumableString<'s, 'a, 'b, 't, T, C, const N: usize, const M: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    style: &'s BitmapFontStyle<'a, 'b, T, C, N>,
+    line_state: Option<LineState<'a, 'b, 't, T, C, N, M>>,
+    next_position: Point,
+}
+
+impl<'a, T, C, const N: usize, const M: usize> ResumableString<'_, 'a, '_, '_, T, C, N, M>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Returns whether this text run has been drawn in full.
+    pub fn is_complete(&self) -> bool {
+        self.line_state.is_none()
+    }
+}
+
+macro_rules! impl_resumable_string {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<'a, 'b, T, const N: usize> BitmapFontStyle<'a, 'b, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                /// Returns a text run at the specified position, which is drawn with this style in
+                /// increments.
+                pub fn resumable<'s, 't>(
+                    &'s self,
+                    text: &'t str,
+                    position: Point,
+                    baseline: Baseline,
+                ) -> ResumableString<'s, 'a, 'b, 't, T, $color_type, N, $array_length> {
+                    ResumableString {
+                        style: self,
+                        line_state: Some(LineState::new(self, text, position, baseline)),
+                        next_position: position,
+                    }
+                }
+            }
+
+            impl<T, const N: usize> ResumableString<'_, '_, '_, '_, T, $color_type, N, $array_length>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                /// Draws the next increment of this text run, returning the position of the next
+                /// character once the text run has been drawn in full.
+                pub fn draw<D>(
+                    &mut self,
+                    limit: DrawLimit,
+                    target: &mut D,
+                ) -> Result<Poll<Point>, D::Error>
+                where
+                    D: DrawTarget<Color = T>,
+                {
+                    let Some(line_state) = self.line_state.as_mut() else {
+                        return Ok(Poll::Ready(self.next_position));
+                    };
+
+                    if !line_state.draw_images(self.style, Some(limit), target)? {
+                        return Ok(Poll::Pending);
+                    }
+
+                    if let Some(line_state) = self.line_state.take() {
+                        self.next_position = line_state.finish(self.style, target)?;
+                    }
+
+                    Ok(Poll::Ready(self.next_position))
+                }
+            }
+        )*
+    }
+}
+
+impl_resumable_string! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
@@ -0,0 +1,359 @@
+//! Terminal emulation on a grid of character cells.
+//!
+//! The monospaced **M<sup>+</sup> Code** typeface, along with its box-drawing characters, block
+//! elements, and braille patterns that are aligned to the pixel grid, makes for a terminal font.
+//! A [`Terminal`] takes text with ANSI escape codes via [`core::fmt::Write`], keeping track of the
+//! character and the attributes of every cell in a grid, and draws the cells using a
+//! [`BitmapFontStyle`].
+//!
+//! The following control sequences are supported, in addition to line feeds, carriage returns,
+//! tabs, and backspaces:
+//!
+//! * Cursor movement --- `CUU`, `CUD`, `CUF`, `CUB`, `CNL`, `CPL`, `CHA`, `CUP`, `VPA`, `HPR`,
+//!   `VPR`, as well as saving and restoring the cursor with `DECSC`/`DECRC` and `SCOSC`/`SCORC`.
+//! * Erasing --- `ED`, `EL`, `ECH`, and inserting and deleting with `ICH`, `DCH`, `IL`, `DL`.
+//! * Scrolling --- `SU`, `SD`, `IND`, `RI`, `NEL`, and scroll regions with `DECSTBM`.
+//! * Select graphic rendition --- bold, underline, reverse, strikethrough, and colors in the
+//!   16-color and 256-color palettes as well as 24-bit colors.
+//!
+//! Line feeds also return the cursor to the first column. Characters such as kanji take up two
+//! cells, as determined by [`BitmapFont::cell_width`](crate::BitmapFont::cell_width).
+//!
+//! A [`Frame`] composes rectangles and lines on a grid of the same cells into box-drawing
+//! characters, finding the right junction for every cell where lines meet, and a [`Canvas`]
+//! turns a monochrome pixel buffer into braille patterns, block sextants, or block octants, each
+//! character standing for a block of pixels.
+//!
+//! With the `ratatui` feature enabled, a [`RatatuiBackend`] draws user interfaces built with
+//! ratatui using the same cells, taking only the cells that have changed from ratatui.
+
+#[cfg(feature = "ratatui")]
+mod backend;
+mod canvas;
+mod cell;
+mod frame;
+mod grid;
+mod parser;
+
+use core::fmt::{self, Write};
+
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::{DrawTarget, DrawTargetExt};
+use embedded_graphics::geometry::{Point, Size};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{PixelColor, Rgb888};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::renderer::TextRenderer;
+use embedded_graphics::text::{Baseline, DecorationColor};
+
+use crate::color::{Invert, Screen, WeightedAvg};
+use crate::scalar::{self, Scalar};
+use crate::style::{self, BitmapFontStyle};
+
+use grid::Grid;
+use parser::Parser;
+
+#[cfg(feature = "ratatui")]
+pub use backend::{BackendError, RatatuiBackend};
+pub use canvas::{Canvas, Semigraphics};
+pub use cell::{AnsiColor, Attributes, Cell, CellWidth};
+pub use frame::{Frame, Junction, LineStyle};
+
+/// Terminal, a grid of character cells that takes text with ANSI escape codes.
+///
+/// The cells are kept in the specified buffer, in row-major order. Every cell has the width of a
+/// space in the bitmap font, rounded to the nearest pixel, and the height of a line. Cells that
+/// use the default colors are drawn using the text color and the background color of the style.
+///
+/// Drawing the terminal draws all cells; use [`draw_dirty`](Self::draw_dirty) to draw only the
+/// cells that have changed since they were last drawn.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Terminal<'a, 'b, 'g, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    grid: Grid<'g>,
+    parser: Parser,
+    position: Point,
+    cell_size: Size,
+    style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+impl<'a, 'b, 'g, T, C, const N: usize> Terminal<'a, 'b, 'g, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new terminal with the specified cells, number of columns, position of the top
+    /// left corner, and style, clearing all cells.
+    ///
+    /// The number of rows is the number of cells divided by the number of columns.
+    pub fn new(
+        cells: &'g mut [Cell],
+        columns: usize,
+        position: Point,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Self {
+        let cell_size = cell_size(&style);
+
+        Self {
+            grid: Grid::new(cells, columns),
+            parser: Parser::new(),
+            position,
+            cell_size,
+            style,
+        }
+    }
+
+    /// Returns the number of columns.
+    pub const fn columns(&self) -> usize {
+        self.grid.columns()
+    }
+
+    /// Returns the number of rows.
+    pub const fn rows(&self) -> usize {
+        self.grid.rows()
+    }
+
+    /// Returns the cursor position as a column and a row, counting from zero.
+    pub const fn cursor(&self) -> (usize, usize) {
+        self.grid.cursor()
+    }
+
+    /// Returns the cell in the specified column and row, or `None` if there is no such cell.
+    pub fn cell(&self, column: usize, row: usize) -> Option<&Cell> {
+        self.grid.cell(column, row)
+    }
+
+    /// Returns the size of a cell in pixels.
+    pub const fn cell_size(&self) -> Size {
+        self.cell_size
+    }
+
+    /// Returns the bounding box of this terminal.
+    pub fn bounding_box(&self) -> Rectangle {
+        let width = self.cell_size.width.saturating_mul(self.columns() as u32);
+        let height = self.cell_size.height.saturating_mul(self.rows() as u32);
+
+        Rectangle::new(self.position, Size::new(width, height))
+    }
+}
+
+impl<'a, 'b, T, C, const N: usize> Terminal<'a, 'b, '_, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg + From<Rgb888>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    BitmapFontStyle<'a, 'b, T, C, N>: TextRenderer<Color = T>,
+{
+    /// Draws the cells that have changed since they were last drawn.
+    pub fn draw_dirty<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = T>,
+    {
+        self.draw_cells(target, true)?;
+        self.grid.mark_drawn();
+
+        Ok(())
+    }
+
+    /// Draws all cells, or only the cells that have changed, together with the other halves of
+    /// their wide characters.
+    fn draw_cells<D>(&self, target: &mut D, only_dirty: bool) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = T>,
+    {
+        for row in 0..self.rows() {
+            let cells = self.grid.row(row);
+            for (column, cell) in cells.iter().enumerate() {
+                let previous = column.checked_sub(1).map(|column| &cells[column]);
+                let next = cells.get(column + 1);
+                if cell.width == CellWidth::Continuation
+                    && previous.is_some_and(|previous| previous.width == CellWidth::Wide)
+                {
+                    continue;
+                }
+
+                let is_wide = cell.width == CellWidth::Wide
+                    && next.is_some_and(|next| next.width == CellWidth::Continuation);
+                let is_dirty = cell.dirty || is_wide && next.is_some_and(|next| next.dirty);
+                if only_dirty && !is_dirty {
+                    continue;
+                }
+
+                let span = if is_wide { 2 } else { 1 };
+                self.draw_cell(cell, column, row, span, target)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws the specified cell, which spans the specified number of columns.
+    fn draw_cell<D>(
+        &self,
+        cell: &Cell,
+        column: usize,
+        row: usize,
+        span: u32,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = T>,
+    {
+        let mut attributes = cell.attributes;
+        if attributes.bold {
+            attributes.foreground = attributes.foreground.bright();
+        }
+
+        let style = cell_style(&self.style, &attributes);
+        let Size { width, height } = self.cell_size;
+        let x = width
+            .saturating_mul(column as u32)
+            .try_into()
+            .unwrap_or(i32::MAX);
+        let y = height
+            .saturating_mul(row as u32)
+            .try_into()
+            .unwrap_or(i32::MAX);
+        let top_left = self.position + Point::new(x, y);
+        let cell_box = Rectangle::new(top_left, Size::new(width * span, height));
+        let mut buffer = [0; 4];
+        let text = cell.character.encode_utf8(&mut buffer);
+
+        draw_in_cell_box(&style, text, &cell_box, target)
+    }
+}
+
+impl<'a, T, C, const N: usize> Write for Terminal<'a, '_, '_, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        let Some(action) = self.parser.advance(c) else {
+            return Ok(());
+        };
+
+        let font = self.style.font;
+        self.grid.apply(action, |c| {
+            let mut buffer = [0; 4];
+
+            font.cell_width(c.encode_utf8(&mut buffer))
+        });
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b, T, C, const N: usize> Drawable for Terminal<'a, 'b, '_, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg + From<Rgb888>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    BitmapFontStyle<'a, 'b, T, C, N>: TextRenderer<Color = T>,
+{
+    type Color = T;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw_cells(target, false)
+    }
+}
+
+/// Returns the size of a cell for the specified style, which has the width of a space in the
+/// bitmap font, rounded to the nearest pixel, and the height of a line.
+fn cell_size<'a, T, C, const N: usize>(style: &BitmapFontStyle<'a, '_, T, C, N>) -> Size
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    let metrics = &style.font.metrics;
+    let advance = style::advance_width(&style.font.charmap, " ");
+    let advance = if advance > Scalar::ZERO {
+        advance
+    } else {
+        scalar::half(metrics.ascender)
+    };
+
+    let width = scalar::floor(advance + scalar::half(Scalar::from_int(1)));
+
+    Size::new(width.try_into().unwrap_or_default(), metrics.line_height())
+}
+
+/// Returns a copy of the specified style with the colors and the decorations of the specified
+/// attributes; whether the text is bold is not taken into account.
+fn cell_style<'a, 'b, T, C, const N: usize>(
+    style: &BitmapFontStyle<'a, 'b, T, C, N>,
+    attributes: &Attributes,
+) -> BitmapFontStyle<'a, 'b, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg + From<Rgb888>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    let foreground = attributes.foreground.to_rgb();
+    let foreground = foreground.map_or(style.text_color(), T::from);
+    let background = attributes.background.to_rgb();
+    let background = background.map_or(style.background_color(), T::from);
+    let (foreground, background) = match attributes.reverse {
+        true => (background, foreground),
+        false => (foreground, background),
+    };
+
+    let decoration_color = |is_enabled| match is_enabled {
+        true => DecorationColor::TextColor,
+        false => DecorationColor::None,
+    };
+
+    let mut style = style.clone();
+    style.text_color = Some(foreground);
+    style.background_color = Some(background);
+    style.underline_color = decoration_color(attributes.underline);
+    style.strikethrough_color = decoration_color(attributes.strikethrough);
+
+    style
+}
+
+/// Draws the specified text in the specified cell box, clipped to the box, and fills the rest of
+/// the box with the background color.
+fn draw_in_cell_box<'a, 'b, T, C, D, const N: usize>(
+    style: &BitmapFontStyle<'a, 'b, T, C, N>,
+    text: &str,
+    cell_box: &Rectangle,
+    target: &mut D,
+) -> Result<(), D::Error>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    D: DrawTarget<Color = T>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    BitmapFontStyle<'a, 'b, T, C, N>: TextRenderer<Color = T>,
+{
+    let top_left = cell_box.top_left;
+    let right = top_left.x.saturating_add_unsigned(cell_box.size.width);
+    let mut target = target.clipped(cell_box);
+    let next = style.draw_string(text, top_left, Baseline::Top, &mut target)?;
+    let width = right.saturating_sub(next.x).try_into().unwrap_or_default();
+    style.draw_whitespace(width, next, Baseline::Top, &mut target)?;
+
+    Ok(())
+}
@@ -0,0 +1,488 @@
+use core::fmt;
+use core::ops::Range;
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Point, Size};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{PixelColor, Rgb888};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::renderer::TextRenderer;
+use ratatui_core::backend::{Backend, ClearType, WindowSize};
+use ratatui_core::buffer::Cell;
+use ratatui_core::layout::{self, Position};
+use ratatui_core::style::{Color, Modifier};
+use unicode_width::UnicodeWidthStr;
+
+use crate::color::{Invert, Screen, WeightedAvg};
+use crate::style::BitmapFontStyle;
+
+use super::{AnsiColor, Attributes};
+
+/// Error returned by a [`RatatuiBackend`] when drawing to its draw target fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BackendError<E>(pub E);
+
+/// Backend for [`ratatui`](ratatui_core), drawing the cells of its buffer to a draw target.
+///
+/// The cells have the size of the cells of a [`Terminal`](super::Terminal) with the same style,
+/// starting from the top left corner of the draw target; the number of columns and rows is given
+/// by the size of the draw target. Cells with bold text are drawn using the bold style, if there
+/// is one, and cells with underlined, crossed-out, or reversed text are drawn using the matching
+/// decorations and colors of the style. Cells that use the reset color are drawn using the text
+/// color and the background color of the style.
+///
+/// Ratatui only passes the cells that have changed since the last frame to the backend, so only
+/// these cells are drawn. The cursor is not drawn; only its position is kept track of.
+#[derive(Debug)]
+pub struct RatatuiBackend<'a, 'b, D, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    target: D,
+    cell_size: Size,
+    cursor: Position,
+    style: BitmapFontStyle<'a, 'b, T, C, N>,
+    bold_style: Option<BitmapFontStyle<'a, 'b, T, C, N>>,
+}
+
+impl<'a, 'b, D, T, C, const N: usize> RatatuiBackend<'a, 'b, D, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new backend with the specified draw target, style, and optional style for bold
+    /// text, which is expected to use a bitmap font with the same advance width.
+    pub fn new(
+        target: D,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+        bold_style: Option<BitmapFontStyle<'a, 'b, T, C, N>>,
+    ) -> Self {
+        let cell_size = super::cell_size(&style);
+
+        Self {
+            target,
+            cell_size,
+            cursor: Position::ORIGIN,
+            style,
+            bold_style,
+        }
+    }
+
+    /// Returns the size of a cell in pixels.
+    pub const fn cell_size(&self) -> Size {
+        self.cell_size
+    }
+
+    /// Returns a reference to the draw target.
+    pub const fn target(&self) -> &D {
+        &self.target
+    }
+
+    /// Returns a mutable reference to the draw target, for example, for flushing its contents to a
+    /// display.
+    pub const fn target_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+
+    /// Consumes this backend, returning the draw target.
+    pub fn into_target(self) -> D {
+        self.target
+    }
+}
+
+impl<'a, 'b, D, T, C, const N: usize> RatatuiBackend<'a, 'b, D, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg + From<Rgb888>,
+    D: DrawTarget<Color = T>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    BitmapFontStyle<'a, 'b, T, C, N>: TextRenderer<Color = T>,
+{
+    /// Returns the number of columns and rows that fit in the draw target.
+    fn grid_size(&self) -> layout::Size {
+        let Size { width, height } = self.target.bounding_box().size;
+        let columns = width.checked_div(self.cell_size.width).unwrap_or_default();
+        let rows = height
+            .checked_div(self.cell_size.height)
+            .unwrap_or_default();
+
+        layout::Size::new(
+            columns.try_into().unwrap_or(u16::MAX),
+            rows.try_into().unwrap_or(u16::MAX),
+        )
+    }
+
+    /// Returns the bounding box of the cells in the specified columns and rows.
+    fn cells_box(&self, columns: Range<u16>, rows: Range<u16>) -> Rectangle {
+        let Size { width, height } = self.cell_size;
+        let x = width.saturating_mul(columns.start.into());
+        let y = height.saturating_mul(rows.start.into());
+        let offset = Point::new(
+            x.try_into().unwrap_or(i32::MAX),
+            y.try_into().unwrap_or(i32::MAX),
+        );
+
+        let columns = columns.len().try_into().unwrap_or(u32::MAX);
+        let rows = rows.len().try_into().unwrap_or(u32::MAX);
+        let size = Size::new(width.saturating_mul(columns), height.saturating_mul(rows));
+
+        Rectangle::new(self.target.bounding_box().top_left + offset, size)
+    }
+
+    /// Fills the cells in the specified columns and rows with the background color of the style.
+    fn fill_cells(
+        &mut self,
+        columns: Range<u16>,
+        rows: Range<u16>,
+    ) -> Result<(), BackendError<D::Error>> {
+        let cells_box = self.cells_box(columns, rows);
+        let color = self.style.background_color();
+
+        self.target
+            .fill_solid(&cells_box, color)
+            .map_err(BackendError)
+    }
+
+    /// Draws the specified cell in the specified column and row.
+    fn draw_cell(&mut self, x: u16, y: u16, cell: &Cell) -> Result<(), BackendError<D::Error>> {
+        let modifier = cell.modifier;
+        let attributes = Attributes {
+            foreground: cell.fg.into(),
+            background: cell.bg.into(),
+            bold: modifier.contains(Modifier::BOLD),
+            underline: modifier.contains(Modifier::UNDERLINED),
+            strikethrough: modifier.contains(Modifier::CROSSED_OUT),
+            reverse: modifier.contains(Modifier::REVERSED),
+        };
+
+        let style = match (attributes.bold, &self.bold_style) {
+            (true, Some(bold_style)) => bold_style,
+            _ => &self.style,
+        };
+
+        let style = super::cell_style(style, &attributes);
+        let symbol = match modifier.contains(Modifier::HIDDEN) {
+            true => " ",
+            false => cell.symbol(),
+        };
+
+        let span = symbol.width().clamp(1, 2) as u16;
+        let cell_box = self.cells_box(x..x.saturating_add(span), y..y.saturating_add(1));
+
+        super::draw_in_cell_box(&style, symbol, &cell_box, &mut self.target).map_err(BackendError)
+    }
+}
+
+impl<'a, 'b, D, T, C, const N: usize> Backend for RatatuiBackend<'a, 'b, D, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg + From<Rgb888>,
+    D: DrawTarget<Color = T>,
+    D::Error: fmt::Debug,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    BitmapFontStyle<'a, 'b, T, C, N>: TextRenderer<Color = T>,
+{
+    type Error = BackendError<D::Error>;
+
+    fn draw<'c, I>(&mut self, content: I) -> Result<(), Self::Error>
+    where
+        I: Iterator<Item = (u16, u16, &'c Cell)>,
+    {
+        for (x, y, cell) in content {
+            self.draw_cell(x, y, cell)?;
+        }
+
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn show_cursor(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn get_cursor_position(&mut self) -> Result<Position, Self::Error> {
+        Ok(self.cursor)
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> Result<(), Self::Error> {
+        self.cursor = position.into();
+
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        let bounding_box = self.target.bounding_box();
+        let color = self.style.background_color();
+
+        self.target
+            .fill_solid(&bounding_box, color)
+            .map_err(BackendError)
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), Self::Error> {
+        let layout::Size { width, height } = self.grid_size();
+        let Position { x, y } = self.cursor;
+        let next_x = x.saturating_add(1);
+        let next_y = y.saturating_add(1);
+        match clear_type {
+            ClearType::All => self.clear(),
+            ClearType::AfterCursor => {
+                self.fill_cells(x..width, y..next_y)?;
+                self.fill_cells(0..width, next_y..height)
+            }
+            ClearType::BeforeCursor => {
+                self.fill_cells(0..width, 0..y)?;
+                self.fill_cells(0..next_x, y..next_y)
+            }
+            ClearType::CurrentLine => self.fill_cells(0..width, y..next_y),
+            ClearType::UntilNewLine => self.fill_cells(x..width, y..next_y),
+        }
+    }
+
+    fn size(&self) -> Result<layout::Size, Self::Error> {
+        Ok(self.grid_size())
+    }
+
+    fn window_size(&mut self) -> Result<WindowSize, Self::Error> {
+        let Size { width, height } = self.target.bounding_box().size;
+        let pixels = layout::Size::new(
+            width.try_into().unwrap_or(u16::MAX),
+            height.try_into().unwrap_or(u16::MAX),
+        );
+
+        Ok(WindowSize {
+            columns_rows: self.grid_size(),
+            pixels,
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl From<Color> for AnsiColor {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Reset => Self::Default,
+            Color::Black => Self::Indexed(0),
+            Color::Red => Self::Indexed(1),
+            Color::Green => Self::Indexed(2),
+            Color::Yellow => Self::Indexed(3),
+            Color::Blue => Self::Indexed(4),
+            Color::Magenta => Self::Indexed(5),
+            Color::Cyan => Self::Indexed(6),
+            Color::Gray => Self::Indexed(7),
+            Color::DarkGray => Self::Indexed(8),
+            Color::LightRed => Self::Indexed(9),
+            Color::LightGreen => Self::Indexed(10),
+            Color::LightYellow => Self::Indexed(11),
+            Color::LightBlue => Self::Indexed(12),
+            Color::LightMagenta => Self::Indexed(13),
+            Color::LightCyan => Self::Indexed(14),
+            Color::White => Self::Indexed(15),
+            Color::Rgb(r, g, b) => Self::Rgb(r, g, b),
+            Color::Indexed(index) => Self::Indexed(index),
+        }
+    }
+}
+
+impl<E> fmt::Display for BackendError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to draw to the draw target")
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for BackendError<E> {}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use embedded_graphics::Drawable;
+    use embedded_graphics::draw_target::DrawTargetExt;
+    use embedded_graphics::mock_display::MockDisplay;
+    use ratatui_core::buffer::Buffer;
+    use ratatui_core::layout::Rect;
+    use ratatui_core::style::Style;
+
+    use super::*;
+    use crate::mplus;
+    use crate::style::BitmapFontStyleBuilder;
+    use crate::terminal::{self, Terminal};
+
+    const TEXT_COLOR: Rgb888 = Rgb888::new(255, 210, 0);
+    const BACKGROUND_COLOR: Rgb888 = Rgb888::new(0, 30, 60);
+    const COLUMNS: u16 = 4;
+    const ROWS: u16 = 2;
+
+    fn new_display() -> MockDisplay<Rgb888> {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        display
+    }
+
+    macro_rules! test_backend_draw {
+        (
+            $(
+                $fn_ident:ident,
+                $symbol:expr,
+                $style:expr,
+                $has_bold_style:expr,
+                $input:expr,
+                $is_bold_input:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~', ["漢"]);
+                    let bold_bitmap_font = mplus!(code(100), 700, 16, false, 1, 4, ' '..='~', ["漢"]);
+                    let style = BitmapFontStyleBuilder::new()
+                        .font(&bitmap_font)
+                        .text_color(TEXT_COLOR)
+                        .background_color(BACKGROUND_COLOR)
+                        .build();
+
+                    let bold_style = BitmapFontStyleBuilder::new()
+                        .font(&bold_bitmap_font)
+                        .text_color(TEXT_COLOR)
+                        .background_color(BACKGROUND_COLOR)
+                        .build();
+
+                    let expected_style = match $is_bold_input {
+                        true => bold_style.clone(),
+                        false => style.clone(),
+                    };
+
+                    let mut cells = [terminal::Cell::BLANK; (COLUMNS * ROWS) as usize];
+                    let mut terminal =
+                        Terminal::new(&mut cells, COLUMNS.into(), Point::zero(), expected_style);
+                    terminal.write_str($input).unwrap();
+
+                    let mut expected = new_display();
+                    terminal.draw(&mut expected).unwrap();
+
+                    let area = Rect::new(0, 0, COLUMNS, ROWS);
+                    let previous = Buffer::empty(area);
+                    let mut buffer = Buffer::empty(area);
+                    buffer.set_string(1, 1, $symbol, $style);
+
+                    let mut display = new_display();
+                    let target = display.clipped(&terminal.bounding_box());
+                    let bold_style = Some(bold_style).filter(|_| $has_bold_style);
+                    let mut backend = RatatuiBackend::new(target, style, bold_style);
+                    backend.clear().unwrap();
+                    backend.draw(previous.diff(&buffer).into_iter()).unwrap();
+
+                    display.assert_eq(&expected);
+                }
+            )*
+        }
+    }
+
+    test_backend_draw! {
+        backend_draw_text, "ab", Style::new(), true, "\x1B[2;2Hab", false,
+        backend_draw_color, "ab", Style::new().fg(Color::Red).bg(Color::Blue), true,
+        "\x1B[2;2H\x1B[31;44mab", false,
+        backend_draw_bold, "ab", Style::new().add_modifier(Modifier::BOLD), true,
+        "\x1B[2;2Hab", true,
+        backend_draw_bold_without_bold_style, "ab", Style::new().add_modifier(Modifier::BOLD),
+        false, "\x1B[2;2Hab", false,
+        backend_draw_reversed, "ab", Style::new().add_modifier(Modifier::REVERSED), true,
+        "\x1B[2;2H\x1B[7mab", false,
+        backend_draw_hidden, "ab", Style::new().add_modifier(Modifier::HIDDEN), true,
+        "\x1B[2;2H  ", false,
+        backend_draw_hidden_reversed, "ab",
+        Style::new().add_modifier(Modifier::HIDDEN | Modifier::REVERSED), true,
+        "\x1B[2;2H\x1B[7m  ", false,
+        backend_draw_underlined, "ab", Style::new().add_modifier(Modifier::UNDERLINED), true,
+        "\x1B[2;2H\x1B[4mab", false,
+        backend_draw_wide_symbol, "漢b", Style::new(), true, "\x1B[2;2H漢b", false,
+    }
+
+    macro_rules! test_backend_clear_region {
+        (
+            $(
+                $fn_ident:ident, $clear_type:expr, $input:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~');
+                    let style = BitmapFontStyleBuilder::new()
+                        .font(&bitmap_font)
+                        .text_color(TEXT_COLOR)
+                        .background_color(BACKGROUND_COLOR)
+                        .build();
+
+                    let mut cells = [terminal::Cell::BLANK; (COLUMNS * ROWS) as usize];
+                    let mut terminal =
+                        Terminal::new(&mut cells, COLUMNS.into(), Point::zero(), style.clone());
+                    terminal.write_str("abcd\nefgh\x1B[1;3H").unwrap();
+                    terminal.write_str($input).unwrap();
+
+                    let mut expected = new_display();
+                    terminal.draw(&mut expected).unwrap();
+
+                    let area = Rect::new(0, 0, COLUMNS, ROWS);
+                    let previous = Buffer::empty(area);
+                    let buffer = Buffer::with_lines(["abcd", "efgh"]);
+
+                    let mut display = new_display();
+                    let target = display.clipped(&terminal.bounding_box());
+                    let mut backend = RatatuiBackend::new(target, style, None);
+                    backend.clear().unwrap();
+                    backend.draw(previous.diff(&buffer).into_iter()).unwrap();
+                    backend.set_cursor_position((2, 0)).unwrap();
+                    backend.clear_region($clear_type).unwrap();
+
+                    display.assert_eq(&expected);
+                }
+            )*
+        }
+    }
+
+    test_backend_clear_region! {
+        backend_clear_region_all, ClearType::All, "\x1B[2J",
+        backend_clear_region_after_cursor, ClearType::AfterCursor, "\x1B[J",
+        backend_clear_region_before_cursor, ClearType::BeforeCursor, "\x1B[1J",
+        backend_clear_region_current_line, ClearType::CurrentLine, "\x1B[2K",
+        backend_clear_region_until_new_line, ClearType::UntilNewLine, "\x1B[K",
+    }
+
+    macro_rules! test_from_color {
+        (
+            $(
+                $fn_ident:ident, $color:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = AnsiColor::from($color);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_from_color! {
+        from_color_reset, Color::Reset, AnsiColor::Default,
+        from_color_red, Color::Red, AnsiColor::Indexed(1),
+        from_color_gray, Color::Gray, AnsiColor::Indexed(7),
+        from_color_dark_gray, Color::DarkGray, AnsiColor::Indexed(8),
+        from_color_white, Color::White, AnsiColor::Indexed(15),
+        from_color_indexed, Color::Indexed(208), AnsiColor::Indexed(208),
+        from_color_rgb, Color::Rgb(80, 0, 80), AnsiColor::Rgb(80, 0, 80),
+    }
+}
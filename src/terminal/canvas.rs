@@ -0,0 +1,344 @@
+use core::convert::Infallible;
+use core::fmt::{self, Write};
+
+use embedded_graphics::Pixel;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{OriginDimensions, Point, Size};
+use embedded_graphics::pixelcolor::BinaryColor;
+
+/// The bits of the braille dots for the dots of a 2-by-4 block, in row-major order.
+const BRAILLE_DOTS: [u8; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+
+/// The 2-by-4 blocks that have no octant character of their own, in row-major order, along with
+/// the characters that are used instead.
+const OCTANT_EXCEPTIONS: [(u8, char); 26] = [
+    (0x00, ' '),
+    (0x01, '\u{1CEA8}'),
+    (0x02, '\u{1CEAB}'),
+    (0x03, '\u{1FB82}'),
+    (0x05, '\u{2598}'),
+    (0x0A, '\u{259D}'),
+    (0x0F, '\u{2580}'),
+    (0x14, '\u{1FBE6}'),
+    (0x28, '\u{1FBE7}'),
+    (0x3F, '\u{1FB85}'),
+    (0x40, '\u{1CEA3}'),
+    (0x50, '\u{2596}'),
+    (0x55, '\u{258C}'),
+    (0x5A, '\u{259E}'),
+    (0x5F, '\u{259B}'),
+    (0x80, '\u{1CEA0}'),
+    (0xA0, '\u{2597}'),
+    (0xA5, '\u{259A}'),
+    (0xAA, '\u{2590}'),
+    (0xAF, '\u{259C}'),
+    (0xC0, '\u{2582}'),
+    (0xF0, '\u{2584}'),
+    (0xF5, '\u{2599}'),
+    (0xFA, '\u{259F}'),
+    (0xFC, '\u{2586}'),
+    (0xFF, '\u{2588}'),
+];
+
+/// Semigraphics, the set of characters that a canvas uses for its blocks of pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Semigraphics {
+    /// Braille patterns, with 2-by-4 dots; blocks without any dots use the blank braille pattern.
+    Braille,
+    /// Block sextants, with 2-by-3 blocks; blocks that are empty, full, or half-full use spaces,
+    /// full blocks, and half blocks.
+    Sextant,
+    /// Block octants, with 2-by-4 blocks; blocks that have a block element, such as a quadrant or
+    /// a block that is filled up to a quarter, use that block element.
+    Octant,
+}
+
+/// Canvas, a monochrome pixel buffer that is made up of blocks of pixels, each of which is shown
+/// as a single character in a grid of character cells.
+///
+/// The canvas is a [`DrawTarget`] for [`BinaryColor`], so that bitmaps, lines, and other
+/// primitives can be drawn onto it, with the pixels of every cell kept as a bitmask in the
+/// specified buffer, in row-major order. Formatting the canvas using [`core::fmt::Display`]
+/// outputs its rows of characters separated by line feeds; to draw the canvas, the bitmap font
+/// needs to include these characters, which line up with each other in **M<sup>+</sup> Code**.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Canvas<'c> {
+    cells: &'c mut [u8],
+    columns: usize,
+    rows: usize,
+    semigraphics: Semigraphics,
+}
+
+impl Semigraphics {
+    /// Returns the size of a block in pixels.
+    pub const fn block_size(self) -> Size {
+        match self {
+            Self::Braille | Self::Octant => Size::new(2, 4),
+            Self::Sextant => Size::new(2, 3),
+        }
+    }
+
+    /// Returns the character for the block with the specified pixels, given as a bitmask in which
+    /// the first bit is for the top left pixel, followed by the other pixels in row-major order.
+    pub fn character(self, block: u8) -> char {
+        let code_point = match self {
+            Self::Braille => {
+                let dots = BRAILLE_DOTS.iter().enumerate();
+                let dots = dots.filter(|&(index, _)| block & 1 << index != 0);
+
+                0x2800 + dots.fold(0, |bits, (_, dot)| bits | dot) as u32
+            }
+            Self::Sextant => match block & 0x3F {
+                0x00 => return ' ',
+                0x15 => return '\u{258C}',
+                0x2A => return '\u{2590}',
+                0x3F => return '\u{2588}',
+                block => 0x1FB00 + block as u32 - 1 - (block > 0x15) as u32 - (block > 0x2A) as u32,
+            },
+            Self::Octant => {
+                match OCTANT_EXCEPTIONS.binary_search_by_key(&block, |&(block, _)| block) {
+                    Ok(index) => return OCTANT_EXCEPTIONS[index].1,
+                    Err(index) => 0x1CD00 + block as u32 - index as u32,
+                }
+            }
+        };
+
+        char::from_u32(code_point).unwrap_or(' ')
+    }
+}
+
+impl<'c> Canvas<'c> {
+    /// Creates a new canvas with the specified cells, number of columns, and semigraphics,
+    /// clearing all pixels.
+    ///
+    /// The number of rows is the number of cells divided by the number of columns.
+    pub fn new(cells: &'c mut [u8], columns: usize, semigraphics: Semigraphics) -> Self {
+        let rows = cells.len().checked_div(columns).unwrap_or_default();
+        cells.fill(0);
+
+        Self {
+            cells,
+            columns,
+            rows,
+            semigraphics,
+        }
+    }
+
+    /// Returns the number of columns.
+    pub const fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the number of rows.
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the semigraphics.
+    pub const fn semigraphics(&self) -> Semigraphics {
+        self.semigraphics
+    }
+
+    /// Returns whether the pixel at the specified point is set, or `None` if there is no such
+    /// pixel.
+    pub fn pixel(&self, point: Point) -> Option<bool> {
+        let (index, bit) = self.locate(point)?;
+
+        Some(self.cells[index] & bit != 0)
+    }
+
+    /// Sets or clears the pixel at the specified point, if there is one.
+    pub fn set_pixel(&mut self, point: Point, is_set: bool) {
+        if let Some((index, bit)) = self.locate(point) {
+            match is_set {
+                true => self.cells[index] |= bit,
+                false => self.cells[index] &= !bit,
+            }
+        }
+    }
+
+    /// Returns the character in the specified column and row, or `None` if there is no such cell.
+    pub fn character(&self, column: usize, row: usize) -> Option<char> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        let block = self.cells[row * self.columns + column];
+
+        Some(self.semigraphics.character(block))
+    }
+
+    /// Returns an iterator over the characters in the specified row, which is empty if there is no
+    /// such row.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = char> + '_ {
+        let columns = if row < self.rows { self.columns } else { 0 };
+
+        (0..columns).filter_map(move |column| self.character(column, row))
+    }
+
+    /// Returns the index of the cell and the bit in its bitmask for the pixel at the specified
+    /// point.
+    fn locate(&self, point: Point) -> Option<(usize, u8)> {
+        let Size { width, height } = self.semigraphics.block_size();
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        let (column, row) = (x / width as usize, y / height as usize);
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        let bit = y % height as usize * width as usize + x % width as usize;
+
+        Some((row * self.columns + column, 1 << bit))
+    }
+}
+
+impl OriginDimensions for Canvas<'_> {
+    fn size(&self) -> Size {
+        let Size { width, height } = self.semigraphics.block_size();
+        let columns = self.columns.try_into().unwrap_or(u32::MAX);
+        let rows = self.rows.try_into().unwrap_or(u32::MAX);
+
+        Size::new(width.saturating_mul(columns), height.saturating_mul(rows))
+    }
+}
+
+impl DrawTarget for Canvas<'_> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set_pixel(point, color.is_on());
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let Size { width, height } = self.semigraphics.block_size();
+        let block = match color {
+            BinaryColor::On => (1u16 << (width * height)) - 1,
+            BinaryColor::Off => 0,
+        };
+
+        self.cells.fill(block as u8);
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Canvas<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                f.write_char('\n')?;
+            }
+
+            for c in self.row(row) {
+                f.write_char(c)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::Drawable;
+    use embedded_graphics::primitives::{Line, Primitive, PrimitiveStyle};
+
+    use super::*;
+
+    macro_rules! test_character {
+        (
+            $(
+                $fn_ident:ident, $semigraphics:expr, $block:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = $semigraphics.character($block);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_draw {
+        (
+            $(
+                $fn_ident:ident, $semigraphics:expr, $columns:expr, $rows:expr, $line:expr,
+                $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut cells = [0; $columns * $rows];
+                    let mut canvas = Canvas::new(&mut cells, $columns, $semigraphics);
+                    let style = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+                    $line.into_styled(style).draw(&mut canvas).unwrap();
+
+                    let expected: [&str; $rows] = $expected;
+                    for (row, expected) in expected.into_iter().enumerate() {
+                        assert!(canvas.row(row).eq(expected.chars()), "row {row}");
+                    }
+                }
+            )*
+        }
+    }
+
+    test_character! {
+        character_braille_blank, Semigraphics::Braille, 0x00, '\u{2800}',
+        character_braille_left_column, Semigraphics::Braille, 0x55, '\u{2847}',
+        character_braille_bottom_row, Semigraphics::Braille, 0xC0, '\u{28C0}',
+        character_braille_full, Semigraphics::Braille, 0xFF, '\u{28FF}',
+        character_sextant_empty, Semigraphics::Sextant, 0x00, ' ',
+        character_sextant_first, Semigraphics::Sextant, 0x01, '\u{1FB00}',
+        character_sextant_before_left_half, Semigraphics::Sextant, 0x14, '\u{1FB13}',
+        character_sextant_left_half, Semigraphics::Sextant, 0x15, '\u{258C}',
+        character_sextant_after_left_half, Semigraphics::Sextant, 0x16, '\u{1FB14}',
+        character_sextant_last, Semigraphics::Sextant, 0x3E, '\u{1FB3B}',
+        character_sextant_full, Semigraphics::Sextant, 0x3F, '\u{2588}',
+        character_octant_first, Semigraphics::Octant, 0x04, '\u{1CD00}',
+        character_octant_after_quadrant, Semigraphics::Octant, 0x06, '\u{1CD01}',
+        character_octant_quadrant, Semigraphics::Octant, 0xA0, '\u{2597}',
+        character_octant_last, Semigraphics::Octant, 0xFE, '\u{1CDE5}',
+        character_octant_full, Semigraphics::Octant, 0xFF, '\u{2588}',
+    }
+
+    test_draw! {
+        draw_braille_diagonal,
+        Semigraphics::Braille,
+        2,
+        1,
+        Line::new(Point::new(0, 0), Point::new(3, 3)),
+        ["\u{2811}\u{2884}"],
+        draw_sextant_top_row,
+        Semigraphics::Sextant,
+        2,
+        1,
+        Line::new(Point::new(0, 0), Point::new(3, 0)),
+        ["\u{1FB02}\u{1FB02}"],
+        draw_octant_left_column,
+        Semigraphics::Octant,
+        1,
+        2,
+        Line::new(Point::new(0, 0), Point::new(0, 7)),
+        ["\u{258C}", "\u{258C}"],
+        draw_octant_clipped,
+        Semigraphics::Octant,
+        1,
+        1,
+        Line::new(Point::new(-2, 3), Point::new(5, 3)),
+        ["\u{2582}"],
+    }
+}
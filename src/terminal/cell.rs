@@ -0,0 +1,185 @@
+use embedded_graphics::pixelcolor::Rgb888;
+
+/// The colors of the 16-color palette, in the order of the SGR color codes, with the normal colors
+/// followed by their bright variants.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The levels of the red, green, and blue components in the 6×6×6 color cube of the 256-color
+/// palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Terminal color, as set by SGR sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AnsiColor {
+    /// The default color, which is the text color or the background color of the style.
+    #[default]
+    Default,
+    /// A color in the 256-color palette, with the 16 colors of the 16-color palette first, followed
+    /// by a 6×6×6 color cube, and then by 24 shades of gray.
+    Indexed(u8),
+    /// A 24-bit color.
+    Rgb(u8, u8, u8),
+}
+
+/// Width of a terminal cell, for characters that take up two cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CellWidth {
+    /// A cell with a character that takes up one cell.
+    #[default]
+    Narrow,
+    /// A cell with a character that takes up this cell and the next one.
+    Wide,
+    /// The second cell of a wide character, which has no character of its own.
+    Continuation,
+}
+
+/// Attributes of a terminal cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Attributes {
+    /// The foreground color.
+    pub foreground: AnsiColor,
+    /// The background color.
+    pub background: AnsiColor,
+    /// Whether the text is bold, which is rendered using the bright variants of the first eight
+    /// colors of the palette.
+    pub bold: bool,
+    /// Whether the text is underlined.
+    pub underline: bool,
+    /// Whether the text is struck through.
+    pub strikethrough: bool,
+    /// Whether the foreground and background colors are swapped.
+    pub reverse: bool,
+}
+
+/// Terminal cell, holding a character and its attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cell {
+    /// The character.
+    pub character: char,
+    /// The attributes.
+    pub attributes: Attributes,
+    /// The width.
+    pub width: CellWidth,
+    /// Whether the cell has changed since it was last drawn.
+    pub(super) dirty: bool,
+}
+
+impl AnsiColor {
+    /// Returns the 24-bit color for this color, or `None` for the default color.
+    pub const fn to_rgb(self) -> Option<Rgb888> {
+        let (r, g, b) = match self {
+            Self::Default => return None,
+            Self::Indexed(index @ 0..16) => PALETTE[index as usize],
+            Self::Indexed(index @ 16..232) => {
+                let index = index as usize - 16;
+                let r = CUBE_LEVELS[index / 36];
+                let g = CUBE_LEVELS[index / 6 % 6];
+                let b = CUBE_LEVELS[index % 6];
+
+                (r, g, b)
+            }
+            Self::Indexed(index) => {
+                let level = 8 + (index - 232) * 10;
+
+                (level, level, level)
+            }
+            Self::Rgb(r, g, b) => (r, g, b),
+        };
+
+        Some(Rgb888::new(r, g, b))
+    }
+
+    /// Returns the bright variant of this color if it is one of the first eight colors of the
+    /// palette, or this color otherwise.
+    pub(super) const fn bright(self) -> Self {
+        match self {
+            Self::Indexed(index @ 0..8) => Self::Indexed(index + 8),
+            color => color,
+        }
+    }
+}
+
+impl Cell {
+    /// Blank cell, with a space character, the default attributes, and marked as changed.
+    pub const BLANK: Self = Self {
+        character: ' ',
+        attributes: Attributes {
+            foreground: AnsiColor::Default,
+            background: AnsiColor::Default,
+            bold: false,
+            underline: false,
+            strikethrough: false,
+            reverse: false,
+        },
+        width: CellWidth::Narrow,
+        dirty: true,
+    };
+
+    /// Returns a blank cell with the specified background color, marked as changed.
+    pub(super) const fn erased(background: AnsiColor) -> Self {
+        let mut cell = Self::BLANK;
+        cell.attributes.background = background;
+
+        cell
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::BLANK
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_to_rgb {
+        (
+            $(
+                $fn_ident:ident, $color:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = $color.to_rgb();
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_to_rgb! {
+        to_rgb_default, AnsiColor::Default, None,
+        to_rgb_red, AnsiColor::Indexed(1), Some(Rgb888::new(205, 0, 0)),
+        to_rgb_bright_white, AnsiColor::Indexed(15), Some(Rgb888::new(255, 255, 255)),
+        to_rgb_cube_first, AnsiColor::Indexed(16), Some(Rgb888::new(0, 0, 0)),
+        to_rgb_cube_orange, AnsiColor::Indexed(208), Some(Rgb888::new(255, 135, 0)),
+        to_rgb_cube_last, AnsiColor::Indexed(231), Some(Rgb888::new(255, 255, 255)),
+        to_rgb_gray_first, AnsiColor::Indexed(232), Some(Rgb888::new(8, 8, 8)),
+        to_rgb_gray_last, AnsiColor::Indexed(255), Some(Rgb888::new(238, 238, 238)),
+        to_rgb_24_bit, AnsiColor::Rgb(80, 0, 80), Some(Rgb888::new(80, 0, 80)),
+    }
+}
@@ -0,0 +1,480 @@
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Point, Size};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::PixelColor;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::primitives::{Line, Rectangle};
+use embedded_graphics::text::renderer::TextRenderer;
+
+use crate::color::{Invert, Screen, WeightedAvg};
+use crate::style::BitmapFontStyle;
+
+/// The box-drawing characters for junctions of light and heavy lines, indexed by the weights of
+/// the arms going up, right, down, and left, in base 3, with `0` for none, `1` for light, and `2`
+/// for heavy.
+const LIGHT_HEAVY: [char; 81] = [
+    ' ', '╴', '╸', '╷', '┐', '┑', '╻', '┒', '┓', '╶', '─', '╾', '┌', '┬', '┭', '┎', '┰', '┱', '╺',
+    '╼', '━', '┍', '┮', '┯', '┏', '┲', '┳', '╵', '┘', '┙', '│', '┤', '┥', '╽', '┧', '┪', '└', '┴',
+    '┵', '├', '┼', '┽', '┟', '╁', '╅', '┕', '┶', '┷', '┝', '┾', '┿', '┢', '╆', '╈', '╹', '┚', '┛',
+    '╿', '┦', '┩', '┃', '┨', '┫', '┖', '┸', '┹', '┞', '╀', '╃', '┠', '╂', '╉', '┗', '┺', '┻', '┡',
+    '╄', '╇', '┣', '╊', '╋',
+];
+
+/// The box-drawing characters for junctions of single and double lines, indexed by the weights of
+/// the arms going up, right, down, and left, in base 3, with `0` for none, `1` for single, and `2`
+/// for double; junctions that have no character of their own are marked with `'\0'`.
+const SINGLE_DOUBLE: [char; 81] = [
+    ' ', '\0', '\0', '\0', '\0', '╕', '\0', '╖', '╗', '\0', '\0', '\0', '\0', '\0', '\0', '╓', '╥',
+    '\0', '\0', '\0', '═', '╒', '\0', '╤', '╔', '\0', '╦', '\0', '\0', '╛', '\0', '\0', '╡', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '╘', '\0', '╧', '╞', '\0',
+    '╪', '\0', '\0', '\0', '\0', '╜', '╝', '\0', '\0', '\0', '║', '╢', '╣', '╙', '╨', '\0', '\0',
+    '\0', '\0', '╟', '╫', '\0', '╚', '\0', '╩', '\0', '\0', '\0', '╠', '\0', '╬',
+];
+
+/// The indices of the arms of a junction.
+const UP: usize = 0;
+const RIGHT: usize = 1;
+const DOWN: usize = 2;
+const LEFT: usize = 3;
+
+/// Line style for the lines of a frame, in order of precedence where lines overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LineStyle {
+    /// Light lines with rounded corners; where these lines meet other lines, they are light lines.
+    Arc,
+    /// Light lines.
+    Light,
+    /// Heavy lines.
+    Heavy,
+    /// Double lines; where these lines meet light or heavy lines in a way that has no matching
+    /// box-drawing character, heavy lines are used instead.
+    Double,
+}
+
+/// Junction in a cell of a frame, made up of the arms of the lines that go through the cell or end
+/// in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Junction {
+    arms: [Option<LineStyle>; 4],
+}
+
+/// Frame, a grid of character cells in which rectangles and horizontal and vertical lines are
+/// composed into box-drawing characters.
+///
+/// The junctions are kept in the specified buffer, in row-major order. Every cell gets the
+/// box-drawing character that matches the arms of all lines that go through it or end in it, with
+/// the heavier line style taking precedence where lines overlap, and cells have the same size as
+/// those of a [`Terminal`](super::Terminal) with the same style. The box-drawing characters need to
+/// be included in the bitmap font, which is best done with **M<sup>+</sup> Code**, in which these
+/// characters line up with each other.
+///
+/// Drawing the frame draws the cells that have a box-drawing character, leaving the other cells
+/// untouched.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Frame<'a, 'b, 'g, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    junctions: Junctions<'g>,
+    position: Point,
+    cell_size: Size,
+    style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+/// Junctions in a grid of cells, in row-major order.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Junctions<'g> {
+    data: &'g mut [Junction],
+    columns: usize,
+    rows: usize,
+}
+
+impl Junction {
+    /// Junction without any arms, in a cell that has no box-drawing character.
+    pub const EMPTY: Self = Self { arms: [None; 4] };
+
+    /// Returns the box-drawing character for this junction, or `None` if it has no arms.
+    pub fn character(&self) -> Option<char> {
+        use LineStyle::*;
+
+        match self.arms {
+            [None, None, None, None] => return None,
+            [None, Some(Arc), Some(Arc), None] => return Some('\u{256D}'),
+            [None, None, Some(Arc), Some(Arc)] => return Some('\u{256E}'),
+            [Some(Arc), None, None, Some(Arc)] => return Some('\u{256F}'),
+            [Some(Arc), Some(Arc), None, None] => return Some('\u{2570}'),
+            _ => {}
+        }
+
+        let weights = self.arms.map(|arm| match arm {
+            None => 0,
+            Some(Arc | Light) => 1,
+            Some(Heavy) => 2,
+            Some(Double) => 3,
+        });
+
+        if !weights.contains(&3) {
+            return Some(LIGHT_HEAVY[index(weights)]);
+        }
+
+        // Heavy lines that meet double lines are drawn as double lines.
+        let mut weights = weights.map(|weight| if weight == 2 { 3 } else { weight });
+        if let Some(c) = single_double(weights) {
+            return Some(c);
+        }
+
+        // Single lines that meet double lines along the same axis are drawn as double lines.
+        for axis in [[UP, DOWN], [RIGHT, LEFT]] {
+            if axis.iter().any(|&arm| weights[arm] == 3) {
+                for arm in axis {
+                    if weights[arm] > 0 {
+                        weights[arm] = 3;
+                    }
+                }
+            }
+        }
+
+        if let Some(c) = single_double(weights) {
+            return Some(c);
+        }
+
+        // Double lines that end in this junction are drawn as heavy lines.
+        Some(LIGHT_HEAVY[index(weights.map(|weight| weight.min(2)))])
+    }
+
+    /// Adds an arm with the specified line style, unless there already is an arm with a line
+    /// style that takes precedence.
+    fn add_arm(&mut self, arm: usize, line_style: LineStyle) {
+        self.arms[arm] = self.arms[arm].max(Some(line_style));
+    }
+}
+
+/// Returns the box-drawing character for a junction of single and double lines with the specified
+/// weights of its arms, where `3` is for double, or `None` if there is no such character.
+fn single_double(weights: [u8; 4]) -> Option<char> {
+    match SINGLE_DOUBLE[index(weights.map(|weight| weight.min(2)))] {
+        '\0' => None,
+        c => Some(c),
+    }
+}
+
+/// Returns the index into a table of box-drawing characters for the specified weights of the arms
+/// going up, right, down, and left.
+fn index(weights: [u8; 4]) -> usize {
+    weights
+        .iter()
+        .fold(0, |index, &weight| index * 3 + weight as usize)
+}
+
+impl<'a, 'b, 'g, T, C, const N: usize> Frame<'a, 'b, 'g, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new frame with the specified junctions, number of columns, position of the top
+    /// left corner, and style, clearing all junctions.
+    ///
+    /// The number of rows is the number of junctions divided by the number of columns.
+    pub fn new(
+        junctions: &'g mut [Junction],
+        columns: usize,
+        position: Point,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Self {
+        let cell_size = super::cell_size(&style);
+
+        Self {
+            junctions: Junctions::new(junctions, columns),
+            position,
+            cell_size,
+            style,
+        }
+    }
+
+    /// Returns the number of columns.
+    pub const fn columns(&self) -> usize {
+        self.junctions.columns
+    }
+
+    /// Returns the number of rows.
+    pub const fn rows(&self) -> usize {
+        self.junctions.rows
+    }
+
+    /// Returns the size of a cell in pixels.
+    pub const fn cell_size(&self) -> Size {
+        self.cell_size
+    }
+
+    /// Returns the bounding box of this frame.
+    pub fn bounding_box(&self) -> Rectangle {
+        let width = self.cell_size.width.saturating_mul(self.columns() as u32);
+        let height = self.cell_size.height.saturating_mul(self.rows() as u32);
+
+        Rectangle::new(self.position, Size::new(width, height))
+    }
+
+    /// Returns the junction in the specified column and row, or `None` if there is no such cell.
+    pub fn junction(&self, column: usize, row: usize) -> Option<&Junction> {
+        self.junctions.get(column, row)
+    }
+
+    /// Adds the outline of the specified rectangle, in columns and rows, with the specified line
+    /// style.
+    ///
+    /// Rectangles with the [`Arc`](LineStyle::Arc) line style have rounded corners, unless other
+    /// lines meet them there.
+    pub fn add_rectangle(&mut self, rectangle: &Rectangle, line_style: LineStyle) {
+        self.junctions.add_rectangle(rectangle, line_style);
+    }
+
+    /// Adds the specified line, in columns and rows, with the specified line style.
+    ///
+    /// Lines that are neither horizontal nor vertical are ignored.
+    pub fn add_line(&mut self, line: &Line, line_style: LineStyle) {
+        self.junctions.add_line(line, line_style);
+    }
+
+    /// Removes all rectangles and lines.
+    pub fn clear(&mut self) {
+        self.junctions.clear();
+    }
+}
+
+impl<'g> Junctions<'g> {
+    /// Creates new junctions with the specified buffer and number of columns, clearing all
+    /// junctions.
+    fn new(data: &'g mut [Junction], columns: usize) -> Self {
+        let rows = data.len().checked_div(columns).unwrap_or_default();
+        let mut junctions = Self {
+            data,
+            columns,
+            rows,
+        };
+        junctions.clear();
+
+        junctions
+    }
+
+    /// Returns the junction in the specified column and row.
+    fn get(&self, column: usize, row: usize) -> Option<&Junction> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.data.get(row * self.columns + column)
+    }
+
+    /// Adds the specified arm to the junction in the specified column and row, if there is one.
+    fn add_arm(&mut self, column: i32, row: i32, arm: usize, line_style: LineStyle) {
+        let (Ok(column), Ok(row)) = (usize::try_from(column), usize::try_from(row)) else {
+            return;
+        };
+
+        if column < self.columns && row < self.rows {
+            self.data[row * self.columns + column].add_arm(arm, line_style);
+        }
+    }
+
+    /// Adds the outline of the specified rectangle.
+    fn add_rectangle(&mut self, rectangle: &Rectangle, line_style: LineStyle) {
+        let Some(bottom_right) = rectangle.bottom_right() else {
+            return;
+        };
+
+        let top_left = rectangle.top_left;
+        let top_right = Point::new(bottom_right.x, top_left.y);
+        let bottom_left = Point::new(top_left.x, bottom_right.y);
+        for (start, end) in [
+            (top_left, top_right),
+            (top_right, bottom_right),
+            (bottom_left, bottom_right),
+            (top_left, bottom_left),
+        ] {
+            self.add_line(&Line::new(start, end), line_style);
+        }
+    }
+
+    /// Adds the specified line, if it is horizontal or vertical.
+    fn add_line(&mut self, line: &Line, line_style: LineStyle) {
+        let Line { start, end } = *line;
+        if start.y == end.y {
+            let (first, last) = (start.x.min(end.x), start.x.max(end.x));
+            for column in first.max(0)..=last.min(self.columns as i32 - 1) {
+                if column > first {
+                    self.add_arm(column, start.y, LEFT, line_style);
+                }
+
+                if column < last {
+                    self.add_arm(column, start.y, RIGHT, line_style);
+                }
+            }
+        } else if start.x == end.x {
+            let (first, last) = (start.y.min(end.y), start.y.max(end.y));
+            for row in first.max(0)..=last.min(self.rows as i32 - 1) {
+                if row > first {
+                    self.add_arm(start.x, row, UP, line_style);
+                }
+
+                if row < last {
+                    self.add_arm(start.x, row, DOWN, line_style);
+                }
+            }
+        }
+    }
+
+    /// Clears all junctions.
+    fn clear(&mut self) {
+        self.data.fill(Junction::EMPTY);
+    }
+}
+
+impl<'a, 'b, T, C, const N: usize> Drawable for Frame<'a, 'b, '_, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    BitmapFontStyle<'a, 'b, T, C, N>: TextRenderer<Color = T>,
+{
+    type Color = T;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let Size { width, height } = self.cell_size;
+        for row in 0..self.rows() {
+            for column in 0..self.columns() {
+                let Some(c) = self.junction(column, row).and_then(Junction::character) else {
+                    continue;
+                };
+
+                let x = width
+                    .saturating_mul(column as u32)
+                    .try_into()
+                    .unwrap_or(i32::MAX);
+                let y = height
+                    .saturating_mul(row as u32)
+                    .try_into()
+                    .unwrap_or(i32::MAX);
+                let top_left = self.position + Point::new(x, y);
+                let cell_box = Rectangle::new(top_left, self.cell_size);
+                let mut buffer = [0; 4];
+                let text = c.encode_utf8(&mut buffer);
+                super::draw_in_cell_box(&self.style, text, &cell_box, target)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_character {
+        (
+            $(
+                $fn_ident:ident, $arms:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let junction = Junction { arms: $arms };
+                    assert_eq!(junction.character(), $expected);
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_compose {
+        (
+            $(
+                $fn_ident:ident, $columns:expr, $rows:expr, $shapes:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut data = [Junction::EMPTY; $columns * $rows];
+                    let mut junctions = Junctions::new(&mut data, $columns);
+                    let shapes: &[(Shape, LineStyle)] = &$shapes;
+                    for (shape, line_style) in shapes {
+                        match shape {
+                            Shape::Rectangle(rectangle) => {
+                                junctions.add_rectangle(rectangle, *line_style);
+                            }
+                            Shape::Line(line) => junctions.add_line(line, *line_style),
+                        }
+                    }
+
+                    let expected: [&str; $rows] = $expected;
+                    for (row, expected) in expected.into_iter().enumerate() {
+                        let result = (0..$columns)
+                            .map(|column| junctions.get(column, row).unwrap())
+                            .map(|junction| junction.character().unwrap_or(' '));
+                        assert!(result.eq(expected.chars()), "row {row}");
+                    }
+                }
+            )*
+        }
+    }
+
+    enum Shape {
+        Rectangle(Rectangle),
+        Line(Line),
+    }
+
+    const fn rect(x: i32, y: i32, width: u32, height: u32) -> Shape {
+        Shape::Rectangle(Rectangle::new(Point::new(x, y), Size::new(width, height)))
+    }
+
+    const fn line(x1: i32, y1: i32, x2: i32, y2: i32) -> Shape {
+        Shape::Line(Line::new(Point::new(x1, y1), Point::new(x2, y2)))
+    }
+
+    use LineStyle::*;
+
+    test_character! {
+        character_empty, [None; 4], None,
+        character_light_cross, [Some(Light); 4], Some('┼'),
+        character_heavy_down_and_right, [None, Some(Heavy), Some(Heavy), None], Some('┏'),
+        character_mixed_tee, [Some(Light), Some(Heavy), Some(Light), None], Some('┝'),
+        character_light_left_end, [None, None, None, Some(Light)], Some('╴'),
+        character_arc_corner, [Some(Arc), None, None, Some(Arc)], Some('╯'),
+        character_arc_tee, [Some(Arc), Some(Arc), Some(Arc), None], Some('├'),
+        character_double_corner, [None, Some(Double), Some(Double), None], Some('╔'),
+        character_double_and_single, [Some(Light), Some(Double), Some(Light), Some(Double)], Some('╪'),
+        character_double_and_heavy, [Some(Heavy), None, Some(Double), None], Some('║'),
+        character_double_unified, [Some(Light), Some(Double), None, Some(Light)], Some('╧'),
+        character_double_end, [None, None, None, Some(Double)], Some('╸'),
+    }
+
+    test_compose! {
+        compose_rectangle, 3, 3, [(rect(0, 0, 3, 3), Light)], ["┌─┐", "│ │", "└─┘"],
+        compose_arc_rectangle, 3, 2, [(rect(0, 0, 3, 2), Arc)], ["╭─╮", "╰─╯"],
+        compose_split_rectangle,
+        3,
+        3,
+        [(rect(0, 0, 3, 3), Double), (line(0, 1, 2, 1), Light)],
+        ["╔═╗", "╟─╢", "╚═╝"],
+        compose_crossing_lines,
+        3,
+        3,
+        [(line(0, 1, 2, 1), Heavy), (line(1, 2, 1, 0), Light)],
+        [" ╷ ", "╺┿╸", " ╵ "],
+        compose_clipped_rectangle, 2, 2, [(rect(-1, -1, 3, 3), Light)], [" │", "─┘"],
+        compose_diagonal_ignored, 2, 2, [(line(0, 0, 1, 1), Light)], ["  ", "  "],
+    }
+}
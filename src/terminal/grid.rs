@@ -0,0 +1,514 @@
+use core::ops::Range;
+
+use super::cell::{AnsiColor, Attributes, Cell, CellWidth};
+use super::parser::{Action, Params};
+
+/// The number of columns between tab stops.
+const TAB_SIZE: usize = 8;
+
+/// Grid of terminal cells, with a cursor, the attributes for new characters, and a scroll region,
+/// applying the actions that are parsed from the input of a terminal.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(super) struct Grid<'g> {
+    cells: &'g mut [Cell],
+    columns: usize,
+    rows: usize,
+    column: usize,
+    row: usize,
+    /// Whether the cursor is past the last column, with the next character printed on a new line.
+    pending_wrap: bool,
+    attributes: Attributes,
+    /// The rows of the scroll region, excluding the bottom row itself.
+    scroll_region: Range<usize>,
+    saved_cursor: (usize, usize, Attributes),
+}
+
+impl<'g> Grid<'g> {
+    /// Creates a new grid with the specified cells and number of columns, clearing all cells.
+    pub(super) fn new(cells: &'g mut [Cell], columns: usize) -> Self {
+        let rows = cells.len().checked_div(columns).unwrap_or_default();
+        cells.fill(Cell::BLANK);
+
+        Self {
+            cells,
+            columns,
+            rows,
+            column: 0,
+            row: 0,
+            pending_wrap: false,
+            attributes: Attributes::default(),
+            scroll_region: 0..rows,
+            saved_cursor: (0, 0, Attributes::default()),
+        }
+    }
+
+    /// Returns the number of columns.
+    pub(super) const fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the number of rows.
+    pub(super) const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the cursor position as a column and a row.
+    pub(super) const fn cursor(&self) -> (usize, usize) {
+        (self.column, self.row)
+    }
+
+    /// Returns the cell in the specified column and row.
+    pub(super) fn cell(&self, column: usize, row: usize) -> Option<&Cell> {
+        if column >= self.columns {
+            return None;
+        }
+
+        self.cells.get(row * self.columns + column)
+    }
+
+    /// Returns the cells in the specified row.
+    pub(super) fn row(&self, row: usize) -> &[Cell] {
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    /// Marks all cells as drawn.
+    pub(super) fn mark_drawn(&mut self) {
+        for cell in self.cells.iter_mut() {
+            cell.dirty = false;
+        }
+    }
+
+    /// Applies the specified action, using a function that returns the number of cells, either
+    /// one or two, that a character takes up.
+    pub(super) fn apply(&mut self, action: Action, width_of: impl Fn(char) -> usize) {
+        if self.rows == 0 || self.columns == 0 {
+            return;
+        }
+
+        match action {
+            Action::Print(c) => self.print(c, width_of(c)),
+            Action::Execute(c) => self.execute(c),
+            Action::Escape(c) => self.escape(c),
+            Action::Csi { private: true, .. } => {}
+            Action::Csi { params, action, .. } => self.csi(params, action),
+        }
+    }
+
+    fn print(&mut self, c: char, width: usize) {
+        let width = width.clamp(1, 2).min(self.columns);
+        if self.pending_wrap || self.column + width > self.columns {
+            self.column = 0;
+            self.line_feed();
+        }
+
+        let column = self.column;
+        self.erase_partners(column..column + width);
+        let cell = Cell {
+            character: c,
+            attributes: self.attributes,
+            width: if width == 2 {
+                CellWidth::Wide
+            } else {
+                CellWidth::Narrow
+            },
+            dirty: true,
+        };
+
+        let index = self.row * self.columns + column;
+        self.cells[index] = cell;
+        if width == 2 {
+            self.cells[index + 1] = Cell {
+                character: ' ',
+                width: CellWidth::Continuation,
+                ..cell
+            };
+        }
+
+        self.column += width;
+        if self.column >= self.columns {
+            self.column = self.columns - 1;
+            self.pending_wrap = true;
+        }
+    }
+
+    fn execute(&mut self, c: char) {
+        match c {
+            '\n' | '\x0B' | '\x0C' => {
+                self.column = 0;
+                self.line_feed();
+            }
+            '\r' => self.set_column(0),
+            '\t' => self.set_column((self.column / TAB_SIZE + 1) * TAB_SIZE),
+            '\x08' => self.set_column(self.column.saturating_sub(1)),
+            _ => {}
+        }
+    }
+
+    fn escape(&mut self, c: char) {
+        match c {
+            'D' => self.line_feed(),
+            'E' => {
+                self.column = 0;
+                self.line_feed();
+            }
+            'M' => self.reverse_line_feed(),
+            '7' => self.saved_cursor = (self.column, self.row, self.attributes),
+            '8' => self.restore_cursor(),
+            'c' => *self = Self::new(core::mem::take(&mut self.cells), self.columns),
+            _ => {}
+        }
+    }
+
+    fn csi(&mut self, params: Params, action: char) {
+        let n = usize::from(params.get(0, 1));
+        let (top, bottom) = (self.scroll_region.start, self.scroll_region.end);
+        match action {
+            'A' => {
+                let limit = if self.row >= top { top } else { 0 };
+                self.set_row(self.row.saturating_sub(n).max(limit));
+            }
+            'B' | 'e' => {
+                let limit = if self.row < bottom { bottom } else { self.rows };
+                self.set_row(self.row.saturating_add(n).min(limit - 1));
+            }
+            'C' | 'a' => self.set_column(self.column.saturating_add(n)),
+            'D' => self.set_column(self.column.saturating_sub(n)),
+            'E' => {
+                self.csi(params, 'B');
+                self.set_column(0);
+            }
+            'F' => {
+                self.csi(params, 'A');
+                self.set_column(0);
+            }
+            'G' | '`' => self.set_column(n - 1),
+            'H' | 'f' => {
+                self.set_row(usize::from(params.get(0, 1)) - 1);
+                self.set_column(usize::from(params.get(1, 1)) - 1);
+            }
+            'd' => self.set_row(n - 1),
+            'J' => {
+                let cursor = self.row * self.columns + self.column;
+                let all = 0..self.rows * self.columns;
+                match params.get(0, 0) {
+                    0 => self.erase(cursor..all.end),
+                    1 => self.erase(0..cursor + 1),
+                    2 | 3 => self.erase(all),
+                    _ => {}
+                }
+            }
+            'K' => {
+                let start = self.row * self.columns;
+                let cursor = start + self.column;
+                match params.get(0, 0) {
+                    0 => self.erase(cursor..start + self.columns),
+                    1 => self.erase(start..cursor + 1),
+                    2 => self.erase(start..start + self.columns),
+                    _ => {}
+                }
+            }
+            'X' => {
+                let cursor = self.row * self.columns + self.column;
+                let end = self.row * self.columns + self.columns;
+                self.erase(cursor..cursor.saturating_add(n).min(end));
+            }
+            '@' => self.shift_chars(n, true),
+            'P' => self.shift_chars(n, false),
+            'L' if (top..bottom).contains(&self.row) => self.scroll_down(self.row..bottom, n),
+            'M' if (top..bottom).contains(&self.row) => self.scroll_up(self.row..bottom, n),
+            'S' => self.scroll_up(top..bottom, n),
+            'T' => self.scroll_down(top..bottom, n),
+            'm' => self.select_graphic_rendition(params.as_slice()),
+            'r' => {
+                let top = usize::from(params.get(0, 1)) - 1;
+                let bottom = usize::from(params.get(1, self.rows as u16)).min(self.rows);
+                if top + 1 < bottom {
+                    self.scroll_region = top..bottom;
+                    self.set_column(0);
+                    self.set_row(0);
+                }
+            }
+            's' => self.saved_cursor = (self.column, self.row, self.attributes),
+            'u' => self.restore_cursor(),
+            _ => {}
+        }
+    }
+
+    fn select_graphic_rendition(&mut self, params: &[u16]) {
+        let mut params = params.iter().copied();
+        let attributes = &mut self.attributes;
+        if params.len() == 0 {
+            *attributes = Attributes::default();
+        }
+
+        while let Some(param) = params.next() {
+            match param {
+                0 => *attributes = Attributes::default(),
+                1 => attributes.bold = true,
+                4 => attributes.underline = true,
+                7 => attributes.reverse = true,
+                9 => attributes.strikethrough = true,
+                21 | 22 => attributes.bold = false,
+                24 => attributes.underline = false,
+                27 => attributes.reverse = false,
+                29 => attributes.strikethrough = false,
+                30..=37 => attributes.foreground = AnsiColor::Indexed(param as u8 - 30),
+                38 => attributes.foreground = extended_color(&mut params),
+                39 => attributes.foreground = AnsiColor::Default,
+                40..=47 => attributes.background = AnsiColor::Indexed(param as u8 - 40),
+                48 => attributes.background = extended_color(&mut params),
+                49 => attributes.background = AnsiColor::Default,
+                90..=97 => attributes.foreground = AnsiColor::Indexed(param as u8 - 82),
+                100..=107 => attributes.background = AnsiColor::Indexed(param as u8 - 92),
+                _ => {}
+            }
+        }
+    }
+
+    fn set_column(&mut self, column: usize) {
+        self.column = column.min(self.columns - 1);
+        self.pending_wrap = false;
+    }
+
+    fn set_row(&mut self, row: usize) {
+        self.row = row.min(self.rows - 1);
+        self.pending_wrap = false;
+    }
+
+    fn restore_cursor(&mut self) {
+        let (column, row, attributes) = self.saved_cursor;
+        self.set_column(column);
+        self.set_row(row);
+        self.attributes = attributes;
+    }
+
+    /// Moves the cursor down, scrolling the scroll region up if the cursor is in its bottom row.
+    fn line_feed(&mut self) {
+        self.pending_wrap = false;
+        if self.row + 1 == self.scroll_region.end {
+            self.scroll_up(self.scroll_region.clone(), 1);
+        } else if self.row + 1 < self.rows {
+            self.row += 1;
+        }
+    }
+
+    /// Moves the cursor up, scrolling the scroll region down if the cursor is in its top row.
+    fn reverse_line_feed(&mut self) {
+        self.pending_wrap = false;
+        if self.row == self.scroll_region.start {
+            self.scroll_down(self.scroll_region.clone(), 1);
+        } else if self.row > 0 {
+            self.row -= 1;
+        }
+    }
+
+    /// Moves the specified rows up by the specified number of rows, with blank rows coming in at
+    /// the bottom.
+    fn scroll_up(&mut self, rows: Range<usize>, n: usize) {
+        let n = n.min(rows.len());
+        let cells = rows.start * self.columns..rows.end * self.columns;
+        let shift = n * self.columns;
+        self.cells[cells.clone()].copy_within(shift.., 0);
+        self.erase(cells.end - shift..cells.end);
+        self.mark_dirty(cells);
+    }
+
+    /// Moves the specified rows down by the specified number of rows, with blank rows coming in at
+    /// the top.
+    fn scroll_down(&mut self, rows: Range<usize>, n: usize) {
+        let n = n.min(rows.len());
+        let cells = rows.start * self.columns..rows.end * self.columns;
+        let shift = n * self.columns;
+        self.cells[cells.clone()].copy_within(..cells.len() - shift, shift);
+        self.erase(cells.start..cells.start + shift);
+        self.mark_dirty(cells);
+    }
+
+    /// Moves the cells from the cursor to the end of the line to the right, with blank cells
+    /// coming in at the cursor, or to the left, with blank cells coming in at the end of the line.
+    fn shift_chars(&mut self, n: usize, right: bool) {
+        let start = self.row * self.columns + self.column;
+        let end = self.row * self.columns + self.columns;
+        let n = n.min(end - start);
+        self.erase_partners(start - self.row * self.columns..self.columns);
+        if right {
+            self.cells[start..end].copy_within(..end - start - n, n);
+            self.erase(start..start + n);
+        } else {
+            self.cells[start..end].copy_within(n.., 0);
+            self.erase(end - n..end);
+        }
+
+        self.mark_dirty(start..end);
+    }
+
+    /// Replaces the specified cells with blank cells that have the current background color,
+    /// including the other halves of wide characters that are only partly replaced.
+    fn erase(&mut self, cells: Range<usize>) {
+        if cells.is_empty() {
+            return;
+        }
+
+        let (first, last) = (cells.start, cells.end - 1);
+        self.erase_partners_at(first, first % self.columns);
+        self.erase_partners_at(last, last % self.columns);
+        self.cells[cells].fill(Cell::erased(self.attributes.background));
+    }
+
+    /// Erases the other halves of wide characters at either end of the specified columns in the
+    /// row of the cursor.
+    fn erase_partners(&mut self, columns: Range<usize>) {
+        let start = self.row * self.columns;
+        self.erase_partners_at(start + columns.start, columns.start);
+        self.erase_partners_at(start + columns.end - 1, columns.end - 1);
+    }
+
+    /// Erases the other half of the wide character at the specified cell, which is in the
+    /// specified column, if there is one.
+    fn erase_partners_at(&mut self, index: usize, column: usize) {
+        let blank = Cell::erased(self.attributes.background);
+        match self.cells[index].width {
+            CellWidth::Wide if column + 1 < self.columns => self.cells[index + 1] = blank,
+            CellWidth::Continuation if column > 0 => self.cells[index - 1] = blank,
+            _ => {}
+        }
+    }
+
+    fn mark_dirty(&mut self, cells: Range<usize>) {
+        for cell in &mut self.cells[cells] {
+            cell.dirty = true;
+        }
+    }
+}
+
+/// Returns the color for the parameters that follow an extended color parameter, either `5` and
+/// an index in the 256-color palette or `2` and the red, green, and blue components.
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> AnsiColor {
+    let mut next = || params.next().unwrap_or_default().min(255) as u8;
+    match next() {
+        5 => AnsiColor::Indexed(next()),
+        2 => AnsiColor::Rgb(next(), next(), next()),
+        _ => AnsiColor::Default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parser::Parser;
+    use super::*;
+
+    macro_rules! test_apply {
+        (
+            $(
+                $fn_ident:ident, $input:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut cells = [Cell::BLANK; 18];
+                    let mut grid = Grid::new(&mut cells, 6);
+                    let mut parser = Parser::new();
+                    for c in $input.chars() {
+                        if let Some(action) = parser.advance(c) {
+                            grid.apply(action, |c| if c >= '\u{2E80}' { 2 } else { 1 });
+                        }
+                    }
+
+                    let expected: [&str; 3] = $expected;
+                    for (row, expected) in expected.into_iter().enumerate() {
+                        let cells = grid.row(row).iter();
+                        let cells = cells.filter(|cell| cell.width != CellWidth::Continuation);
+                        let mut chars = expected.chars();
+                        for cell in cells {
+                            assert_eq!(cell.character, chars.next().unwrap_or(' '), "row {row}");
+                        }
+                    }
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_select_graphic_rendition {
+        (
+            $(
+                $fn_ident:ident, $input:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut cells = [Cell::BLANK; 1];
+                    let mut grid = Grid::new(&mut cells, 1);
+                    let mut parser = Parser::new();
+                    for c in $input.chars() {
+                        if let Some(action) = parser.advance(c) {
+                            grid.apply(action, |_| 1);
+                        }
+                    }
+
+                    assert_eq!(grid.attributes, $expected);
+                }
+            )*
+        }
+    }
+
+    test_apply! {
+        apply_print, "abc", ["abc", "", ""],
+        apply_line_feed, "ab\ncd", ["ab", "cd", ""],
+        apply_carriage_return, "abc\rx", ["xbc", "", ""],
+        apply_wrap, "abcdefgh", ["abcdef", "gh", ""],
+        apply_pending_wrap, "abcdef\r", ["abcdef", "", ""],
+        apply_scroll, "a\nb\nc\nd", ["b", "c", "d"],
+        apply_tab, "a\tb", ["a    b", "", ""],
+        apply_backspace, "ab\x08x", ["ax", "", ""],
+        apply_cursor_position, "\x1B[2;3Hx", ["", "  x", ""],
+        apply_cursor_movement, "\x1B[3;4H\x1B[2A\x1B[2Dx\x1B[Cy", [" x y", "", ""],
+        apply_erase_display, "abc\ndef\x1B[1;2H\x1B[J", ["a", "", ""],
+        apply_erase_display_above, "abc\ndef\x1B[2;2H\x1B[1J", ["", "  f", ""],
+        apply_erase_line, "abcdef\x1B[1;3H\x1B[K", ["ab", "", ""],
+        apply_erase_chars, "abcdef\x1B[1;2H\x1B[2X", ["a  def", "", ""],
+        apply_insert_chars, "abcd\x1B[1;2H\x1B[2@", ["a  bcd", "", ""],
+        apply_delete_chars, "abcd\x1B[1;2H\x1B[2P", ["ad", "", ""],
+        apply_insert_lines, "a\nb\nc\x1B[2H\x1B[L", ["a", "", "b"],
+        apply_delete_lines, "a\nb\nc\x1B[1H\x1B[M", ["b", "c", ""],
+        apply_scroll_region, "a\x1B[2;3r\x1B[2Hb\nc\nd", ["a", "c", "d"],
+        apply_reverse_index, "a\nb\x1B[H\x1BMc", ["c", "a", "b"],
+        apply_save_restore, "ab\x1B7\ncd\x1B8x", ["abx", "cd", ""],
+        apply_wide, "a\u{6F22}b", ["a\u{6F22}b", "", ""],
+        apply_wide_wrap, "abcde\u{6F22}", ["abcde", "\u{6F22}", ""],
+        apply_wide_overwritten, "\u{6F22}\u{5B57}\x1B[1;2Hx", [" x\u{5B57}", "", ""],
+        apply_private_ignored, "\x1B[?25la", ["a", "", ""],
+    }
+
+    test_select_graphic_rendition! {
+        select_graphic_rendition_reset,
+        "\x1B[1;4;31m\x1B[m",
+        Attributes::default(),
+        select_graphic_rendition_colors,
+        "\x1B[31;102m",
+        Attributes {
+            foreground: AnsiColor::Indexed(1),
+            background: AnsiColor::Indexed(10),
+            ..Attributes::default()
+        },
+        select_graphic_rendition_256_colors,
+        "\x1B[38;5;208;48;2;80;0;80m",
+        Attributes {
+            foreground: AnsiColor::Indexed(208),
+            background: AnsiColor::Rgb(80, 0, 80),
+            ..Attributes::default()
+        },
+        select_graphic_rendition_decorations,
+        "\x1B[1;4;7;9m\x1B[22;27m",
+        Attributes {
+            underline: true,
+            strikethrough: true,
+            ..Attributes::default()
+        },
+        select_graphic_rendition_default_colors,
+        "\x1B[91;44m\x1B[39;49m",
+        Attributes::default(),
+    }
+}
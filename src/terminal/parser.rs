@@ -0,0 +1,247 @@
+/// The maximum number of parameters in a control sequence; any further parameters are ignored.
+const MAX_PARAMS: usize = 16;
+
+/// Parameters of a control sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(super) struct Params {
+    values: [u16; MAX_PARAMS],
+    len: usize,
+    is_overflowed: bool,
+}
+
+/// Action for a terminal to perform, as parsed from its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(super) enum Action {
+    /// Prints a character.
+    Print(char),
+    /// Executes a control character.
+    Execute(char),
+    /// Dispatches an escape sequence with the specified final character.
+    Escape(char),
+    /// Dispatches a control sequence with the specified parameters and final character, which, if
+    /// marked as private, starts with a parameter byte other than a digit or a semicolon.
+    Csi {
+        params: Params,
+        private: bool,
+        action: char,
+    },
+}
+
+/// Parser for the input of a terminal, turning characters into actions, following the state
+/// machine of DEC-compatible terminals.
+///
+/// Operating system commands are ignored, as are escape sequences and control sequences with
+/// intermediate characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(super) struct Parser {
+    state: State,
+    params: Params,
+    private: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum State {
+    #[default]
+    Ground,
+    Escape,
+    EscapeIntermediate,
+    Csi,
+    CsiIgnore,
+    Osc,
+}
+
+impl Params {
+    /// Returns the parameter at the specified index, or the specified default value if the
+    /// parameter is missing or zero.
+    pub(super) fn get(&self, index: usize, default: u16) -> u16 {
+        match self.values[..self.len].get(index) {
+            Some(&value) if value != 0 => value,
+            _ => default,
+        }
+    }
+
+    /// Returns the parameters as a slice, which is empty if there are none.
+    pub(super) fn as_slice(&self) -> &[u16] {
+        &self.values[..self.len]
+    }
+
+    /// Appends the specified digit to the last parameter, unless the parameters past the maximum
+    /// number are being ignored.
+    fn push_digit(&mut self, digit: u16) {
+        if self.is_overflowed {
+            return;
+        }
+
+        if self.len == 0 {
+            self.len = 1;
+        }
+
+        let value = &mut self.values[self.len - 1];
+        *value = value.saturating_mul(10).saturating_add(digit);
+    }
+
+    /// Starts the next parameter, or, if the maximum number of parameters has been reached,
+    /// starts ignoring the parameters that follow.
+    fn next_param(&mut self) {
+        if self.len == 0 {
+            self.len = 1;
+        }
+
+        if self.len < MAX_PARAMS {
+            self.len += 1;
+        } else {
+            self.is_overflowed = true;
+        }
+    }
+}
+
+impl Parser {
+    /// Creates a new parser in its ground state.
+    pub(super) const fn new() -> Self {
+        Self {
+            state: State::Ground,
+            params: Params {
+                values: [0; MAX_PARAMS],
+                len: 0,
+                is_overflowed: false,
+            },
+            private: false,
+        }
+    }
+
+    /// Advances the parser by the specified character, returning the action to perform, if any.
+    pub(super) fn advance(&mut self, c: char) -> Option<Action> {
+        match (self.state, c) {
+            (_, '\x18' | '\x1A') => self.state = State::Ground,
+            (State::Osc, '\x07') => self.state = State::Ground,
+            (_, '\x1B') => self.state = State::Escape,
+            (State::Osc, _) => {}
+            (_, c) if c.is_control() => return Some(Action::Execute(c)),
+            (State::Ground, c) => return Some(Action::Print(c)),
+            (State::Escape, '[') => {
+                self.params = Params::default();
+                self.private = false;
+                self.state = State::Csi;
+            }
+            (State::Escape, ']') => self.state = State::Osc,
+            (State::Escape | State::EscapeIntermediate, '\x20'..='\x2F') => {
+                self.state = State::EscapeIntermediate;
+            }
+            (State::Escape, '\x30'..='\x7E') => {
+                self.state = State::Ground;
+
+                return Some(Action::Escape(c));
+            }
+            (State::Csi, '0'..='9') => self.params.push_digit(c as u16 - '0' as u16),
+            (State::Csi, ';') => self.params.next_param(),
+            (State::Csi, '<'..='?') if self.params == Params::default() && !self.private => {
+                self.private = true;
+            }
+            (State::Csi, '\x40'..='\x7E') => {
+                self.state = State::Ground;
+
+                return Some(Action::Csi {
+                    params: self.params,
+                    private: self.private,
+                    action: c,
+                });
+            }
+            (State::Csi | State::CsiIgnore, _) => self.state = State::CsiIgnore,
+            (State::EscapeIntermediate, '\x30'..='\x7E') => self.state = State::Ground,
+            (State::Escape | State::EscapeIntermediate, _) => self.state = State::Ground,
+        }
+
+        if self.state == State::CsiIgnore && ('\x40'..='\x7E').contains(&c) {
+            self.state = State::Ground;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_advance {
+        (
+            $(
+                $fn_ident:ident, $input:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut parser = Parser::new();
+                    let mut actions = $input.chars().filter_map(|c| parser.advance(c));
+                    let expected: &[Action] = &$expected;
+                    for &action in expected {
+                        assert_eq!(actions.next(), Some(action));
+                    }
+
+                    assert_eq!(actions.next(), None);
+                }
+            )*
+        }
+    }
+
+    fn params(values: &[u16]) -> Params {
+        let mut params = Params::default();
+        params.values[..values.len()].copy_from_slice(values);
+        params.len = values.len();
+
+        params
+    }
+
+    fn csi(values: &[u16], action: char) -> Action {
+        Action::Csi {
+            params: params(values),
+            private: false,
+            action,
+        }
+    }
+
+    fn overflowed_csi(values: &[u16], action: char) -> Action {
+        let mut params = params(values);
+        params.is_overflowed = true;
+
+        Action::Csi {
+            params,
+            private: false,
+            action,
+        }
+    }
+
+    test_advance! {
+        advance_print, "ab", [Action::Print('a'), Action::Print('b')],
+        advance_execute, "a\r\n", [Action::Print('a'), Action::Execute('\r'), Action::Execute('\n')],
+        advance_escape, "\x1B7", [Action::Escape('7')],
+        advance_csi_no_params, "\x1B[H", [csi(&[], 'H')],
+        advance_csi_params, "\x1B[12;40H", [csi(&[12, 40], 'H')],
+        advance_csi_empty_param, "\x1B[;5H", [csi(&[0, 5], 'H')],
+        advance_csi_sgr, "\x1B[1;38;5;208mx", [csi(&[1, 38, 5, 208], 'm'), Action::Print('x')],
+        advance_csi_private,
+        "\x1B[?25l",
+        [Action::Csi { params: params(&[25]), private: true, action: 'l' }],
+        advance_csi_control_inside, "\x1B[1\n2A", [Action::Execute('\n'), csi(&[12], 'A')],
+        advance_csi_intermediate_ignored, "\x1B[2 qa", [Action::Print('a')],
+        advance_csi_cancel, "\x1B[2\x18a", [Action::Print('a')],
+        advance_charset_ignored, "\x1B(Ba", [Action::Print('a')],
+        advance_osc_bell, "\x1B]0;title\x07a", [Action::Print('a')],
+        advance_osc_string_terminator, "\x1B]0;title\x1B\\a", [Action::Escape('\\'), Action::Print('a')],
+        advance_param_saturating, "\x1B[99999A", [csi(&[u16::MAX], 'A')],
+        advance_params_max,
+        "\x1B[1;2;3;4;5;6;7;8;9;10;11;12;13;14;15;16m",
+        [csi(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16], 'm')],
+        advance_params_past_max_ignored,
+        "\x1B[1;2;3;4;5;6;7;8;9;10;11;12;13;14;15;16;17;18m",
+        [overflowed_csi(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16], 'm')],
+        advance_params_reset_after_overflow,
+        "\x1B[1;2;3;4;5;6;7;8;9;10;11;12;13;14;15;16;17m\x1B[2J",
+        [overflowed_csi(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16], 'm'), csi(&[2], 'J')],
+    }
+}
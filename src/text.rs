@@ -0,0 +1,23 @@
+//! Text drawables.
+//!
+//! The drawables in this module render text using a [`BitmapFontStyle`](crate::style::BitmapFontStyle),
+//! taking care of what would otherwise require multiple calls to
+//! [`draw_string`](embedded_graphics::text::renderer::TextRenderer::draw_string), with glyph
+//! images positioned, clipped, and blended the same way as they are in a single text run.
+mod arc;
+mod best_fit;
+mod console;
+mod crossfade;
+mod marquee;
+mod number;
+mod overlay;
+mod paragraph;
+
+pub use arc::{ArcBaseline, ArcDirection, ArcText};
+pub use best_fit::BestFit;
+pub use console::Console;
+pub use crossfade::Crossfade;
+pub use marquee::Marquee;
+pub use number::{Number, NumberFormat, Padding, Radix, Sign};
+pub use overlay::{MetricsOverlay, OverlayColors};
+pub use paragraph::{LineSpans, Paragraph};
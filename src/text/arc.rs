@@ -0,0 +1,505 @@
+use core::f32::consts::{FRAC_2_PI, FRAC_PI_2};
+
+use embedded_graphics::Drawable;
+use embedded_graphics::Pixel;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Angle, Dimensions, Point};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, GrayColor, PixelColor};
+use embedded_graphics::primitives::{PointsIter, Rectangle};
+use embedded_graphics::text::Baseline;
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::image::{Image, ImageRaw};
+use crate::scalar::Scalar;
+use crate::style::{self, BitmapFontStyle, ImagesOfChars};
+
+/// Text along a circular arc, for round displays, with every glyph cluster rotated to the tangent
+/// of the circle at its center.
+///
+/// Angles are measured from the positive _x_-axis, increasing in the clockwise direction as seen
+/// on the display. The glyph images are resampled using bilinear interpolation of their gray
+/// values, and where glyph images overlap, the colors are mixed in the blend mode of the style.
+/// The background is filled in for the area that the rotated glyph images cover; text decorations
+/// are not drawn.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ArcText<'a, 'b, 't, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// The text.
+    pub text: &'t str,
+    /// The center of the circle.
+    pub center: Point,
+    /// The radius of the circle in pixels.
+    pub radius: u32,
+    /// The angle at which the text starts.
+    pub start_angle: Angle,
+    /// The direction in which the text runs along the circle.
+    pub direction: ArcDirection,
+    /// The side of the text that the circle is on.
+    pub baseline: ArcBaseline,
+    /// The style.
+    pub style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+/// Direction of text along a circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ArcDirection {
+    /// The text runs clockwise, with the tops of the glyphs facing away from the center; for text
+    /// along the top of a round display.
+    #[default]
+    Clockwise,
+    /// The text runs counter-clockwise, with the tops of the glyphs facing the center; for text
+    /// along the bottom of a round display.
+    CounterClockwise,
+}
+
+/// Side of the text that the circle is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ArcBaseline {
+    /// The circle runs along the inner edge of the line, with the text outside the circle.
+    Inner,
+    /// The circle runs along the outer edge of the line, with the text inside the circle; for text
+    /// that follows the bezel of a round display.
+    #[default]
+    Outer,
+}
+
+/// The maximum number of glyph images that are mixed at any one pixel, which is also the size of
+/// the window of consecutive glyph images that a glyph image is mixed with; glyph images that are
+/// further apart in the text are not mixed with each other.
+const MAX_LAYERS: usize = 16;
+
+/// The number of glyph images after the one being drawn that are kept in the window.
+const LOOKAHEAD: usize = MAX_LAYERS / 2 - 1;
+
+/// Frame of a glyph cluster, for mapping between the positions of pixels in a straight text run
+/// with its baseline at the _x_-axis and their positions on the display.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    origin: [f32; 2],
+    tangent: [f32; 2],
+    normal: [f32; 2],
+    anchor: f32,
+}
+
+/// Iterator over the glyph images of the characters in a text run, along with the frames of the
+/// glyph clusters that they belong to.
+#[derive(Debug, Clone)]
+struct FramedImages<'a, 'b, 't, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    images: ImagesOfChars<'a, 'b, 't, C, N>,
+    frame: Option<Frame>,
+    center: [f32; 2],
+    radius: f32,
+    start_angle: f32,
+    direction: ArcDirection,
+}
+
+/// Gray value of a glyph image pixel, as a level from zero to the maximum value for its type.
+trait Level: Sized {
+    /// Returns the level of this gray value.
+    fn level(self) -> u8;
+
+    /// Returns the gray value for the specified level.
+    fn from_level(level: u8) -> Self;
+}
+
+impl<'a, 'b, 't, T, C, const N: usize> ArcText<'a, 'b, 't, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new arc text with the specified text, circle, start angle, and style, running
+    /// clockwise inside the circle.
+    pub const fn new(
+        text: &'t str,
+        center: Point,
+        radius: u32,
+        start_angle: Angle,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Self {
+        Self {
+            text,
+            center,
+            radius,
+            start_angle,
+            direction: ArcDirection::Clockwise,
+            baseline: ArcBaseline::Outer,
+            style,
+        }
+    }
+
+    /// Returns the radius of the circle that the alphabetic baseline runs along.
+    fn baseline_radius(&self) -> f32 {
+        let metrics = &self.style.font.metrics;
+        let top = metrics.y_offset(Baseline::Top) as f32;
+        let bottom = metrics.y_offset(Baseline::Bottom) as f32;
+        let radius = self.radius as f32;
+        match (self.direction, self.baseline) {
+            (ArcDirection::Clockwise, ArcBaseline::Inner) => radius - bottom,
+            (ArcDirection::Clockwise, ArcBaseline::Outer) => radius - top,
+            (ArcDirection::CounterClockwise, ArcBaseline::Inner) => radius + top,
+            (ArcDirection::CounterClockwise, ArcBaseline::Outer) => radius + bottom,
+        }
+    }
+
+    /// Returns the glyph images of the text, along with the frames of their glyph clusters.
+    fn framed_images(&self) -> FramedImages<'a, '_, 't, C, N> {
+        let zero = Scalar::ZERO;
+        let images = style::images_of_chars(&self.style.font.charmap, self.text, zero, zero);
+
+        FramedImages {
+            images,
+            frame: None,
+            center: [self.center.x as f32, self.center.y as f32],
+            radius: self.baseline_radius(),
+            start_angle: self.start_angle.to_radians(),
+            direction: self.direction,
+        }
+    }
+}
+
+impl<'a, C, const N: usize> FramedImages<'a, '_, '_, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Returns the angle at the specified distance along the baseline, in radians.
+    fn angle(&self, distance: f32) -> f32 {
+        match self.direction {
+            ArcDirection::Clockwise => self.start_angle + distance / self.radius,
+            ArcDirection::CounterClockwise => self.start_angle - distance / self.radius,
+        }
+    }
+
+    /// Returns the frame of a glyph cluster that is centered at the specified distance along the
+    /// baseline.
+    fn frame(&self, anchor: f32) -> Frame {
+        let [sin, cos] = sin_cos(self.angle(anchor));
+        let [x, y] = self.center;
+        let origin = [x + self.radius * cos, y + self.radius * sin];
+        let (tangent, normal) = match self.direction {
+            ArcDirection::Clockwise => ([-sin, cos], [cos, sin]),
+            ArcDirection::CounterClockwise => ([sin, -cos], [-cos, -sin]),
+        };
+
+        Frame {
+            origin,
+            tangent,
+            normal,
+            anchor,
+        }
+    }
+}
+
+impl<'a, C, const N: usize> Iterator for FramedImages<'a, '_, '_, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    type Item = (Image<ImageRaw<'a, C>>, Frame);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (image, is_overlay) = self.images.next()?;
+        let frame = match self.frame {
+            Some(frame) if is_overlay => frame,
+            _ => {
+                let start = self.images.x.to_f32();
+                let mut images = self.images.clone();
+                while images.next().is_some_and(|(_, is_overlay)| is_overlay) {}
+
+                let end = images.x.to_f32();
+                let frame = self.frame((start + end) / 2.0);
+                self.frame = Some(frame);
+
+                frame
+            }
+        };
+
+        Some((image, frame))
+    }
+}
+
+impl Frame {
+    /// Returns the position in the straight text run that the center of the specified pixel on the
+    /// display maps to.
+    fn to_text(self, point: Point) -> [f32; 2] {
+        let dx = point.x as f32 + 0.5 - self.origin[0];
+        let dy = point.y as f32 + 0.5 - self.origin[1];
+        let x = self.anchor + dx * self.tangent[0] + dy * self.tangent[1];
+        let y = -(dx * self.normal[0] + dy * self.normal[1]);
+
+        [x, y]
+    }
+
+    /// Returns the position on the display that the specified position in the straight text run
+    /// maps to.
+    fn to_display(self, [x, y]: [f32; 2]) -> [f32; 2] {
+        let along = x - self.anchor;
+        let [origin_x, origin_y] = self.origin;
+        let [tangent_x, tangent_y] = self.tangent;
+        let [normal_x, normal_y] = self.normal;
+
+        [
+            origin_x + along * tangent_x - y * normal_x,
+            origin_y + along * tangent_y - y * normal_y,
+        ]
+    }
+
+    /// Returns the area on the display that the specified glyph image can be drawn to, including
+    /// the pixels that are partially covered by the glyph image after interpolation.
+    fn display_area<I: Dimensions>(self, image: &I) -> Rectangle {
+        let image_box = image.bounding_box();
+        let left = image_box.top_left.x as f32 - 1.0;
+        let top = image_box.top_left.y as f32 - 1.0;
+        let right = left + image_box.size.width as f32 + 2.0;
+        let bottom = top + image_box.size.height as f32 + 2.0;
+        let corners = [[left, top], [right, top], [left, bottom], [right, bottom]];
+        let [mut min_x, mut min_y, mut max_x, mut max_y] = [i32::MAX, i32::MAX, i32::MIN, i32::MIN];
+        for corner in corners {
+            let [x, y] = self.to_display(corner);
+            min_x = min_x.min(floor(x));
+            min_y = min_y.min(floor(y));
+            max_x = max_x.max(floor(x));
+            max_y = max_y.max(floor(y));
+        }
+
+        Rectangle::with_corners(Point::new(min_x, min_y), Point::new(max_x, max_y))
+    }
+}
+
+/// Returns the gray value of the specified glyph image at the specified position, interpolated
+/// bilinearly between the centers of the four closest pixels, or `None` if the position is not
+/// close enough to the glyph image for any of its pixels to contribute.
+fn sample<'a, C>(image: &Image<ImageRaw<'a, C>>, [x, y]: [f32; 2]) -> Option<C>
+where
+    C: PixelColor + From<C::Raw> + Level,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    let image_box = image.bounding_box();
+    let x = x - image_box.top_left.x as f32 - 0.5;
+    let y = y - image_box.top_left.y as f32 - 0.5;
+    let (width, height) = (image_box.size.width as f32, image_box.size.height as f32);
+    if x <= -1.0 || y <= -1.0 || x >= width || y >= height {
+        return None;
+    }
+
+    let (left, top) = (floor(x), floor(y));
+    let (x_weight, y_weight) = (x - left as f32, y - top as f32);
+    let level = |column: i32, row: i32| {
+        let point = image_box.top_left + Point::new(column, row);
+        let level = image.color_at(point).map(C::level);
+
+        level.unwrap_or_default() as f32
+    };
+
+    let upper = level(left, top) * (1.0 - x_weight) + level(left + 1, top) * x_weight;
+    let lower = level(left, top + 1) * (1.0 - x_weight) + level(left + 1, top + 1) * x_weight;
+    let level = upper * (1.0 - y_weight) + lower * y_weight;
+
+    Some(C::from_level((level + 0.5) as u8))
+}
+
+/// Returns the largest integer that is less than or equal to the specified value.
+fn floor(value: f32) -> i32 {
+    let result = value as i32;
+
+    result - ((result as f32) > value) as i32
+}
+
+/// Returns the sine and the cosine of the specified angle in radians.
+fn sin_cos(angle: f32) -> [f32; 2] {
+    let quadrant = angle * FRAC_2_PI;
+    let quadrant = floor(quadrant + 0.5);
+    let x = angle - quadrant as f32 * FRAC_PI_2;
+    let x2 = x * x;
+    let sin = x * (1.0 - x2 / 6.0 * (1.0 - x2 / 20.0 * (1.0 - x2 / 42.0)));
+    let cos = 1.0 - x2 / 2.0 * (1.0 - x2 / 12.0 * (1.0 - x2 / 30.0 * (1.0 - x2 / 56.0)));
+    match quadrant & 3 {
+        0 => [sin, cos],
+        1 => [cos, -sin],
+        2 => [-sin, -cos],
+        _ => [-cos, sin],
+    }
+}
+
+macro_rules! impl_level {
+    (
+        $(
+            $color_ident:ident, $level_ident:ident, $color_type:ty, $into_level:expr, $from_level:expr,
+        )*
+    ) => {
+        $(
+            impl Level for $color_type {
+                fn level($color_ident) -> u8 {
+                    $into_level
+                }
+
+                fn from_level($level_ident: u8) -> Self {
+                    $from_level
+                }
+            }
+        )*
+    }
+}
+
+impl_level! {
+    self, level, BinaryColor, self.is_on().into(), BinaryColor::from(level > 0),
+    self, level, Gray2, self.luma(), Gray2::new(level),
+    self, level, Gray4, self.luma(), Gray4::new(level),
+    self, level, Gray8, self.luma(), Gray8::new(level),
+}
+
+macro_rules! impl_drawable {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<T, const N: usize> Drawable for ArcText<'_, '_, '_, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+                type Output = Angle;
+
+                fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let style = &self.style;
+                    let colormap: Colormap<T, $array_length> = style.faded_colormap();
+                    let start = colormap.first();
+                    let end = colormap.last();
+                    let mut framed_images = self.framed_images();
+                    if framed_images.radius <= 0.0 {
+                        return Ok(self.start_angle);
+                    }
+
+                    let clip_area = target.bounding_box();
+                    let mut window = [const { None }; MAX_LAYERS];
+                    let mut framed_areas = framed_images.by_ref().map(|(image, frame)| {
+                        let area = frame.display_area(&image);
+                        (image, frame, area)
+                    });
+
+                    for slot in window.iter_mut().take(LOOKAHEAD + 1) {
+                        *slot = framed_areas.next();
+                    }
+
+                    let mut index = 0;
+                    while let Some((image, frame, area)) = &window[index % MAX_LAYERS] {
+                        let area = area.intersection(&clip_area);
+                        if !area.is_zero_sized() {
+                            let mut layers = [None; MAX_LAYERS];
+                            let first = index.saturating_sub(MAX_LAYERS - LOOKAHEAD - 1);
+                            let last = index + LOOKAHEAD;
+                            let others = (first..=last).filter_map(|other_index| {
+                                let (other_image, other_frame, other_area) =
+                                    window[other_index % MAX_LAYERS].as_ref()?;
+                                let is_this = other_index == index;
+
+                                (!area.intersection(other_area).is_zero_sized())
+                                    .then_some((other_image, other_frame, is_this))
+                            });
+
+                            for (layer, other) in layers.iter_mut().zip(others) {
+                                *layer = Some(other);
+                            }
+
+                            let pixels = area.points().filter_map(|point| {
+                                sample(image, frame.to_text(point))?;
+
+                                let mut color = None;
+                                for (other_image, other_frame, is_this) in layers.iter().flatten() {
+                                    let Some(value) =
+                                        sample(other_image, other_frame.to_text(point))
+                                    else {
+                                        continue;
+                                    };
+
+                                    if color.is_none() && !is_this {
+                                        return None;
+                                    }
+
+                                    let other_color = colormap.get(value);
+                                    color = Some(color.map_or(other_color, |color: T| {
+                                        color.blend(other_color, start, end, style.blend_mode)
+                                    }));
+                                }
+
+                                color.map(|color| Pixel(point, color))
+                            });
+
+                            target.draw_iter(pixels)?;
+                        }
+
+                        window[(index + LOOKAHEAD + 1) % MAX_LAYERS] = framed_areas.next();
+                        index += 1;
+                    }
+
+                    let distance = framed_images.images.x.to_f32();
+
+                    Ok(Angle::from_radians(framed_images.angle(distance)))
+                }
+            }
+        )*
+    }
+}
+
+impl_drawable! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::{FRAC_PI_2, FRAC_PI_6, PI};
+
+    use super::*;
+
+    macro_rules! test_sin_cos {
+        (
+            $(
+                $fn_ident:ident, $angle:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let [sin, cos] = sin_cos($angle);
+                    let [expected_sin, expected_cos]: [f32; 2] = $expected;
+                    assert!((sin - expected_sin).abs() < 1e-5, "sin: {sin}");
+                    assert!((cos - expected_cos).abs() < 1e-5, "cos: {cos}");
+                }
+            )*
+        }
+    }
+
+    test_sin_cos! {
+        sin_cos_zero, 0.0, [0.0, 1.0],
+        sin_cos_sixth_pi, FRAC_PI_6, [0.5, 0.866_025_4],
+        sin_cos_half_pi, FRAC_PI_2, [1.0, 0.0],
+        sin_cos_pi, PI, [0.0, -1.0],
+        sin_cos_negative_half_pi, -FRAC_PI_2, [-1.0, 0.0],
+        sin_cos_one_radian, 1.0, [0.841_471, 0.540_302_3],
+        sin_cos_two_turns, 4.0 * PI + 1.0, [0.841_471, 0.540_302_3],
+        sin_cos_negative_three_radians, -3.0, [-0.141_12, -0.989_992_5],
+    }
+}
@@ -0,0 +1,194 @@
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::Point;
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::renderer::TextRenderer;
+use embedded_graphics::text::{Alignment, Baseline};
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::scalar;
+use crate::style::BitmapFontStyle;
+
+use super::Paragraph;
+
+/// Best fit, a text that is drawn with the first of several styles, such as styles for different
+/// sizes of a font, in which it fits into a bounding box.
+///
+/// The styles are expected to be ordered from the one with the largest font to the one with the
+/// smallest font. Without wrapping, the text is broken into lines at line feeds only, and the
+/// bounding box of every line as given by
+/// [`measure_string`](embedded_graphics::text::renderer::TextRenderer::measure_string) has to fit;
+/// with wrapping, the text is laid out as a [`Paragraph`] in the bounding box, which has to fit
+/// the text as a whole. Lines are placed from the top of the bounding box downwards. If the text
+/// does not fit with any of the styles, nothing is drawn.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BestFit<'a, 'b, 's, 't, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// The text.
+    pub text: &'t str,
+    /// The bounding box, which the text has to fit into.
+    pub bounding_box: Rectangle,
+    /// The horizontal alignment of every line within the bounding box.
+    pub alignment: Alignment,
+    /// Whether to break lines at spaces as well, for lines that are wider than the bounding box.
+    pub wrap: bool,
+    /// The styles to choose from, from the largest to the smallest font.
+    pub styles: &'s [BitmapFontStyle<'a, 'b, T, C, N>],
+}
+
+impl<'a, 'b, 's, 't, T, C, const N: usize> BestFit<'a, 'b, 's, 't, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new best fit for the specified text, bounding box, and styles, with every line
+    /// aligned to the left and with no wrapping.
+    pub const fn new(
+        text: &'t str,
+        bounding_box: Rectangle,
+        styles: &'s [BitmapFontStyle<'a, 'b, T, C, N>],
+    ) -> Self {
+        Self {
+            text,
+            bounding_box,
+            alignment: Alignment::Left,
+            wrap: false,
+            styles,
+        }
+    }
+
+    /// Returns the paragraph for laying out the text of this best fit with the specified style.
+    fn paragraph(
+        &self,
+        style: &BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Paragraph<'a, 'b, 't, T, C, Rectangle, N> {
+        let mut paragraph = Paragraph::new(self.text, self.bounding_box, style.clone());
+        paragraph.alignment = self.alignment;
+
+        paragraph
+    }
+}
+
+impl<'a, 'b, T, C, const N: usize> BestFit<'a, 'b, '_, '_, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+    BitmapFontStyle<'a, 'b, T, C, N>: TextRenderer<Color = T>,
+{
+    /// Returns the index of the first style with which the text fits into the bounding box, or
+    /// `None` if there is no such style.
+    pub fn position(&self) -> Option<usize> {
+        self.styles.iter().position(|style| self.fits(style))
+    }
+
+    /// Returns whether the text fits into the bounding box with the specified style.
+    fn fits(&self, style: &BitmapFontStyle<'a, 'b, T, C, N>) -> bool {
+        let line_fits = |line: &str, position: Point| {
+            let metrics = style.measure_string(line, position, Baseline::Top);
+            let bounding_box = metrics.bounding_box;
+            let bottom_right = bounding_box.bottom_right();
+
+            bounding_box.is_zero_sized()
+                || self.bounding_box.contains(bounding_box.top_left)
+                    && bottom_right.is_some_and(|point| self.bounding_box.contains(point))
+        };
+
+        if self.wrap {
+            let mut fits = true;
+            let rest = self.paragraph(style).lay_out(|line, x, y| {
+                fits &= line_fits(line, Point::new(scalar::floor(x), y));
+            });
+
+            return fits && rest.is_empty();
+        }
+
+        let line_height = style.font.metrics.line_height();
+        let mut y = self.bounding_box.top_left.y;
+        self.text.split('\n').all(|line| {
+            let position = self.line_position(style, line, y);
+            y = y.saturating_add_unsigned(line_height);
+
+            line_fits(line, position)
+        })
+    }
+
+    /// Returns the position of the specified line with the specified style, aligned within the
+    /// bounding box, at the specified position along the _y_-axis.
+    fn line_position(&self, style: &BitmapFontStyle<'a, 'b, T, C, N>, line: &str, y: i32) -> Point {
+        let left = self.bounding_box.top_left.x;
+        let width = self.bounding_box.size.width;
+        let metrics = style.measure_string(line, Point::new(left, y), Baseline::Top);
+        let gap = width.saturating_sub(metrics.bounding_box.size.width);
+        let x = match self.alignment {
+            Alignment::Left => left,
+            Alignment::Center => left.saturating_add_unsigned(gap / 2),
+            Alignment::Right => left.saturating_add_unsigned(gap),
+        };
+
+        Point::new(x, y)
+    }
+}
+
+macro_rules! impl_drawable {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<T, const N: usize> Drawable for BestFit<'_, '_, '_, '_, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+                type Output = Option<usize>;
+
+                fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let Some(index) = self.position() else {
+                        return Ok(None);
+                    };
+
+                    let style = &self.styles[index];
+                    if self.wrap {
+                        self.paragraph(style).draw(target)?;
+
+                        return Ok(Some(index));
+                    }
+
+                    let line_height = style.font.metrics.line_height();
+                    let mut y = self.bounding_box.top_left.y;
+                    style.discard_carryover();
+                    for line in self.text.split('\n') {
+                        let position = self.line_position(style, line, y);
+                        style.draw_string(line, position, Baseline::Top, target)?;
+                        style.discard_carryover();
+                        y = y.saturating_add_unsigned(line_height);
+                    }
+
+                    Ok(Some(index))
+                }
+            }
+        )*
+    }
+}
+
+impl_drawable! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
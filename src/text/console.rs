@@ -0,0 +1,391 @@
+use core::cell::Cell;
+use core::fmt::{self, Write};
+
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::{DrawTarget, DrawTargetExt};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::scalar::Scalar;
+use crate::style::{self, BitmapFontStyle};
+
+/// The number of characters between tab stops.
+const TAB_SIZE: usize = 8;
+
+/// Console, a terminal-like text area that takes formatted output via [`core::fmt::Write`],
+/// wrapping lines that are too wide and scrolling up once the bottom row is full.
+///
+/// The console keeps its lines in a ring buffer, in which every row of the bounding box is given
+/// an equal share of the specified byte buffer, for the UTF-8 encoded text of its line. Line feeds
+/// start a new line, carriage returns move the cursor back to the start of the line so that the
+/// text that follows overwrites it, and tabs advance the cursor to the next tab stop using spaces;
+/// all other control characters are ignored.
+///
+/// Drawing the console only redraws the lines that have changed since it was last drawn, unless
+/// it has scrolled, in which case every line is redrawn. Draw targets that can move a region of
+/// their framebuffer can avoid this by moving the contents of the bounding box up by
+/// [`scroll_offset`](Self::scroll_offset) pixels and then calling
+/// [`commit_scroll`](Self::commit_scroll).
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Console<'a, 'b, 'l, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    lines: Lines<'l>,
+    bounding_box: Rectangle,
+    style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+/// Ring buffer of the lines of a console, with a cursor at the end of the line in the bottom row
+/// that is in use, or wherever a carriage return has put it on that line.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Lines<'l> {
+    data: &'l mut [u8],
+    rows: usize,
+    first: usize,
+    row: usize,
+    column: usize,
+    /// The number of lines that have scrolled out of view.
+    top_line: usize,
+    /// The first line, counting the lines that have scrolled out of view, that has changed since
+    /// the lines were last drawn.
+    dirty_line: Cell<usize>,
+    /// The number of lines that had scrolled out of view when the lines were last drawn.
+    drawn_top_line: Cell<usize>,
+}
+
+impl<'a, 'b, 'l, T, C, const N: usize> Console<'a, 'b, 'l, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new, empty console with the specified buffer, bounding box, and style.
+    ///
+    /// The bounding box has as many rows as there are lines that fit into its height, and each
+    /// row gets the same number of bytes of the buffer.
+    pub fn new(
+        buffer: &'l mut [u8],
+        bounding_box: Rectangle,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Self {
+        let rows = bounding_box
+            .size
+            .height
+            .checked_div(style.font.metrics.line_height())
+            .unwrap_or_default();
+
+        Self {
+            lines: Lines::new(buffer, rows as usize),
+            bounding_box,
+            style,
+        }
+    }
+
+    /// Returns the bounding box of this console.
+    pub const fn bounding_box(&self) -> Rectangle {
+        self.bounding_box
+    }
+
+    /// Returns the text of the line in the specified row, or `None` if there is no such row.
+    pub fn line(&self, row: usize) -> Option<&str> {
+        (row < self.lines.rows).then(|| self.lines.line(row))
+    }
+
+    /// Clears all lines and moves the cursor to the top row.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Returns the distance in pixels that the lines have moved up since this console was last
+    /// drawn, which is at most the height of all rows.
+    pub fn scroll_offset(&self) -> u32 {
+        let scrolled_lines = self.lines.top_line - self.lines.drawn_top_line.get();
+        let scrolled_lines = scrolled_lines.min(self.lines.rows) as u32;
+
+        scrolled_lines * self.style.font.metrics.line_height()
+    }
+
+    /// Marks the lines as having been moved up by the [scroll offset](Self::scroll_offset) on the
+    /// draw target, so that drawing this console does not redraw the lines that were moved.
+    pub fn commit_scroll(&mut self) {
+        self.lines.drawn_top_line.set(self.lines.top_line);
+    }
+}
+
+impl<'a, T, C, const N: usize> Write for Console<'a, '_, '_, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        let width = self.bounding_box.size.width.try_into().unwrap_or(i32::MAX);
+        let width = Scalar::from_int(width);
+        let charmap = &self.style.font.charmap;
+        self.lines.write_char(c, &|line, c| {
+            let mut buffer = [0; 4];
+            let c = c.encode_utf8(&mut buffer);
+
+            style::advance_width(charmap, line) + style::advance_width(charmap, c) <= width
+        });
+
+        Ok(())
+    }
+}
+
+impl<'l> Lines<'l> {
+    /// Creates new, empty lines with the specified buffer and number of rows.
+    fn new(data: &'l mut [u8], rows: usize) -> Self {
+        let mut lines = Self {
+            data,
+            rows,
+            first: 0,
+            row: 0,
+            column: 0,
+            top_line: 0,
+            dirty_line: Cell::new(0),
+            drawn_top_line: Cell::new(0),
+        };
+
+        lines.clear();
+        lines
+    }
+
+    /// Returns the number of bytes that every line can hold.
+    fn capacity(&self) -> usize {
+        self.data.len().checked_div(self.rows).unwrap_or_default()
+    }
+
+    /// Returns the bytes in the slot of the ring buffer for the line in the specified row.
+    fn slot(&self, row: usize) -> &[u8] {
+        let capacity = self.capacity();
+        let start = (self.first + row) % self.rows * capacity;
+
+        &self.data[start..start + capacity]
+    }
+
+    /// Returns the bytes in the slot of the ring buffer for the line in the specified row.
+    fn slot_mut(&mut self, row: usize) -> &mut [u8] {
+        let capacity = self.capacity();
+        let start = (self.first + row) % self.rows * capacity;
+
+        &mut self.data[start..start + capacity]
+    }
+
+    /// Returns the text of the line in the specified row, which ends at the first zero byte in
+    /// its slot.
+    fn line(&self, row: usize) -> &str {
+        let slot = self.slot(row);
+        let len = slot
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(slot.len());
+
+        core::str::from_utf8(&slot[..len]).unwrap_or_default()
+    }
+
+    /// Clears all lines and moves the cursor to the top row.
+    fn clear(&mut self) {
+        self.data.fill(0);
+        self.first = 0;
+        self.row = 0;
+        self.column = 0;
+        self.mark_dirty(0);
+    }
+
+    /// Marks the line in the specified row, and all lines below it, as changed.
+    fn mark_dirty(&self, row: usize) {
+        let line = self.top_line + row;
+        if line < self.dirty_line.get() {
+            self.dirty_line.set(line);
+        }
+    }
+
+    /// Returns whether the line in the specified row has changed since the lines were last drawn,
+    /// with all lines considered to have changed if the lines have scrolled since then.
+    fn is_dirty(&self, row: usize) -> bool {
+        self.top_line + row >= self.dirty_line.get() || self.top_line != self.drawn_top_line.get()
+    }
+
+    /// Marks all lines as drawn.
+    fn mark_drawn(&self) {
+        self.dirty_line.set(usize::MAX);
+        self.drawn_top_line.set(self.top_line);
+    }
+
+    /// Moves the cursor to the start of a new line, scrolling up if the cursor is in the bottom
+    /// row.
+    fn new_line(&mut self) {
+        if self.row + 1 < self.rows {
+            self.row += 1;
+        } else {
+            self.first = (self.first + 1) % self.rows;
+            self.top_line += 1;
+        }
+
+        self.column = 0;
+        self.slot_mut(self.row).fill(0);
+        self.mark_dirty(self.row);
+    }
+
+    /// Writes the specified character at the cursor, using a function that takes the text before
+    /// the cursor and the character, returning whether the character fits on the line.
+    fn write_char(&mut self, c: char, fits: &dyn Fn(&str, char) -> bool) {
+        if self.rows == 0 {
+            return;
+        }
+
+        match c {
+            '\n' => self.new_line(),
+            '\r' => self.column = 0,
+            '\t' => {
+                let chars = self.line(self.row)[..self.column].chars().count();
+                for _ in chars % TAB_SIZE..TAB_SIZE {
+                    self.write_char(' ', fits);
+                }
+            }
+            c if c.is_control() => {}
+            c => {
+                let line = self.line(self.row);
+                let prefix = &line[..self.column];
+                let replaced_len = line[self.column..].chars().next().map_or(0, char::len_utf8);
+                let len = line.len() - replaced_len + c.len_utf8();
+                if self.column > 0 && (len > self.capacity() || !fits(prefix, c)) {
+                    self.new_line();
+                    self.write_char(c, fits);
+
+                    return;
+                }
+
+                if len > self.capacity() {
+                    return;
+                }
+
+                let column = self.column;
+                let tail = column + replaced_len..line.len();
+                let slot = self.slot_mut(self.row);
+                slot.copy_within(tail, column + c.len_utf8());
+                c.encode_utf8(&mut slot[column..]);
+                slot[len..].fill(0);
+                self.column += c.len_utf8();
+                self.mark_dirty(self.row);
+            }
+        }
+    }
+}
+
+macro_rules! impl_drawable {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<T, const N: usize> Drawable for Console<'_, '_, '_, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+                type Output = ();
+
+                fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let style = &self.style;
+                    let line_height = style.font.metrics.line_height();
+                    let left = self.bounding_box.top_left.x;
+                    let right = left.saturating_add_unsigned(self.bounding_box.size.width);
+                    let mut target = target.clipped(&self.bounding_box);
+                    let mut position = self.bounding_box.top_left;
+                    style.discard_carryover();
+                    for row in 0..self.lines.rows {
+                        if self.lines.is_dirty(row) {
+                            let line = self.lines.line(row);
+                            let next = style.draw_string(line, position, Baseline::Top, &mut target)?;
+                            let width = right.saturating_sub(next.x).try_into().unwrap_or_default();
+                            style.draw_whitespace(width, next, Baseline::Top, &mut target)?;
+                            style.discard_carryover();
+                        }
+
+                        position.y = position.y.saturating_add_unsigned(line_height);
+                    }
+
+                    self.lines.mark_drawn();
+
+                    Ok(())
+                }
+            }
+        )*
+    }
+}
+
+impl_drawable! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_lines {
+        (
+            $(
+                $fn_ident:ident, $text:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let mut data = [0; 30];
+                    let mut lines = Lines::new(&mut data, 3);
+                    for c in $text.chars() {
+                        lines.write_char(c, &|prefix, _| prefix.chars().count() < 6);
+                    }
+
+                    let result: [&str; 3] = core::array::from_fn(|row| lines.line(row));
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_lines! {
+        lines_empty, "", ["", "", ""],
+        lines_single_line, "abc", ["abc", "", ""],
+        lines_line_feed, "abc\ndef", ["abc", "def", ""],
+        lines_wrap, "abcdefgh", ["abcdef", "gh", ""],
+        lines_scroll, "a\nb\nc\nd", ["b", "c", "d"],
+        lines_scroll_wrap, "a\nb\ncdefghij", ["b", "cdefgh", "ij"],
+        lines_carriage_return, "abcd\rxy", ["xycd", "", ""],
+        lines_carriage_return_line_feed, "ab\r\ncd", ["ab", "cd", ""],
+        lines_carriage_return_multi_byte, "\u{3042}bc\rxy", ["xyc", "", ""],
+        lines_tab, "a\tb", ["a     ", "  b", ""],
+        lines_tab_stop, "\tb", ["      ", "  b", ""],
+        lines_control, "a\x1Bb\x07c", ["abc", "", ""],
+        lines_byte_capacity, "\u{3042}\u{3044}\u{3046}\u{3048}", ["\u{3042}\u{3044}\u{3046}", "\u{3048}", ""],
+        lines_clear_on_scroll, "abcdef\n\n\nx", ["", "", "x"],
+    }
+}
@@ -0,0 +1,247 @@
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Dimensions, Point, Size};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
+use embedded_graphics::primitives::{PointsIter, Rectangle};
+use embedded_graphics::text::Baseline;
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::image::{Image, ImageRaw};
+use crate::scalar::{self, Scalar};
+use crate::style::{self, BitmapFontStyle, ImagesOfChars};
+
+/// Crossfade, two text runs at the same position, with the glyph coverage of one text run faded
+/// out and that of the other faded in.
+///
+/// Each text run has its colormap faded towards the background color by its share of the ratio,
+/// on top of the opacity factor of the style. Where glyph images overlap, be it glyph images of
+/// the same text run or of both, the colors are mixed in the blend mode of the style. The
+/// background is filled in from top to bottom, for the length of the longer text run; text
+/// decorations are not drawn.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Crossfade<'a, 'b, 't, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// The text that is faded out.
+    pub from: &'t str,
+    /// The text that is faded in.
+    pub to: &'t str,
+    /// The position.
+    pub position: Point,
+    /// The baseline.
+    pub baseline: Baseline,
+    /// The crossfade ratio, from `0`, which shows only the text that is faded out, to `255`, which
+    /// shows only the text that is faded in.
+    pub ratio: u8,
+    /// The style.
+    pub style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+impl<'a, 'b, 't, T, C, const N: usize> Crossfade<'a, 'b, 't, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new crossfade between the specified texts at the specified position, using the
+    /// alphabetic baseline, with the ratio set to show only the text that is faded out.
+    pub const fn new(
+        from: &'t str,
+        to: &'t str,
+        position: Point,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            position,
+            baseline: Baseline::Alphabetic,
+            ratio: 0,
+            style,
+        }
+    }
+
+    /// Returns the glyph images of the specified text, placed at the position of this crossfade.
+    fn images_of<'s>(&'s self, text: &'t str) -> ImagesOfChars<'a, 's, 't, C, N> {
+        let metrics = &self.style.font.metrics;
+        let x = Scalar::from_int(self.position.x);
+        let y = self
+            .position
+            .y
+            .saturating_add(metrics.y_offset(self.baseline));
+
+        style::images_of_chars(&self.style.font.charmap, text, x, Scalar::from_int(y))
+    }
+
+    /// Returns the leftmost and the rightmost column that is covered by the glyph images of the
+    /// specified text, the line piece up to the position of the next character included.
+    fn columns(&self, text: &'t str) -> (i32, i32) {
+        let mut left = self.position.x;
+        let mut right = self.position.x;
+        let mut images = self.images_of(text);
+        for (image, _) in images.by_ref() {
+            let image_box = image.bounding_box();
+            let image_right = image_box
+                .top_left
+                .x
+                .saturating_add_unsigned(image_box.size.width);
+            if !image_box.is_zero_sized() {
+                left = left.min(image_box.top_left.x);
+                right = right.max(image_right);
+            }
+        }
+
+        (left, right.max(scalar::trunc(images.x)))
+    }
+}
+
+/// The maximum number of glyph images that are mixed at any one pixel; glyph images beyond this
+/// number that cover the same column are not drawn.
+const MAX_LAYERS: usize = 16;
+
+/// Glyph images that cover a column, each with the colormap of the text run that it belongs to.
+type Layers<'a, 'c, T, C, const M: usize> =
+    [Option<(Image<ImageRaw<'a, C>>, &'c Colormap<T, M>)>; MAX_LAYERS];
+
+/// Skips the glyph images that end at or before the specified column, for as long as they are in
+/// order, then adds the glyph images that cover the column to the layers, returning the next
+/// column at which a glyph image starts or ends.
+fn push_layers<'a, 'c, T, C, const N: usize, const M: usize>(
+    images: &mut ImagesOfChars<'a, '_, '_, C, N>,
+    x: i32,
+    colormap: &'c Colormap<T, M>,
+    layers: &mut Layers<'a, 'c, T, C, M>,
+    count: &mut usize,
+) -> i32
+where
+    C: PixelColor + From<C::Raw>,
+    T: Copy,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    loop {
+        let mut next_images = images.clone();
+        match next_images.next() {
+            Some((image, _)) if image.bounding_box().columns().end <= x => *images = next_images,
+            _ => break,
+        }
+    }
+
+    let mut next_x = i32::MAX;
+    for (image, is_overlay) in images.clone() {
+        let columns = image.bounding_box().columns();
+        if columns.is_empty() {
+            continue;
+        }
+
+        if columns.start > x {
+            next_x = next_x.min(columns.start);
+            if is_overlay {
+                continue;
+            }
+
+            break;
+        }
+
+        if columns.end > x {
+            next_x = next_x.min(columns.end);
+            if let Some(layer) = layers.get_mut(*count) {
+                *layer = Some((image, colormap));
+                *count += 1;
+            }
+        }
+    }
+
+    next_x
+}
+
+macro_rules! impl_drawable {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<T, const N: usize> Drawable for Crossfade<'_, '_, '_, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+                type Output = ();
+
+                fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let style = &self.style;
+                    let metrics = &style.font.metrics;
+                    let y = self.position.y.saturating_add(metrics.y_offset(self.baseline));
+                    let top = y.saturating_sub(metrics.y_offset(Baseline::Top));
+                    let bottom = y.saturating_sub(metrics.y_offset(Baseline::Bottom));
+                    let height = bottom.saturating_sub(top).try_into().unwrap_or_default();
+                    let (from_left, from_right) = self.columns(self.from);
+                    let (to_left, to_right) = self.columns(self.to);
+                    let left = from_left.min(to_left);
+                    let right = from_right.max(to_right);
+
+                    let colormap: Colormap<T, $array_length> = style.faded_colormap();
+                    let from_colormap = colormap.with_opacity(u8::MAX - self.ratio);
+                    let to_colormap = colormap.with_opacity(self.ratio);
+                    let start = colormap.first();
+                    let end = colormap.last();
+
+                    let mut from_images = self.images_of(self.from);
+                    let mut to_images = self.images_of(self.to);
+                    style.discard_carryover();
+                    let mut x = left;
+                    while x < right {
+                        let mut layers = [const { None }; MAX_LAYERS];
+                        let mut count = 0;
+                        let from_x =
+                            push_layers(&mut from_images, x, &from_colormap, &mut layers, &mut count);
+                        let to_x =
+                            push_layers(&mut to_images, x, &to_colormap, &mut layers, &mut count);
+                        let next_x = from_x.min(to_x).min(right);
+                        let width = next_x.abs_diff(x);
+                        let area = Rectangle::new(Point::new(x, top), Size::new(width, height));
+                        if count == 0 {
+                            target.fill_solid(&area, start)?;
+                        } else {
+                            let layers = layers.iter().flatten();
+                            let colors = area.points().map(|point| {
+                                layers.clone().fold(start, |color, (image, colormap)| {
+                                    match image.color_at(point) {
+                                        Some(value) => {
+                                            let other_color = colormap.get(value);
+                                            color.blend(other_color, start, end, style.blend_mode)
+                                        }
+                                        None => color,
+                                    }
+                                })
+                            });
+
+                            target.fill_contiguous(&area, colors)?;
+                        }
+
+                        x = next_x;
+                    }
+
+                    Ok(())
+                }
+            }
+        )*
+    }
+}
+
+impl_drawable! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
@@ -0,0 +1,130 @@
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::{DrawTarget, DrawTargetExt};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::scalar::{self, Scalar};
+use crate::style::{self, BitmapFontStyle, LineState};
+
+/// Marquee, a text run that scrolls horizontally through a viewport, wrapping around.
+///
+/// The text run is repeated for as long as it takes to fill the viewport, with its top aligned
+/// with the top of the viewport. Only the glyph clusters that are visible are drawn, using the
+/// glyph images for the sub-pixel offsets that are closest to their positions, which makes for
+/// smooth scrolling with bitmap fonts that have more than one glyph image per glyph.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Marquee<'a, 'b, 't, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// The text.
+    pub text: &'t str,
+    /// The viewport, outside of which nothing is drawn.
+    pub viewport: Rectangle,
+    /// The scroll offset in pixels; increasing this value moves the text to the left.
+    pub offset: Scalar,
+    /// The width of the gap between the end of the text run and its repetition in pixels.
+    pub gap: u32,
+    /// The style.
+    pub style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+impl<'a, 'b, 't, T, C, const N: usize> Marquee<'a, 'b, 't, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new marquee with the specified text, viewport, and style, with no scroll offset
+    /// and no gap.
+    pub const fn new(
+        text: &'t str,
+        viewport: Rectangle,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Self {
+        Self {
+            text,
+            viewport,
+            offset: Scalar::ZERO,
+            gap: 0,
+            style,
+        }
+    }
+
+    /// Returns the advance width of the text run in pixels.
+    fn text_width(&self) -> Scalar {
+        style::advance_width(&self.style.font.charmap, self.text)
+    }
+}
+
+macro_rules! impl_drawable {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<T, const N: usize> Drawable for Marquee<'_, '_, '_, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+                type Output = ();
+
+                fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let style = &self.style;
+                    let text_width = self.text_width();
+                    let gap = Scalar::from_int(self.gap.try_into().unwrap_or(i32::MAX));
+                    let period = text_width + gap;
+                    if period <= Scalar::ZERO || self.viewport.is_zero_sized() {
+                        return Ok(());
+                    }
+
+                    let offset = scalar::rem_euclid(self.offset, period);
+
+                    let y = self.viewport.top_left.y;
+                    let left = self.viewport.top_left.x;
+                    let right = left.saturating_add_unsigned(self.viewport.size.width);
+                    let mut target = target.clipped(&self.viewport);
+                    let mut x = Scalar::from_int(left) - offset - period;
+                    let colormap: Colormap<T, $array_length> = style.faded_colormap();
+                    style.discard_carryover();
+                    while scalar::floor(x) < right {
+                        let colormap = colormap.clone();
+                        let mut line_state =
+                            LineState::with_scalar_x(style, self.text, x, y, Baseline::Top, colormap)
+                                .visible_between(left, right);
+
+                        line_state.draw_images(style, None, &mut target)?;
+                        let position = line_state.finish(style, &mut target)?;
+                        style.draw_whitespace(self.gap, position, Baseline::Top, &mut target)?;
+                        x += period;
+                    }
+
+                    style.discard_carryover();
+
+                    Ok(())
+                }
+            }
+        )*
+    }
+}
+
+impl_drawable! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
@@ -0,0 +1,520 @@
+use core::convert::Infallible;
+
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::Point;
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
+use embedded_graphics::text::renderer::TextRenderer;
+use embedded_graphics::text::{Alignment, Baseline};
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::scalar::{self, Scalar};
+use crate::style::{self, BitmapFontStyle, LineState};
+
+/// The maximum number of digits, before and after the decimal point each, including zero padding.
+const MAX_DIGITS: u32 = 32;
+
+/// The size of the buffer for the characters of a formatted number, in bytes.
+const CAPACITY: usize = 128;
+
+/// Number, an integer or a fixed-point decimal value that is rendered without allocating memory.
+///
+/// The value is formatted into a buffer on the stack, and then drawn in pieces, using the glyph
+/// images of the digits, the sign, the separators, and the decimal point, which the bitmap font is
+/// required to include. With [`NumberFormat::fixed_width`] set, every digit is centered in a cell
+/// as wide as the widest digit, so that numbers do not jitter when drawn with proportional fonts.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Number<'a, 'b, 't, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// The value; with decimals, the value in units of the last decimal place.
+    pub value: i64,
+    /// The number format.
+    pub format: NumberFormat<'t>,
+    /// The position.
+    pub position: Point,
+    /// The horizontal alignment.
+    pub alignment: Alignment,
+    /// The baseline.
+    pub baseline: Baseline,
+    /// The style.
+    pub style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+/// Number format, for how the value of a [`Number`] is turned into characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NumberFormat<'t> {
+    /// The radix.
+    pub radix: Radix,
+    /// The number of decimal places; not used with hexadecimal numbers.
+    pub decimals: u8,
+    /// The minimum number of digits before the decimal point, which are padded to on the left.
+    pub min_digits: u8,
+    /// The padding.
+    pub padding: Padding,
+    /// The sign.
+    pub sign: Sign,
+    /// The optional separator between groups of three digits, or four in hexadecimal numbers.
+    pub separator: Option<char>,
+    /// The decimal point.
+    pub decimal_point: char,
+    /// The text before the number, such as a currency symbol or `0x`.
+    pub prefix: &'t str,
+    /// The text after the number, such as a unit symbol.
+    pub suffix: &'t str,
+    /// Whether every digit is centered in a cell as wide as the widest digit.
+    pub fixed_width: bool,
+}
+
+/// Radix, the base of the numeral system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Radix {
+    /// Decimal digits.
+    #[default]
+    Decimal,
+    /// Hexadecimal digits, using lowercase letters.
+    LowerHex,
+    /// Hexadecimal digits, using uppercase letters.
+    UpperHex,
+}
+
+/// Padding, for what fills the digits that are missing from the minimum number of digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Padding {
+    /// Leading zeros, after the sign.
+    #[default]
+    Zeros,
+    /// Blank space as wide as the digits and separators that it replaces, before the sign.
+    Blanks,
+}
+
+/// Sign, for which values are drawn with a sign in front of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Sign {
+    /// Only negative values have a sign, `-`.
+    #[default]
+    Negative,
+    /// Negative values have a `-` sign, and all other values have a `+` sign.
+    Always,
+}
+
+/// Formatted number, with its characters written from the end of the buffer backwards, and with
+/// the blank padding that goes in front of them.
+#[derive(Debug, Clone)]
+struct Formatted {
+    bytes: [u8; CAPACITY],
+    start: usize,
+    blank_digits: u32,
+    blank_separators: u32,
+}
+
+impl<'a, 'b, 't, T, C, const N: usize> Number<'a, 'b, 't, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new number with the specified value, number format, position, and style, using
+    /// left alignment and the alphabetic baseline.
+    pub const fn new(
+        value: i64,
+        format: NumberFormat<'t>,
+        position: Point,
+        style: BitmapFontStyle<'a, 'b, T, C, N>,
+    ) -> Self {
+        Self {
+            value,
+            format,
+            position,
+            alignment: Alignment::Left,
+            baseline: Baseline::Alphabetic,
+            style,
+        }
+    }
+
+    /// Returns the width of a digit cell, which is the advance width of either the widest digit or
+    /// the digit zero, depending on whether digits have a fixed width.
+    fn cell_width(&self) -> Scalar {
+        let charmap = &self.style.font.charmap;
+        if !self.format.fixed_width {
+            return style::advance_width(charmap, "0");
+        }
+
+        let digits = self.format.radix.digits();
+        let mut cell_width = Scalar::ZERO;
+        for index in 0..digits.len() {
+            let advance_width = style::advance_width(charmap, &digits[index..index + 1]);
+            if advance_width > cell_width {
+                cell_width = advance_width;
+            }
+        }
+
+        cell_width
+    }
+
+    /// Visits the pieces of the formatted number, starting at the specified position along the
+    /// _x_-axis, with the text to draw, if any, its position, and the end of the space that it
+    /// takes up, returning the position after the last piece.
+    fn layout<E, F>(&self, formatted: &Formatted, x: Scalar, mut visit: F) -> Result<Scalar, E>
+    where
+        F: FnMut(Option<&str>, Scalar, Scalar) -> Result<(), E>,
+    {
+        let format = &self.format;
+        let charmap = &self.style.font.charmap;
+        let cell_width = self.cell_width();
+        let mut x = x;
+        let prefix_width = style::advance_width(charmap, format.prefix);
+        visit(Some(format.prefix), x, x + prefix_width)?;
+        x += prefix_width;
+
+        let mut blank_width = Scalar::ZERO;
+        for _ in 0..formatted.blank_digits {
+            blank_width += cell_width;
+        }
+
+        if let Some(separator) = format.separator {
+            let mut buffer = [0; 4];
+            let separator_width = style::advance_width(charmap, separator.encode_utf8(&mut buffer));
+            for _ in 0..formatted.blank_separators {
+                blank_width += separator_width;
+            }
+        }
+
+        if blank_width > Scalar::ZERO {
+            visit(None, x, x + blank_width)?;
+            x += blank_width;
+        }
+
+        let text = formatted.as_str();
+        if format.fixed_width {
+            for (index, char) in text.char_indices() {
+                let text = &text[index..index + char.len_utf8()];
+                let advance_width = style::advance_width(charmap, text);
+                if format.radix.is_digit(char) {
+                    let offset = scalar::half(cell_width - advance_width);
+                    visit(Some(text), x + offset, x + cell_width)?;
+                    x += cell_width;
+                } else {
+                    visit(Some(text), x, x + advance_width)?;
+                    x += advance_width;
+                }
+            }
+        } else {
+            let text_width = style::advance_width(charmap, text);
+            visit(Some(text), x, x + text_width)?;
+            x += text_width;
+        }
+
+        let suffix_width = style::advance_width(charmap, format.suffix);
+        visit(Some(format.suffix), x, x + suffix_width)?;
+        x += suffix_width;
+
+        Ok(x)
+    }
+}
+
+impl NumberFormat<'_> {
+    /// Creates a new number format for decimal integers, without padding and separators.
+    pub const fn new() -> Self {
+        Self {
+            radix: Radix::Decimal,
+            decimals: 0,
+            min_digits: 1,
+            padding: Padding::Zeros,
+            sign: Sign::Negative,
+            separator: None,
+            decimal_point: '.',
+            prefix: "",
+            suffix: "",
+            fixed_width: false,
+        }
+    }
+}
+
+impl Default for NumberFormat<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Radix {
+    /// Returns the digits of this radix, in order of their values.
+    const fn digits(self) -> &'static str {
+        match self {
+            Self::Decimal => "0123456789",
+            Self::LowerHex => "0123456789abcdef",
+            Self::UpperHex => "0123456789ABCDEF",
+        }
+    }
+
+    /// Returns the number of digits in a group, for placing separators.
+    const fn group_size(self) -> u32 {
+        match self {
+            Self::Decimal => 3,
+            Self::LowerHex | Self::UpperHex => 4,
+        }
+    }
+
+    /// Returns whether the specified character is one of the digits of this radix.
+    fn is_digit(self, char: char) -> bool {
+        self.digits().contains(char)
+    }
+}
+
+impl Formatted {
+    /// Formats the specified value according to the specified number format.
+    fn new(value: i64, format: &NumberFormat) -> Self {
+        let mut formatted = Self {
+            bytes: [0; CAPACITY],
+            start: CAPACITY,
+            blank_digits: 0,
+            blank_separators: 0,
+        };
+
+        let digits = format.radix.digits().as_bytes();
+        let radix = digits.len() as u64;
+        let group_size = format.radix.group_size();
+        let decimals = match format.radix {
+            Radix::Decimal => u32::from(format.decimals).min(MAX_DIGITS),
+            Radix::LowerHex | Radix::UpperHex => 0,
+        };
+        let min_digits = u32::from(format.min_digits).min(MAX_DIGITS);
+        let mut magnitude = value.unsigned_abs();
+        for _ in 0..decimals {
+            formatted.push(char::from(digits[(magnitude % radix) as usize]));
+            magnitude /= radix;
+        }
+
+        if decimals > 0 {
+            formatted.push(format.decimal_point);
+        }
+
+        let mut count = 0;
+        while count == 0
+            || magnitude > 0
+            || (format.padding == Padding::Zeros && count < min_digits)
+        {
+            let is_group_start = count > 0 && count % group_size == 0;
+            if let Some(separator) = format.separator.filter(|_| is_group_start) {
+                formatted.push(separator);
+            }
+
+            formatted.push(char::from(digits[(magnitude % radix) as usize]));
+            magnitude /= radix;
+            count += 1;
+        }
+
+        if value < 0 {
+            formatted.push('-');
+        } else if format.sign == Sign::Always {
+            formatted.push('+');
+        }
+
+        if format.padding == Padding::Blanks {
+            while count < min_digits {
+                if format.separator.is_some() && count % group_size == 0 {
+                    formatted.blank_separators += 1;
+                }
+
+                formatted.blank_digits += 1;
+                count += 1;
+            }
+        }
+
+        formatted
+    }
+
+    /// Writes the specified character in front of the characters written so far.
+    fn push(&mut self, char: char) {
+        let Some(start) = self.start.checked_sub(char.len_utf8()) else {
+            return;
+        };
+
+        char.encode_utf8(&mut self.bytes[start..]);
+        self.start = start;
+    }
+
+    /// Returns the characters written so far.
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[self.start..]).unwrap_or_default()
+    }
+}
+
+macro_rules! impl_drawable {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<T, const N: usize> Drawable for Number<'_, '_, '_, T, $color_type, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+                type Output = Point;
+
+                fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let style = &self.style;
+                    let formatted = Formatted::new(self.value, &self.format);
+                    let x = Scalar::from_int(self.position.x);
+                    let Ok(right) = self.layout::<Infallible, _>(&formatted, x, |_, _, _| Ok(()));
+
+                    let x = match self.alignment {
+                        Alignment::Left => x,
+                        Alignment::Center => x - scalar::half(right - x),
+                        Alignment::Right => x - (right - x),
+                    };
+
+                    let y = self.position.y;
+                    let mut left = scalar::floor(x);
+                    let colormap: Colormap<T, $array_length> = style.faded_colormap();
+                    self.layout(&formatted, x, |text, x, end| {
+                        if let Some(text) = text.filter(|text| !text.is_empty()) {
+                            let x_floor = scalar::floor(x);
+                            if x_floor > left {
+                                let width = x_floor.abs_diff(left);
+                                let position = Point::new(left, y);
+                                left = style.draw_whitespace(width, position, self.baseline, target)?.x;
+                            }
+
+                            let colormap = colormap.clone();
+                            let mut line_state =
+                                LineState::with_scalar_x(style, text, x, y, self.baseline, colormap);
+                            line_state.draw_images(style, None, target)?;
+                            left = line_state.finish(style, target)?.x;
+                        }
+
+                        let end = scalar::floor(end);
+                        if end > left {
+                            let width = end.abs_diff(left);
+                            let position = Point::new(left, y);
+                            left = style.draw_whitespace(width, position, self.baseline, target)?.x;
+                        }
+
+                        Ok(())
+                    })?;
+
+                    Ok(Point::new(left, y))
+                }
+            }
+        )*
+    }
+}
+
+impl_drawable! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_formatted {
+        (
+            $(
+                $fn_ident:ident,
+                $value:expr,
+                $format:expr,
+                $expected:expr,
+                $blank_digits:expr,
+                $blank_separators:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = Formatted::new($value, &$format);
+                    assert_eq!(result.as_str(), $expected);
+                    assert_eq!(result.blank_digits, $blank_digits);
+                    assert_eq!(result.blank_separators, $blank_separators);
+                }
+            )*
+        }
+    }
+
+    const DEFAULT: NumberFormat = NumberFormat::new();
+
+    test_formatted! {
+        formatted_zero, 0, DEFAULT, "0", 0, 0,
+        formatted_integer, 1234, DEFAULT, "1234", 0, 0,
+        formatted_negative, -1234, DEFAULT, "-1234", 0, 0,
+        formatted_min, i64::MIN, DEFAULT, "-9223372036854775808", 0, 0,
+        formatted_sign_always, 42, NumberFormat { sign: Sign::Always, ..DEFAULT }, "+42", 0, 0,
+        formatted_sign_always_zero, 0, NumberFormat { sign: Sign::Always, ..DEFAULT }, "+0", 0, 0,
+        formatted_decimals, 1234, NumberFormat { decimals: 2, ..DEFAULT }, "12.34", 0, 0,
+        formatted_decimals_small, -5, NumberFormat { decimals: 2, ..DEFAULT }, "-0.05", 0, 0,
+        formatted_decimal_comma,
+        75,
+        NumberFormat { decimals: 1, decimal_point: ',', ..DEFAULT },
+        "7,5",
+        0,
+        0,
+        formatted_zeros, -42, NumberFormat { min_digits: 4, ..DEFAULT }, "-0042", 0, 0,
+        formatted_blanks,
+        -42,
+        NumberFormat { min_digits: 4, padding: Padding::Blanks, ..DEFAULT },
+        "-42",
+        2,
+        0,
+        formatted_separator,
+        1234567,
+        NumberFormat { separator: Some(','), ..DEFAULT },
+        "1,234,567",
+        0,
+        0,
+        formatted_separator_group,
+        123,
+        NumberFormat { separator: Some(','), ..DEFAULT },
+        "123",
+        0,
+        0,
+        formatted_separator_zeros,
+        1234,
+        NumberFormat { separator: Some('\u{2009}'), min_digits: 7, ..DEFAULT },
+        "0\u{2009}001\u{2009}234",
+        0,
+        0,
+        formatted_separator_blanks,
+        123,
+        NumberFormat { separator: Some(','), min_digits: 7, padding: Padding::Blanks, ..DEFAULT },
+        "123",
+        4,
+        2,
+        formatted_upper_hex,
+        0xAB,
+        NumberFormat { radix: Radix::UpperHex, min_digits: 4, prefix: "0x", ..DEFAULT },
+        "00AB",
+        0,
+        0,
+        formatted_lower_hex_separator,
+        0xDEADBEEF,
+        NumberFormat { radix: Radix::LowerHex, separator: Some('_'), decimals: 2, ..DEFAULT },
+        "dead_beef",
+        0,
+        0,
+        formatted_max_digits,
+        1,
+        NumberFormat { min_digits: u8::MAX, decimals: u8::MAX, ..DEFAULT },
+        "00000000000000000000000000000000.00000000000000000000000000000001",
+        0,
+        0,
+    }
+}
@@ -0,0 +1,281 @@
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Dimensions, Point, Size};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{PixelColor, Rgb888};
+use embedded_graphics::primitives::{Primitive, PrimitiveStyle, Rectangle};
+use embedded_graphics::text::Baseline;
+
+use crate::font::BitmapFont;
+use crate::metrics::DecorationDimensions;
+use crate::scalar::{self, Scalar};
+use crate::style;
+
+/// Metrics overlay, a debug drawable that shows the metrics of a bitmap font for a text run, as
+/// well as where the glyph clusters and the glyph images of the text run are placed.
+///
+/// The text itself is not drawn; the overlay is meant to be drawn on top of a text run that has
+/// been drawn at the same position and using the same baseline. Each of the metrics is drawn as a
+/// horizontal line on the row of pixels directly below it, except for the bottom of the line,
+/// which is drawn on the row directly above it, and the underline and strikethrough are drawn as
+/// filled bands, all of them spanning the text run. Every glyph cluster has the outline of its
+/// advance box drawn, from the top to the bottom of the line, and every glyph image the outline of
+/// its bounding box. Anything that has no color is not drawn.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MetricsOverlay<'a, 'b, 't, T, C, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// The text.
+    pub text: &'t str,
+    /// The position.
+    pub position: Point,
+    /// The baseline.
+    pub baseline: Baseline,
+    /// The colors.
+    pub colors: OverlayColors<T>,
+    /// The bitmap font.
+    pub font: &'b BitmapFont<'a, C, N>,
+}
+
+/// Colors for a [`MetricsOverlay`], one for each of the lines, boxes, and bands that it draws.
+///
+/// The default colors are all different from each other, and from black and white.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OverlayColors<T: PixelColor> {
+    /// The color of the line at the top of the line.
+    pub top: Option<T>,
+    /// The color of the line at the typographic ascender.
+    pub ascender: Option<T>,
+    /// The color of the line at the top of capital letters.
+    pub cap_height: Option<T>,
+    /// The color of the line at the top of the small letter _x_.
+    pub x_height: Option<T>,
+    /// The color of the line at the baseline.
+    pub baseline: Option<T>,
+    /// The color of the line at the typographic descender.
+    pub descender: Option<T>,
+    /// The color of the line at the bottom of the line.
+    pub bottom: Option<T>,
+    /// The color of the outlines of the advance boxes of glyph clusters.
+    pub advance: Option<T>,
+    /// The color of the outlines of the bounding boxes of glyph images.
+    pub image: Option<T>,
+    /// The color of the underline band.
+    pub underline: Option<T>,
+    /// The color of the strikethrough band.
+    pub strikethrough: Option<T>,
+}
+
+impl<'a, 'b, 't, T, C, const N: usize> MetricsOverlay<'a, 'b, 't, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new metrics overlay for the specified text at the specified position, using the
+    /// alphabetic baseline, with the specified colors and bitmap font.
+    pub const fn new(
+        text: &'t str,
+        position: Point,
+        colors: OverlayColors<T>,
+        font: &'b BitmapFont<'a, C, N>,
+    ) -> Self {
+        Self {
+            text,
+            position,
+            baseline: Baseline::Alphabetic,
+            colors,
+            font,
+        }
+    }
+
+    /// Returns the position of the baseline along the _y_-axis.
+    fn y(&self) -> i32 {
+        let y_offset = self.font.metrics.y_offset(self.baseline);
+
+        self.position.y.saturating_add(y_offset)
+    }
+
+    /// Returns the leftmost and the rightmost column that is covered by the glyph images, the line
+    /// piece up to the position of the next character included.
+    fn columns(&self) -> (i32, i32) {
+        let mut left = self.position.x;
+        let mut right = self.position.x;
+        let x = Scalar::from_int(self.position.x);
+        let y = Scalar::from_int(self.y());
+        let mut images = style::images_of_chars(&self.font.charmap, self.text, x, y);
+        for (image, _) in images.by_ref() {
+            let image_box = image.bounding_box();
+            if let Some(bottom_right) = image_box.bottom_right() {
+                left = left.min(image_box.top_left.x);
+                right = right.max(bottom_right.x.saturating_add(1));
+            }
+        }
+
+        (left, right.max(scalar::floor(images.x)))
+    }
+
+    /// Draws a horizontal line between the specified columns on the row of pixels directly below
+    /// the specified metric.
+    fn draw_metric<D>(
+        &self,
+        metric: Scalar,
+        color: Option<T>,
+        (left, right): (i32, i32),
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = T>,
+    {
+        let y = self.y();
+        let bottom = y.saturating_sub(self.font.metrics.y_offset(Baseline::Bottom));
+        let row = y.saturating_sub(scalar::div_ceil(metric, 1));
+        let row = row.min(bottom.saturating_sub(1));
+
+        self.draw_band(row, 1, color, (left, right), target)
+    }
+
+    /// Draws a filled band of the specified height between the specified columns.
+    fn draw_band<D>(
+        &self,
+        top: i32,
+        height: u32,
+        color: Option<T>,
+        (left, right): (i32, i32),
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = T>,
+    {
+        let Some(color) = color else {
+            return Ok(());
+        };
+
+        let width = right.saturating_sub(left).try_into().unwrap_or_default();
+        let band = Rectangle::new(Point::new(left, top), Size::new(width, height));
+
+        target.fill_solid(&band, color)
+    }
+
+    /// Draws a filled band for the specified decoration between the specified columns.
+    fn draw_decoration<D>(
+        &self,
+        dimensions: DecorationDimensions,
+        color: Option<T>,
+        columns: (i32, i32),
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = T>,
+    {
+        let top = self.y().saturating_sub(dimensions.y_offset());
+        let height = dimensions.stroke_width();
+
+        self.draw_band(top, height, color, columns, target)
+    }
+}
+
+impl<'a, T, C, const N: usize> Drawable for MetricsOverlay<'a, '_, '_, T, C, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    type Color = T;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let metrics = &self.font.metrics;
+        let colors = &self.colors;
+        let columns = self.columns();
+        let y = self.y();
+        let top = y.saturating_sub(metrics.y_offset(Baseline::Top));
+        let height = metrics.line_height();
+
+        self.draw_decoration(self.font.underline, colors.underline, columns, target)?;
+        self.draw_decoration(
+            self.font.strikethrough,
+            colors.strikethrough,
+            columns,
+            target,
+        )?;
+
+        let lines = [
+            (metrics.top, colors.top),
+            (metrics.ascender, colors.ascender),
+            (metrics.cap_height, colors.cap_height),
+            (metrics.x_height, colors.x_height),
+            (metrics.baseline, colors.baseline),
+            (metrics.descender, colors.descender),
+            (metrics.bottom, colors.bottom),
+        ];
+
+        for (metric, color) in lines {
+            self.draw_metric(metric, color, columns, target)?;
+        }
+
+        let x = Scalar::from_int(self.position.x);
+        let mut images =
+            style::images_of_chars(&self.font.charmap, self.text, x, Scalar::from_int(y));
+        let mut cluster_left = None;
+        loop {
+            let next = images.next();
+            let is_next_cluster = next.as_ref().is_none_or(|(_, is_overlay)| !is_overlay);
+            if is_next_cluster {
+                let cluster_right = scalar::floor(images.x);
+                if let (Some(left), Some(color)) =
+                    (cluster_left.replace(cluster_right), colors.advance)
+                {
+                    let width = cluster_right
+                        .saturating_sub(left)
+                        .try_into()
+                        .unwrap_or_default();
+                    let advance_box =
+                        Rectangle::new(Point::new(left, top), Size::new(width, height));
+                    let style = PrimitiveStyle::with_stroke(color, 1);
+                    advance_box.into_styled(style).draw(target)?;
+                }
+            }
+
+            let Some((image, _)) = next else {
+                break;
+            };
+
+            if let Some(color) = colors.image {
+                let style = PrimitiveStyle::with_stroke(color, 1);
+                image.bounding_box().into_styled(style).draw(target)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: PixelColor + From<Rgb888>> Default for OverlayColors<T> {
+    fn default() -> Self {
+        let color = |r, g, b| Some(Rgb888::new(r, g, b).into());
+
+        Self {
+            top: color(255, 0, 255),
+            ascender: color(255, 128, 0),
+            cap_height: color(255, 255, 0),
+            x_height: color(0, 255, 0),
+            baseline: color(255, 0, 0),
+            descender: color(0, 255, 255),
+            bottom: color(128, 0, 255),
+            advance: color(0, 128, 255),
+            image: color(128, 128, 128),
+            underline: color(0, 0, 255),
+            strikethrough: color(255, 0, 128),
+        }
+    }
+}
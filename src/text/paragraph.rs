@@ -0,0 +1,400 @@
+use core::ops::Range;
+
+use embedded_graphics::Drawable;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{Dimensions, Point};
+use embedded_graphics::iterator::raw::RawDataSlice;
+use embedded_graphics::pixelcolor::raw::BigEndian;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray2, Gray4, Gray8, PixelColor};
+use embedded_graphics::primitives::{Circle, ContainsPoint, Ellipse, Rectangle, RoundedRectangle};
+use embedded_graphics::text::{Alignment, Baseline};
+
+use crate::color::{Blend, Colormap, Invert, Linear, Opacity, Screen, WeightedAvg};
+use crate::scalar::{self, Scalar};
+use crate::style::{self, BitmapFontStyle, LineState};
+
+/// Shape that provides the columns available to every line of a paragraph, for text that wraps
+/// within non-rectangular areas.
+///
+/// This trait is implemented for the primitives [`Rectangle`], [`RoundedRectangle`], [`Circle`],
+/// and [`Ellipse`]; for the curved ones, each line gets the columns that are inside of the shape
+/// in all of the rows that the line covers.
+pub trait LineSpans: Dimensions {
+    /// Returns the columns that are available to a line covering the specified rows, or `None` if
+    /// there are none.
+    fn line_span(&self, rows: Range<i32>) -> Option<Range<i32>>;
+}
+
+/// Paragraph, a text that is broken into lines at spaces and at line feeds, with every line
+/// wrapped to the columns that the shape of the paragraph makes available at its position.
+///
+/// Lines are placed from the top of the shape downwards, one line height apart, measured using
+/// the advance widths of the glyphs in the bitmap font. Lines that are too narrow to fit the next
+/// word are left empty, unless the line below is no wider, in which case the word is broken at a
+/// character boundary instead. Drawing the paragraph returns the rest of the text that does not
+/// fit in the shape.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Paragraph<'a, 'b, 't, T, C, S, const N: usize>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    S: LineSpans,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// The text.
+    pub text: &'t str,
+    /// The shape.
+    pub shape: S,
+    /// The horizontal alignment of every line within its columns.
+    pub alignment: Alignment,
+    /// The style.
+    pub style: BitmapFontStyle<'a, 'b, T, C, N>,
+}
+
+impl<'a, 'b, 't, T, C, S, const N: usize> Paragraph<'a, 'b, 't, T, C, S, N>
+where
+    C: PixelColor + From<C::Raw>,
+    T: PixelColor + Default + Invert + Screen + WeightedAvg,
+    S: LineSpans,
+    RawDataSlice<'a, C::Raw, BigEndian>: IntoIterator<Item = C::Raw>,
+{
+    /// Creates a new paragraph with the specified text, shape, and style, with every line aligned
+    /// to the left.
+    pub const fn new(text: &'t str, shape: S, style: BitmapFontStyle<'a, 'b, T, C, N>) -> Self {
+        Self {
+            text,
+            shape,
+            alignment: Alignment::Left,
+            style,
+        }
+    }
+
+    /// Returns the advance width of the specified text run in pixels.
+    fn text_width(&self, text: &str) -> Scalar {
+        style::advance_width(&self.style.font.charmap, text)
+    }
+
+    /// Returns the width of the columns that are available to the line at the specified position,
+    /// or zero if there are none.
+    fn span_width(&self, top: i32, line_height: i32) -> u32 {
+        self.shape
+            .line_span(top..top.saturating_add(line_height))
+            .map_or(0, |span| span.end.abs_diff(span.start))
+    }
+
+    /// Returns the lines of this paragraph, each with its text and the position of its top left
+    /// corner, and the rest of the text that does not fit in the shape.
+    pub(super) fn lay_out(&self, mut visit: impl FnMut(&'t str, Scalar, i32)) -> &'t str {
+        let line_height = self.style.font.metrics.line_height();
+        let line_height = line_height.try_into().unwrap_or(i32::MAX);
+        let bounding_box = self.shape.bounding_box();
+        let bottom = bounding_box
+            .top_left
+            .y
+            .saturating_add_unsigned(bounding_box.size.height);
+
+        let mut rest = self.text;
+        let mut top = bounding_box.top_left.y;
+        while !rest.is_empty() && line_height > 0 && top.saturating_add(line_height) <= bottom {
+            let next_top = top.saturating_add(line_height);
+            let Some(span) = self.shape.line_span(top..next_top) else {
+                top = next_top;
+                continue;
+            };
+
+            let width = span.end.abs_diff(span.start);
+            let overflow = self.span_width(next_top, line_height) <= width;
+            let width = Scalar::from_int(width.try_into().unwrap_or(i32::MAX));
+            let measure = |text: &str| self.text_width(text);
+            if let Some((line, next_rest)) = break_line(rest, width, overflow, measure) {
+                let left = Scalar::from_int(span.start);
+                let x = match self.alignment {
+                    Alignment::Left => left,
+                    Alignment::Center => left + scalar::half(width - self.text_width(line)),
+                    Alignment::Right => left + width - self.text_width(line),
+                };
+
+                visit(line, x, top);
+                rest = next_rest;
+            }
+
+            top = next_top;
+        }
+
+        rest
+    }
+}
+
+/// Breaks off the first line of the specified text, returning the text of the line and the rest
+/// of the text, or `None` if not even the first word fits in the specified width.
+///
+/// Lines end at line feeds and at spaces, with the spaces at the end of lines dropped. If the
+/// overflow flag is set, a word that is too long is broken at a character boundary instead, with
+/// at least one character on the line.
+fn break_line(
+    text: &str,
+    width: Scalar,
+    overflow: bool,
+    measure: impl Fn(&str) -> Scalar,
+) -> Option<(&str, &str)> {
+    let (paragraph, after_paragraph) = match text.split_once('\n') {
+        Some((paragraph, rest)) => (paragraph.strip_suffix('\r').unwrap_or(paragraph), rest),
+        None => (text, ""),
+    };
+
+    let whole = paragraph.trim_end_matches(' ');
+    if measure(whole) <= width {
+        return Some((whole, after_paragraph));
+    }
+
+    let mut line = None;
+    let word_ends = paragraph.match_indices(' ').map(|(index, _)| index);
+    for end in word_ends {
+        let candidate = paragraph[..end].trim_end_matches(' ');
+        if candidate.is_empty() || line == Some(candidate) {
+            continue;
+        }
+
+        if measure(candidate) > width {
+            break;
+        }
+
+        line = Some(candidate);
+    }
+
+    let line = match line {
+        Some(line) => line,
+        None if overflow => {
+            let mut char_ends = whole.char_indices().map(|(index, c)| index + c.len_utf8());
+            let first_end = char_ends.next().unwrap_or_default();
+            let end = char_ends
+                .take_while(|&end| measure(&whole[..end]) <= width)
+                .last()
+                .unwrap_or(first_end);
+
+            &whole[..end]
+        }
+        None => return None,
+    };
+
+    if paragraph[line.len()..].trim_start_matches(' ').is_empty() {
+        return Some((line, after_paragraph));
+    }
+
+    Some((line, text[line.len()..].trim_start_matches(' ')))
+}
+
+/// Returns the columns that are inside of the specified shape in the specified row, which is
+/// expected to be convex, searching from the center column of its bounding box outwards.
+fn row_span<S>(shape: &S, y: i32) -> Option<Range<i32>>
+where
+    S: ContainsPoint + Dimensions,
+{
+    let bounding_box = shape.bounding_box();
+    let center = bounding_box.center().x;
+    if !shape.contains(Point::new(center, y)) {
+        return None;
+    }
+
+    let search = |mut inside: i32, mut outside: i32| {
+        while inside.abs_diff(outside) > 1 {
+            let middle = inside + (outside - inside) / 2;
+            if shape.contains(Point::new(middle, y)) {
+                inside = middle;
+            } else {
+                outside = middle;
+            }
+        }
+
+        inside
+    };
+
+    let left = bounding_box.top_left.x.saturating_sub(1);
+    let right = left
+        .saturating_add_unsigned(bounding_box.size.width)
+        .saturating_add(1);
+
+    Some(search(center, left)..search(center, right).saturating_add(1))
+}
+
+/// Returns the columns that are inside of the specified shape in all of the specified rows, which,
+/// for convex shapes, are the columns that are inside of the shape in both the top and the bottom
+/// row.
+fn convex_line_span<S>(shape: &S, rows: Range<i32>) -> Option<Range<i32>>
+where
+    S: ContainsPoint + Dimensions,
+{
+    let top = row_span(shape, rows.start)?;
+    let bottom = row_span(shape, rows.end.checked_sub(1)?)?;
+    let span = top.start.max(bottom.start)..top.end.min(bottom.end);
+
+    (!span.is_empty()).then_some(span)
+}
+
+impl LineSpans for Rectangle {
+    fn line_span(&self, rows: Range<i32>) -> Option<Range<i32>> {
+        let top = self.top_left.y;
+        let bottom = top.saturating_add_unsigned(self.size.height);
+        let left = self.top_left.x;
+        let right = left.saturating_add_unsigned(self.size.width);
+        let is_inside = rows.start >= top && rows.end <= bottom && !rows.is_empty();
+
+        (is_inside && left < right).then_some(left..right)
+    }
+}
+
+macro_rules! impl_line_spans {
+    (
+        $(
+            $shape_type:ty,
+        )*
+    ) => {
+        $(
+            impl LineSpans for $shape_type {
+                fn line_span(&self, rows: Range<i32>) -> Option<Range<i32>> {
+                    convex_line_span(self, rows)
+                }
+            }
+        )*
+    }
+}
+
+impl_line_spans! {
+    RoundedRectangle,
+    Circle,
+    Ellipse,
+}
+
+macro_rules! impl_drawable {
+    (
+        $(
+            $color_type:ty, $array_length:literal,
+        )*
+    ) => {
+        $(
+            impl<'t, T, S, const N: usize> Drawable for Paragraph<'_, '_, 't, T, $color_type, S, N>
+            where
+                T: PixelColor + Default + Invert + Screen + WeightedAvg + Opacity + Blend,
+                S: LineSpans,
+                Colormap<T, $array_length>: Linear<T>,
+            {
+                type Color = T;
+                type Output = &'t str;
+
+                fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+                where
+                    D: DrawTarget<Color = Self::Color>,
+                {
+                    let style = &self.style;
+                    let mut result = Ok(());
+                    let colormap: Colormap<T, $array_length> = style.faded_colormap();
+                    style.discard_carryover();
+                    let rest = self.lay_out(|line, x, y| {
+                        if result.is_err() || line.is_empty() {
+                            return;
+                        }
+
+                        let colormap = colormap.clone();
+                        let mut line_state =
+                            LineState::with_scalar_x(style, line, x, y, Baseline::Top, colormap);
+
+                        result = line_state
+                            .draw_images(style, None, target)
+                            .and_then(|_| line_state.finish(style, target))
+                            .map(drop);
+
+                        style.discard_carryover();
+                    });
+
+                    result.map(|_| rest)
+                }
+            }
+        )*
+    }
+}
+
+impl_drawable! {
+    BinaryColor, 2,
+    Gray2, 4,
+    Gray4, 16,
+    Gray8, 256,
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::geometry::Size;
+
+    use super::*;
+
+    macro_rules! test_break_line {
+        (
+            $(
+                $fn_ident:ident, $text:expr, $width:expr, $overflow:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let measure = |text: &str| Scalar::from_int(text.chars().count() as i32);
+                    let result = break_line($text, Scalar::from_int($width), $overflow, measure);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    macro_rules! test_line_span {
+        (
+            $(
+                $fn_ident:ident, $shape:expr, $rows:expr, $expected:expr,
+            )*
+        ) => {
+            $(
+                #[test]
+                fn $fn_ident() {
+                    let result = $shape.line_span($rows);
+                    assert_eq!(result, $expected);
+                }
+            )*
+        }
+    }
+
+    test_break_line! {
+        break_line_fits, "one two", 7, false, Some(("one two", "")),
+        break_line_at_space, "one two three", 9, false, Some(("one two", "three")),
+        break_line_multiple_spaces, "one  two", 5, false, Some(("one", "two")),
+        break_line_trailing_spaces, "one   ", 3, false, Some(("one", "")),
+        break_line_line_feed, "one\ntwo", 9, false, Some(("one", "two")),
+        break_line_carriage_return, "one\r\ntwo", 9, false, Some(("one", "two")),
+        break_line_empty_line, "\ntwo", 9, false, Some(("", "two")),
+        break_line_before_line_feed, "one two\nthree", 5, false, Some(("one", "two\nthree")),
+        break_line_too_narrow, "three", 4, false, None,
+        break_line_overflow, "three", 4, true, Some(("thre", "e")),
+        break_line_overflow_first_char, "three", 0, true, Some(("t", "hree")),
+        break_line_overflow_prefers_space, "one three", 4, true, Some(("one", "three")),
+    }
+
+    test_line_span! {
+        line_span_rectangle, Rectangle::new(Point::new(2, 3), Size::new(10, 8)), 3..11, Some(2..12),
+        line_span_rectangle_outside, Rectangle::new(Point::new(2, 3), Size::new(10, 8)), 2..10, None,
+        line_span_circle_middle, Circle::new(Point::new(0, 0), 20), 8..12, Some(0..20),
+        line_span_circle_top, Circle::new(Point::new(0, 0), 20), 0..4, Some(7..13),
+        line_span_circle_bottom, Circle::new(Point::new(0, 0), 20), 16..20, Some(7..13),
+        line_span_circle_outside, Circle::new(Point::new(0, 0), 20), 18..22, None,
+        line_span_rounded_rectangle_corner,
+        RoundedRectangle::with_equal_corners(
+            Rectangle::new(Point::new(0, 0), Size::new(40, 20)),
+            Size::new(8, 8),
+        ),
+        0..4,
+        Some(5..35),
+        line_span_rounded_rectangle_middle,
+        RoundedRectangle::with_equal_corners(
+            Rectangle::new(Point::new(0, 0), Size::new(40, 20)),
+            Size::new(8, 8),
+        ),
+        8..12,
+        Some(0..40),
+    }
+}
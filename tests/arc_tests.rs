@@ -0,0 +1,113 @@
+mod common;
+
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+use mplusfonts::text::{ArcBaseline, ArcDirection, ArcText};
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR, new_display};
+
+const CENTER: Point = Point::new(32, 32);
+
+/// Returns the distance from the center and the angle in radians of every pixel that is drawn,
+/// weighted by how close its color is to the text color.
+fn polar_weights(display: &MockDisplay<Rgb888>) -> Vec<(f32, f32, f32)> {
+    let mut weights = Vec::new();
+    for y in 0..64 {
+        for x in 0..64 {
+            let Some(color) = display.get_pixel(Point::new(x, y)) else {
+                continue;
+            };
+
+            let weight = f32::from(color.r() - BACKGROUND_COLOR.r()) / 255.0;
+            let dx = x as f32 + 0.5 - CENTER.x as f32;
+            let dy = y as f32 + 0.5 - CENTER.y as f32;
+            weights.push((dx.hypot(dy), dy.atan2(dx), weight));
+        }
+    }
+
+    weights
+}
+
+/// Returns the weighted mean distance from the center.
+fn mean_distance(weights: &[(f32, f32, f32)]) -> f32 {
+    let total: f32 = weights.iter().map(|&(_, _, weight)| weight).sum();
+
+    weights
+        .iter()
+        .map(|&(distance, _, weight)| distance * weight)
+        .sum::<f32>()
+        / total
+}
+
+macro_rules! test_arc_text_draw {
+    (
+        $(
+            $fn_ident:ident, $direction:expr, $baseline:expr, $radius:expr, $band:expr,
+            $is_clockwise:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 12, false, 4, 4, '!'..='~');
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color(BACKGROUND_COLOR)
+                    .build();
+
+                let line_height = bitmap_font.metrics.line_height() as f32;
+                let draw = |text| {
+                    let start_angle = Angle::zero();
+                    let mut arc_text = ArcText::new(text, CENTER, $radius, start_angle, style.clone());
+                    arc_text.direction = $direction;
+                    arc_text.baseline = $baseline;
+
+                    let mut display = new_display();
+                    let end_angle = arc_text.draw(&mut display).unwrap();
+
+                    (display, end_angle)
+                };
+
+                let (display, end_angle) = draw("^_^");
+                let weights = polar_weights(&display);
+                assert!(!weights.is_empty());
+
+                let radius = $radius as f32;
+                let (near, far): (f32, f32) = $band;
+                let (near, far) = (radius + near * line_height, radius + far * line_height);
+                for &(distance, angle, _) in &weights {
+                    assert!(
+                        (near - 1.5..=far + 1.5).contains(&distance),
+                        "distance {distance} outside of {near}..={far}"
+                    );
+                    match $is_clockwise {
+                        true => assert!((-0.1..=end_angle.to_radians() + 0.1).contains(&angle)),
+                        false => assert!((end_angle.to_radians() - 0.1..=0.1).contains(&angle)),
+                    }
+                }
+
+                let end_angle = end_angle.to_radians();
+                assert_eq!(end_angle > 0.0, $is_clockwise, "end angle: {end_angle}");
+
+                let top = mean_distance(&polar_weights(&draw("^").0));
+                let bottom = mean_distance(&polar_weights(&draw("_").0));
+                assert_eq!(top > bottom, $is_clockwise, "top: {top}, bottom: {bottom}");
+            }
+        )*
+    }
+}
+
+test_arc_text_draw! {
+    arc_text_draw_clockwise_inner,
+    ArcDirection::Clockwise, ArcBaseline::Inner, 12, (0.0, 1.0), true,
+    arc_text_draw_clockwise_outer,
+    ArcDirection::Clockwise, ArcBaseline::Outer, 28, (-1.0, 0.0), true,
+    arc_text_draw_counter_clockwise_inner,
+    ArcDirection::CounterClockwise, ArcBaseline::Inner, 12, (0.0, 1.0), false,
+    arc_text_draw_counter_clockwise_outer,
+    ArcDirection::CounterClockwise, ArcBaseline::Outer, 28, (-1.0, 0.0), false,
+}
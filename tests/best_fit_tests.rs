@@ -0,0 +1,137 @@
+mod common;
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::renderer::TextRenderer;
+use embedded_graphics::text::{Alignment, Baseline};
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+use mplusfonts::text::{BestFit, Paragraph};
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR, new_display};
+
+/// Returns whether the specified rectangle contains the other rectangle.
+fn contains(rectangle: &Rectangle, other: &Rectangle) -> bool {
+    rectangle.intersection(other) == *other
+}
+
+macro_rules! test_best_fit {
+    (
+        $(
+            $fn_ident:ident, $text:expr, $bounding_box:expr, $alignment:expr, $wrap:expr,
+            $expected:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let fonts = [
+                    mplus!(1, 500, 24, false, 1, 4, ' '..='~'),
+                    mplus!(1, 500, 16, false, 1, 4, ' '..='~'),
+                    mplus!(1, 500, 11, false, 1, 4, ' '..='~'),
+                ];
+                let styles = fonts.each_ref().map(|font| {
+                    BitmapFontStyleBuilder::new()
+                        .font(font)
+                        .text_color(TEXT_COLOR)
+                        .background_color(BACKGROUND_COLOR)
+                        .build()
+                });
+
+                let bounding_box = $bounding_box;
+                let mut best_fit = BestFit::new($text, bounding_box, &styles);
+                best_fit.alignment = $alignment;
+                best_fit.wrap = $wrap;
+
+                let mut display = new_display();
+                let result = best_fit.draw(&mut display).unwrap();
+
+                let expected: Option<usize> = $expected;
+                assert_eq!(result, expected);
+                assert_eq!(best_fit.position(), expected);
+
+                let mut expected_display = new_display();
+                let Some(index) = expected else {
+                    display.assert_eq(&expected_display);
+
+                    return;
+                };
+
+                let style = &styles[index];
+                if $wrap {
+                    let mut paragraph = Paragraph::new($text, bounding_box, style.clone());
+                    paragraph.alignment = $alignment;
+                    paragraph.draw(&mut expected_display).unwrap();
+                } else {
+                    let line_height = style.line_height() as i32;
+                    for (row, line) in $text.split('\n').enumerate() {
+                        let mut position = bounding_box.top_left;
+                        position.y += line_height * row as i32;
+                        let metrics = style.measure_string(line, position, Baseline::Top);
+                        let width = metrics.bounding_box.size.width;
+                        let gap = (bounding_box.size.width - width) as i32;
+                        position.x += match $alignment {
+                            Alignment::Left => 0,
+                            Alignment::Center => gap / 2,
+                            Alignment::Right => gap,
+                        };
+
+                        let metrics = style.measure_string(line, position, Baseline::Top);
+                        assert!(contains(&bounding_box, &metrics.bounding_box));
+
+                        style
+                            .draw_string(line, position, Baseline::Top, &mut expected_display)
+                            .unwrap();
+                    }
+                }
+
+                display.assert_eq(&expected_display);
+                assert!(contains(&bounding_box, &display.affected_area()));
+
+                // The larger styles do not fit, either because of the width or the height.
+                for style in &styles[..index] {
+                    let line_height = style.line_height() as i32;
+                    let fits = $text.split('\n').enumerate().all(|(row, line)| {
+                        let mut position = bounding_box.top_left;
+                        position.y += line_height * row as i32;
+                        let metrics = style.measure_string(line, position, Baseline::Top);
+
+                        contains(&bounding_box, &metrics.bounding_box)
+                    });
+
+                    assert!($wrap || !fits);
+                }
+            }
+        )*
+    }
+}
+
+test_best_fit! {
+    best_fit_largest,
+    "Fit", Rectangle::new(Point::new(2, 3), Size::new(60, 40)), Alignment::Left, false,
+    Some(0),
+    best_fit_too_wide,
+    "Best fit", Rectangle::new(Point::new(2, 3), Size::new(60, 40)), Alignment::Left, false,
+    Some(1),
+    best_fit_too_tall,
+    "Fit\nFit", Rectangle::new(Point::new(2, 3), Size::new(60, 50)), Alignment::Left, false,
+    Some(1),
+    best_fit_smallest,
+    "Fitting\nin\nplace", Rectangle::new(Point::new(2, 3), Size::new(60, 50)), Alignment::Left, false,
+    Some(2),
+    best_fit_centered,
+    "Fit", Rectangle::new(Point::new(7, 1), Size::new(50, 40)), Alignment::Center, false,
+    Some(0),
+    best_fit_aligned_right,
+    "Best fit", Rectangle::new(Point::new(0, 0), Size::new(64, 20)), Alignment::Right, false,
+    Some(2),
+    best_fit_wrapped,
+    "Wrap to fit", Rectangle::new(Point::new(2, 3), Size::new(60, 60)), Alignment::Left, true,
+    Some(1),
+    best_fit_nothing_fits,
+    "Nothing fits", Rectangle::new(Point::new(2, 3), Size::new(20, 10)), Alignment::Left, false,
+    None,
+    best_fit_nothing_fits_wrapped,
+    "Nothing fits", Rectangle::new(Point::new(2, 3), Size::new(20, 10)), Alignment::Left, true,
+    None,
+}
@@ -0,0 +1,20 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::Rgb888;
+
+/// The text color of the styles in the tests.
+pub const TEXT_COLOR: Rgb888 = Rgb888::new(255, 210, 0);
+
+/// The background color of the styles in the tests.
+pub const BACKGROUND_COLOR: Rgb888 = Rgb888::new(0, 30, 60);
+
+/// Returns a mock display that allows pixels to be drawn more than once, which is the case for
+/// blended glyph images and for text that is drawn over again.
+pub fn new_display() -> MockDisplay<Rgb888> {
+    let mut display = MockDisplay::new();
+    display.set_allow_overdraw(true);
+    display
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::color::Screen;
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+use mplusfonts::text::Crossfade;
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR, new_display};
+
+macro_rules! test_crossfade {
+    (
+        $(
+            $fn_ident:ident, $from:expr, $to:expr, $ratio:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 16, false, 4, 4, kern('A'..='z', []));
+                let new_style = |opacity| {
+                    BitmapFontStyleBuilder::new()
+                        .font(&bitmap_font)
+                        .text_color(TEXT_COLOR)
+                        .background_color(BACKGROUND_COLOR)
+                        .opacity(opacity)
+                        .build()
+                };
+
+                let position = Point::new(2, 4);
+                let mut crossfade = Crossfade::new($from, $to, position, new_style(u8::MAX));
+                crossfade.baseline = Baseline::Top;
+                crossfade.ratio = $ratio;
+
+                let mut display = new_display();
+                crossfade.draw(&mut display).unwrap();
+
+                let draw_string = |text, opacity| {
+                    let style = new_style(opacity);
+                    let mut display = new_display();
+                    style
+                        .draw_string(text, position, Baseline::Top, &mut display)
+                        .unwrap();
+
+                    display
+                };
+
+                let from_display = draw_string($from, u8::MAX - $ratio);
+                let to_display = draw_string($to, $ratio);
+                for y in 0..64 {
+                    for x in 0..64 {
+                        let point = Point::new(x, y);
+                        let from_color = from_display.get_pixel(point);
+                        let to_color = to_display.get_pixel(point);
+                        let result = display.get_pixel(point);
+                        if from_color.is_none() && to_color.is_none() {
+                            if let Some(result) = result {
+                                assert_eq!(result, BACKGROUND_COLOR, "\n  x, y: {x:?}, {y:?}");
+                            }
+
+                            continue;
+                        }
+
+                        let from_color = from_color.unwrap_or(BACKGROUND_COLOR);
+                        let to_color = to_color.unwrap_or(BACKGROUND_COLOR);
+                        let expected = from_color.screen(to_color, BACKGROUND_COLOR, TEXT_COLOR);
+                        assert_eq!(result, Some(expected), "\n  x, y: {x:?}, {y:?}");
+                    }
+                }
+            }
+        )*
+    }
+}
+
+test_crossfade! {
+    crossfade_ratio_0, "Fade", "Wave", 0,
+    crossfade_ratio_128, "Fade", "Wave", 128,
+    crossfade_ratio_255, "Fade", "Wave", 255,
+    crossfade_ratio_0_overlapping, "AVAW", "Ty", 0,
+    crossfade_ratio_255_overlapping, "AVAW", "Ty", 255,
+}
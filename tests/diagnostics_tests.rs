@@ -0,0 +1,116 @@
+mod common;
+
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Baseline;
+use mplusfonts::diagnostics::{Coverage, TextReport};
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR};
+
+const POSITION: Point = Point::new(3, 2);
+
+macro_rules! test_check_string {
+    (
+        $(
+            $fn_ident:ident, $text:expr, $background_color:expr, $width:expr, $overdrawn:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 17, false, 4, 4, kern('A'..='z', ["g̈́"]));
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color($background_color)
+                    .build();
+
+                let mut display = MockDisplay::<Rgb888>::new();
+                display.set_allow_overdraw(true);
+                let area = Rectangle::new(Point::zero(), Size::new(64, 64));
+                let mut counts = [0; 64 * 64];
+                let mut coverage = Coverage::new(&mut display, area, &mut counts);
+                let result = coverage.check_string(&style, $text, POSITION, Baseline::Top).unwrap();
+
+                let line_height = bitmap_font.metrics.line_height();
+                let line_box = Rectangle::new(POSITION, Size::new($width, line_height));
+                let next_position = POSITION + Point::new($width, 0);
+                let expected = TextReport {
+                    drawn_box: line_box,
+                    measured_box: line_box,
+                    next_position,
+                    measured_next_position: next_position,
+                    overdrawn: $overdrawn,
+                    undrawn: 0,
+                };
+
+                assert_eq!(result, expected);
+                assert_eq!(result.is_ok(), $overdrawn == 0);
+            }
+        )*
+    }
+}
+
+test_check_string! {
+    check_string_text, "Fit", BACKGROUND_COLOR, 23, 0,
+    check_string_trailing_space, "a ", BACKGROUND_COLOR, 17, 0,
+    check_string_overlapping, "AVAW", BACKGROUND_COLOR, 48, 0,
+    check_string_overlapping_descenders, "fj", BACKGROUND_COLOR, 13, 0,
+    // Every combining mark after the first is mixed again with the glyph images below it.
+    check_string_stacked_marks, "g̈́a", BACKGROUND_COLOR, 19, 31,
+}
+
+#[test]
+fn check_string_empty() {
+    let bitmap_font = mplus!(1, 500, 17, false, 4, 4, 'A'..='z');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .build();
+
+    let mut display = MockDisplay::<Rgb888>::new();
+    let area = Rectangle::new(Point::zero(), Size::new(64, 64));
+    let mut counts = [0; 64 * 64];
+    let mut coverage = Coverage::new(&mut display, area, &mut counts);
+    let result = coverage
+        .check_string(&style, "", POSITION, Baseline::Top)
+        .unwrap();
+
+    let line_height = bitmap_font.metrics.line_height();
+    assert_eq!(result.drawn_box, Rectangle::zero());
+    assert_eq!(
+        result.measured_box,
+        Rectangle::new(POSITION, Size::new(0, line_height))
+    );
+    assert_eq!(result.next_position, POSITION);
+    assert_eq!(result.measured_next_position, POSITION);
+    assert_eq!((result.overdrawn, result.undrawn), (0, 0));
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_string_partial_area() {
+    let bitmap_font = mplus!(1, 500, 17, false, 4, 4, 'A'..='z');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .background_color(BACKGROUND_COLOR)
+        .build();
+
+    let mut display = MockDisplay::<Rgb888>::new();
+    let area = Rectangle::new(Point::new(10, 4), Size::new(8, 6));
+    let mut counts = [0; 8 * 6];
+    let mut coverage = Coverage::new(&mut display, area, &mut counts);
+    let result = coverage
+        .check_string(&style, "Fit", POSITION, Baseline::Top)
+        .unwrap();
+
+    // Pixels outside of the area are not counted, but they do extend the bounding box.
+    assert_eq!(result.drawn_box, result.measured_box);
+    assert!(area.points().all(|point| coverage.count(point) == Some(1)));
+    assert!(result.is_ok());
+}
@@ -0,0 +1,127 @@
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::{BinaryColor, Gray4, GrayColor};
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::color::Dithering;
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+
+const POSITION: Point = Point::new(2, 2);
+
+/// The thresholds of ordered dithering, as a 4×4 Bayer matrix.
+const BAYER_MATRIX: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Returns whether a pixel with the specified gray value out of 15 at the specified point is
+/// turned on by ordered dithering.
+fn is_on_ordered(value: u8, point: Point) -> bool {
+    let threshold = BAYER_MATRIX[point.y.rem_euclid(4) as usize][point.x.rem_euclid(4) as usize];
+
+    u32::from(value) * 32 > (u32::from(threshold) * 2 + 1) * 15
+}
+
+macro_rules! test_dithering {
+    (
+        $(
+            $fn_ident:ident, $text:expr, $dithering:expr, $check:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 20, false, 1, 4, 'A'..='z');
+                let gray_style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(Gray4::WHITE)
+                    .background_color(Gray4::BLACK)
+                    .build();
+
+                let mut gray_display = MockDisplay::new();
+                gray_display.set_allow_overdraw(true);
+                let gray_next = gray_style
+                    .draw_string($text, POSITION, Baseline::Top, &mut gray_display)
+                    .unwrap();
+
+                let binary_style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(BinaryColor::On)
+                    .background_color(BinaryColor::Off)
+                    .dithering($dithering)
+                    .build();
+
+                let mut display = MockDisplay::new();
+                display.set_allow_overdraw(true);
+                let next = binary_style
+                    .draw_string($text, POSITION, Baseline::Top, &mut display)
+                    .unwrap();
+
+                assert_eq!(next, gray_next);
+
+                let check: fn(&MockDisplay<Gray4>, &MockDisplay<BinaryColor>) = $check;
+                check(&gray_display, &display);
+            }
+        )*
+    }
+}
+
+test_dithering! {
+    dithering_none, "Hi", Dithering::None, |gray_display, display| {
+        for y in 0..64 {
+            for x in 0..64 {
+                let point = Point::new(x, y);
+                let expected = gray_display.get_pixel(point).map(|color| BinaryColor::from(color.luma() >= 8));
+                assert_eq!(display.get_pixel(point), expected, "\n  x, y: {x:?}, {y:?}");
+            }
+        }
+    },
+    dithering_ordered, "Hi", Dithering::Ordered, |gray_display, display| {
+        let mut gray_count = 0;
+        for y in 0..64 {
+            for x in 0..64 {
+                let point = Point::new(x, y);
+                let gray_color = gray_display.get_pixel(point);
+                if gray_color.is_some_and(|color| color.luma() > 0 && color.luma() < 15) {
+                    gray_count += 1;
+                }
+
+                let expected = gray_color.map(|color| BinaryColor::from(is_on_ordered(color.luma(), point)));
+                assert_eq!(display.get_pixel(point), expected, "\n  x, y: {x:?}, {y:?}");
+            }
+        }
+
+        assert!(gray_count > 0);
+    },
+    dithering_error_diffusion, "Hi", Dithering::ErrorDiffusion, |gray_display, display| {
+        // Along every row, the number of pixels that are turned on stays close to the sum of the
+        // gray values; the error is carried over within each glyph image that is drawn.
+        let mut differences = 0;
+        for y in 0..64 {
+            let mut gray_sum = 0;
+            let mut count = 0;
+            for x in 0..64 {
+                let point = Point::new(x, y);
+                let gray_color = gray_display.get_pixel(point);
+                let color = display.get_pixel(point);
+                assert_eq!(color.is_some(), gray_color.is_some(), "\n  x, y: {x:?}, {y:?}");
+
+                let Some((gray_color, color)) = gray_color.zip(color) else {
+                    continue;
+                };
+
+                gray_sum += u32::from(gray_color.luma());
+                if color.is_on() {
+                    count += 1;
+                }
+
+                if color.is_on() != (gray_color.luma() >= 8) {
+                    differences += 1;
+                }
+            }
+
+            let expected = gray_sum as f32 / 15.0;
+            assert!((count as f32 - expected).abs() <= 1.0, "\n  y: {y:?}");
+        }
+
+        assert!(differences > 0);
+    },
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::style::BitmapFontStyleBuilder;
+use mplusfonts::text::Marquee;
+use mplusfonts::{Scalar, mplus};
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR, new_display};
+
+const VIEWPORT: Rectangle = Rectangle::new(Point::new(6, 10), Size::new(50, 20));
+
+macro_rules! test_marquee_wrap_around {
+    (
+        $(
+            $fn_ident:ident, $offset:expr, $repetition_offset:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                // Every character advances the position by exactly 8 pixels.
+                let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, 'A'..='z');
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color(BACKGROUND_COLOR)
+                    .build();
+
+                let mut marquee = Marquee::new("Wrap", VIEWPORT, style.clone());
+                marquee.offset = Scalar::from_int($offset);
+                marquee.gap = 8;
+
+                let mut display = new_display();
+                marquee.draw(&mut display).unwrap();
+
+                let mut expected = new_display();
+                let mut target = expected.clipped(&VIEWPORT);
+                let mut position = VIEWPORT.top_left - Point::new($repetition_offset, 0);
+                while position.x < VIEWPORT.top_left.x + VIEWPORT.size.width as i32 {
+                    position = style
+                        .draw_string("Wrap", position, Baseline::Top, &mut target)
+                        .unwrap();
+                    position = style
+                        .draw_whitespace(8, position, Baseline::Top, &mut target)
+                        .unwrap();
+                }
+
+                display.assert_eq(&expected);
+            }
+        )*
+    }
+}
+
+test_marquee_wrap_around! {
+    marquee_wrap_around_zero, 0, 0,
+    marquee_wrap_around_within_period, 13, 13,
+    marquee_wrap_around_one_period, 40, 0,
+    marquee_wrap_around_past_period, 53, 13,
+    marquee_wrap_around_negative, -27, 13,
+}
+
+#[test]
+fn marquee_viewport_clipping() {
+    let bitmap_font = mplus!(1, 500, 16, false, 4, 4, 'A'..='z');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .background_color(BACKGROUND_COLOR)
+        .build();
+
+    let mut marquee = Marquee::new("Clipped to the viewport", VIEWPORT, style);
+    marquee.offset = Scalar::from_f32(17.25);
+    marquee.gap = 5;
+
+    let mut display = new_display();
+    marquee.draw(&mut display).unwrap();
+
+    for y in 0..64 {
+        for x in 0..64 {
+            let point = Point::new(x, y);
+            let is_drawn = display.get_pixel(point).is_some();
+            assert_eq!(is_drawn, VIEWPORT.contains(point), "\n  x, y: {x:?}, {y:?}");
+        }
+    }
+}
+
+#[test]
+fn marquee_sub_pixel_offsets() {
+    let bitmap_font = mplus!(1, 500, 16, false, 4, 4, 'A'..='z');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .background_color(BACKGROUND_COLOR)
+        .build();
+
+    // A single glyph is visible at a time, at the position given by the negative offset.
+    let viewport = Rectangle::new(Point::zero(), Size::new(12, 20));
+    let draw = |offset: f32| {
+        let mut marquee = Marquee::new("l", viewport, style.clone());
+        marquee.offset = Scalar::from_f32(offset);
+        marquee.gap = 40;
+
+        let mut display = new_display();
+        marquee.draw(&mut display).unwrap();
+        display
+    };
+
+    let displays = [-1.0, -1.25, -1.5, -1.75].map(draw);
+    for (index, display) in displays.iter().enumerate() {
+        for other_display in &displays[index + 1..] {
+            assert_ne!(display, other_display);
+        }
+    }
+
+    let display = draw(-2.0);
+    for y in 0..20 {
+        for x in 1..12 {
+            let result = display.get_pixel(Point::new(x, y));
+            let expected = displays[0].get_pixel(Point::new(x - 1, y));
+            assert_eq!(result, expected, "\n  x, y: {x:?}, {y:?}");
+        }
+    }
+}
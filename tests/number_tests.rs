@@ -0,0 +1,297 @@
+mod common;
+
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::renderer::TextRenderer;
+use embedded_graphics::text::{Alignment, Baseline};
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+use mplusfonts::text::{Number, NumberFormat, Padding, Radix, Sign};
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR, new_display};
+
+const POSITION: Point = Point::new(2, 20);
+
+/// Returns the leftmost and the rightmost columns of the pixels that are drawn in a color other
+/// than the background color.
+fn ink_columns(display: &MockDisplay<Rgb888>) -> (i32, i32) {
+    let mut columns = (i32::MAX, i32::MIN);
+    for y in 0..64 {
+        for x in 0..64 {
+            let color = display.get_pixel(Point::new(x, y));
+            if color.is_some_and(|color| color != BACKGROUND_COLOR) {
+                columns = (columns.0.min(x), columns.1.max(x));
+            }
+        }
+    }
+
+    columns
+}
+
+/// Returns the runs of consecutive columns that contain pixels drawn in a color other than the
+/// background color, as pairs of the leftmost and the rightmost column of every run.
+fn ink_runs(display: &MockDisplay<Rgb888>) -> Vec<(i32, i32)> {
+    let mut runs: Vec<(i32, i32)> = Vec::new();
+    for x in 0..64 {
+        let is_ink = (0..64).any(|y| {
+            let color = display.get_pixel(Point::new(x, y));
+            color.is_some_and(|color| color != BACKGROUND_COLOR)
+        });
+
+        match runs.last_mut() {
+            Some((_, right)) if is_ink && *right == x - 1 => *right = x,
+            _ if is_ink => runs.push((x, x)),
+            _ => {}
+        }
+    }
+
+    runs
+}
+
+macro_rules! test_number_draw {
+    (
+        $(
+            $fn_ident:ident, $value:expr, $format:expr, $text:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 10, false, 4, 4, '0'..='9', 'A'..='F', 'a'..='f', [",.-+ xV"]);
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color(BACKGROUND_COLOR)
+                    .build();
+
+                let number = Number::new($value, $format, POSITION, style.clone());
+                let mut display = new_display();
+                let next = number.draw(&mut display).unwrap();
+
+                let mut expected_display = new_display();
+                let expected_next = style
+                    .draw_string($text, POSITION, Baseline::Alphabetic, &mut expected_display)
+                    .unwrap();
+
+                assert_eq!(display, expected_display);
+                assert_eq!(next, expected_next);
+            }
+        )*
+    }
+}
+
+test_number_draw! {
+    number_draw_integer, 1234, NumberFormat::new(), "1234",
+    number_draw_zero, 0, NumberFormat::new(), "0",
+    number_draw_negative, -42, NumberFormat::new(), "-42",
+    number_draw_sign_always, 5, NumberFormat { sign: Sign::Always, ..NumberFormat::new() }, "+5",
+    number_draw_separator, 1234567, NumberFormat { separator: Some(','), ..NumberFormat::new() }, "1,234,567",
+    number_draw_decimals, -314, NumberFormat { decimals: 2, ..NumberFormat::new() }, "-3.14",
+    number_draw_zeros, 7, NumberFormat { min_digits: 3, ..NumberFormat::new() }, "007",
+    number_draw_lower_hex, 0xbeef, NumberFormat { radix: Radix::LowerHex, ..NumberFormat::new() }, "beef",
+    number_draw_prefix, 0xFF, NumberFormat { radix: Radix::UpperHex, prefix: "0x", ..NumberFormat::new() }, "0xFF",
+    number_draw_suffix, 12, NumberFormat { suffix: " V", ..NumberFormat::new() }, "12 V",
+}
+
+#[test]
+fn number_fixed_width_cells() {
+    let bitmap_font = mplus!(1, 500, 10, false, 4, 4, '0'..='9', 'A'..='F');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .background_color(BACKGROUND_COLOR)
+        .build();
+
+    let format = NumberFormat {
+        radix: Radix::UpperHex,
+        fixed_width: true,
+        ..NumberFormat::new()
+    };
+
+    let draw = |value, format| {
+        let number = Number::new(value, format, POSITION, style.clone());
+        let mut display = new_display();
+        let next = number.draw(&mut display).unwrap();
+
+        (display, next.x - POSITION.x)
+    };
+
+    // Every digit takes up the width of the widest digit, which is wider than the digit one.
+    let (_, one_width) = draw(0x1, format);
+    let (_, proportional_width) = draw(
+        0x1,
+        NumberFormat {
+            fixed_width: false,
+            ..format
+        },
+    );
+    assert!(one_width > proportional_width);
+    for value in 0x0..=0xF {
+        let (_, width) = draw(value, format);
+        assert_eq!(width, one_width, "\n  value: {value:X}");
+    }
+
+    // Consecutive digits are one cell width apart.
+    let (display, width) = draw(0x111, format);
+    assert!(width.abs_diff(3 * one_width) <= 2);
+
+    let runs = ink_runs(&display);
+    assert_eq!(runs.len(), 3);
+    for pair in runs.windows(2) {
+        let ((left, right), (next_left, next_right)) = (pair[0], pair[1]);
+        assert!((next_left - left).abs_diff(one_width) <= 1);
+        assert!((next_right - right).abs_diff(one_width) <= 1);
+    }
+}
+
+#[test]
+fn number_fixed_width_centered() {
+    let bitmap_font = mplus!(1, 500, 36, false, 4, 4, '0'..='9', 'A'..='F');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .background_color(BACKGROUND_COLOR)
+        .build();
+
+    let format = NumberFormat {
+        radix: Radix::UpperHex,
+        fixed_width: true,
+        ..NumberFormat::new()
+    };
+
+    let position = Point::new(2, 0);
+    let mut number = Number::new(0x1, format, position, style.clone());
+    number.baseline = Baseline::Top;
+    let mut display = new_display();
+    let cell_width = number.draw(&mut display).unwrap().x - position.x;
+
+    let mut plain_display = new_display();
+    let plain_width = style
+        .draw_string("1", position, Baseline::Top, &mut plain_display)
+        .unwrap()
+        .x
+        - position.x;
+
+    // The digit one is moved to the right by half of the space that its cell has to spare.
+    let spare_width = cell_width - plain_width;
+    let (left, right) = ink_columns(&display);
+    let (plain_left, plain_right) = ink_columns(&plain_display);
+    assert!(spare_width >= 2);
+    assert_eq!(left - plain_left, right - plain_right);
+    assert!((left - plain_left).abs_diff(spare_width - (left - plain_left)) <= 1);
+}
+
+macro_rules! test_number_alignment {
+    (
+        $(
+            $fn_ident:ident, $alignment:expr, $numerator:literal / $denominator:literal,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 10, false, 4, 4, '0'..='9', [","]);
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color(BACKGROUND_COLOR)
+                    .build();
+
+                let format = NumberFormat {
+                    separator: Some(','),
+                    ..NumberFormat::new()
+                };
+
+                let mut left_number = Number::new(12345, format, Point::zero(), style.clone());
+                let mut width_display = MockDisplay::new();
+                width_display.set_allow_out_of_bounds_drawing(true);
+                width_display.set_allow_overdraw(true);
+                let width = left_number.draw(&mut width_display).unwrap().x;
+                let shift = width * $numerator / $denominator;
+
+                let position = POSITION + Point::new(shift, 0);
+                left_number.position = POSITION;
+                let mut left_display = new_display();
+                let left_next = left_number.draw(&mut left_display).unwrap();
+
+                let mut number = Number::new(12345, format, position, style);
+                number.alignment = $alignment;
+                let mut display = new_display();
+                let next = number.draw(&mut display).unwrap();
+
+                let (left, right) = ink_columns(&display);
+                let (expected_left, expected_right) = ink_columns(&left_display);
+                assert!(left.abs_diff(expected_left) <= 1);
+                assert!(right.abs_diff(expected_right) <= 1);
+                assert!(next.x.abs_diff(left_next.x) <= 1);
+                assert_eq!(next.y, position.y);
+            }
+        )*
+    }
+}
+
+test_number_alignment! {
+    number_alignment_left, Alignment::Left, 0 / 2,
+    number_alignment_center, Alignment::Center, 1 / 2,
+    number_alignment_right, Alignment::Right, 2 / 2,
+}
+
+macro_rules! test_number_blanks {
+    (
+        $(
+            $fn_ident:ident, $value:expr, $min_digits:expr, $separator:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 10, false, 4, 4, '0'..='9', [",-"]);
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color(BACKGROUND_COLOR)
+                    .build();
+
+                let format = NumberFormat {
+                    min_digits: $min_digits,
+                    separator: $separator,
+                    ..NumberFormat::new()
+                };
+
+                let draw = |padding| {
+                    let format = NumberFormat { padding, ..format };
+                    let number = Number::new($value, format, POSITION, style.clone());
+                    let mut display = new_display();
+                    let next = number.draw(&mut display).unwrap();
+
+                    (display, next)
+                };
+
+                // The blanks take up as much space as the zeros and the separators that they
+                // replace, so that the digits that are drawn are in the same place.
+                let (display, next) = draw(Padding::Blanks);
+                let (zeros_display, zeros_next) = draw(Padding::Zeros);
+                assert_eq!(next, zeros_next);
+                for y in 0..64 {
+                    for x in 0..64 {
+                        let point = Point::new(x, y);
+                        let color = display.get_pixel(point);
+                        if color != zeros_display.get_pixel(point) {
+                            assert_eq!(color, Some(BACKGROUND_COLOR), "\n  x, y: {x:?}, {y:?}");
+                        }
+                    }
+                }
+
+                let (left, _) = ink_columns(&display);
+                let (zeros_left, _) = ink_columns(&zeros_display);
+                assert!(left > zeros_left);
+            }
+        )*
+    }
+}
+
+test_number_blanks! {
+    number_blanks, 7, 3, None,
+    number_blanks_separator, 7, 5, Some(','),
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use mplusfonts::mplus;
+use mplusfonts::text::{MetricsOverlay, OverlayColors};
+
+use common::new_display;
+
+const COLOR: Rgb888 = Rgb888::WHITE;
+const POSITION: Point = Point::new(3, 20);
+const NO_COLORS: OverlayColors<Rgb888> = OverlayColors {
+    top: None,
+    ascender: None,
+    cap_height: None,
+    x_height: None,
+    baseline: None,
+    descender: None,
+    bottom: None,
+    advance: None,
+    image: None,
+    underline: None,
+    strikethrough: None,
+};
+
+/// Returns a band that spans the text run `Hg` in the font used in these tests, at the specified
+/// row of pixels and with the specified height.
+fn band(row: i32, height: u32) -> Rectangle {
+    Rectangle::new(Point::new(3, row), Size::new(16, height))
+}
+
+macro_rules! test_metrics_overlay_draw {
+    (
+        $(
+            $fn_ident:ident, $field:ident, $filled:expr, $outlined:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~');
+                let colors = OverlayColors {
+                    $field: Some(COLOR),
+                    ..NO_COLORS
+                };
+
+                let overlay = MetricsOverlay::new("Hg", POSITION, colors, &bitmap_font);
+                let mut display = new_display();
+                overlay.draw(&mut display).unwrap();
+
+                let mut expected = new_display();
+                let filled: &[Rectangle] = &$filled;
+                for rectangle in filled {
+                    expected.fill_solid(rectangle, COLOR).unwrap();
+                }
+
+                let outlined: &[Rectangle] = &$outlined;
+                for rectangle in outlined {
+                    let style = PrimitiveStyle::with_stroke(COLOR, 1);
+                    rectangle.into_styled(style).draw(&mut expected).unwrap();
+                }
+
+                display.assert_eq(&expected);
+            }
+        )*
+    }
+}
+
+test_metrics_overlay_draw! {
+    metrics_overlay_draw_top, top, [band(0, 1)], [],
+    metrics_overlay_draw_ascender, ascender, [band(4, 1)], [],
+    metrics_overlay_draw_cap_height, cap_height, [band(8, 1)], [],
+    metrics_overlay_draw_x_height, x_height, [band(11, 1)], [],
+    metrics_overlay_draw_baseline, baseline, [band(20, 1)], [],
+    metrics_overlay_draw_descender, descender, [band(23, 1)], [],
+    metrics_overlay_draw_bottom, bottom, [band(23, 1)], [],
+    metrics_overlay_draw_underline, underline, [band(21, 1)], [],
+    metrics_overlay_draw_strikethrough, strikethrough, [band(15, 1)], [],
+    metrics_overlay_draw_advance, advance, [],
+    [
+        Rectangle::new(Point::new(3, 0), Size::new(8, 24)),
+        Rectangle::new(Point::new(11, 0), Size::new(8, 24)),
+    ],
+    metrics_overlay_draw_image, image, [],
+    [
+        Rectangle::new(Point::new(3, 8), Size::new(8, 12)),
+        Rectangle::new(Point::new(11, 11), Size::new(7, 13)),
+    ],
+}
+
+#[test]
+fn metrics_overlay_draw_default_colors() {
+    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~');
+    let colors = OverlayColors::default();
+    let overlay = MetricsOverlay::new("Hg", POSITION, colors, &bitmap_font);
+    let mut display = new_display();
+    overlay.draw(&mut display).unwrap();
+
+    // The outlines of the advance boxes and of the bounding boxes of glyph images are drawn over
+    // the metric lines.
+    let color_at = |x, y| display.get_pixel(Point::new(x, y));
+    assert_eq!(color_at(5, 0), colors.advance);
+    assert_eq!(color_at(5, 4), colors.ascender);
+    assert_eq!(color_at(14, 8), colors.cap_height);
+    assert_eq!(color_at(14, 11), colors.image);
+    assert_eq!(color_at(5, 15), colors.strikethrough);
+    assert_eq!(color_at(5, 20), colors.baseline);
+    assert_eq!(color_at(5, 21), colors.underline);
+    assert_eq!(color_at(5, 23), colors.advance);
+    assert_eq!(color_at(3, 4), colors.advance);
+    assert_eq!(color_at(4, 8), colors.image);
+    assert_eq!(color_at(5, 12), None);
+    assert_eq!(display.affected_area(), band(0, 24));
+}
+
+#[test]
+fn metrics_overlay_draw_no_colors() {
+    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~');
+    let overlay = MetricsOverlay::new("Hg", POSITION, NO_COLORS, &bitmap_font);
+    let mut display = new_display();
+    overlay.draw(&mut display).unwrap();
+
+    display.assert_eq(&new_display());
+}
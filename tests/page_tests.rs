@@ -0,0 +1,133 @@
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::mplus;
+use mplusfonts::page::{BlitMode, PageBuffer};
+use mplusfonts::style::BitmapFontStyleBuilder;
+
+const WIDTH: u32 = 64;
+const PAGES: u32 = 4;
+const PATTERN: [u8; 4] = [0b1010_0101, 0b0000_0000, 0b1111_1111, 0b0011_1100];
+
+fn pattern_data() -> [u8; (WIDTH * PAGES) as usize] {
+    let mut data = [0; (WIDTH * PAGES) as usize];
+    for (index, byte) in data.iter_mut().enumerate() {
+        *byte = PATTERN[index % PATTERN.len()];
+    }
+
+    data
+}
+
+fn pattern_display() -> MockDisplay<BinaryColor> {
+    let data = pattern_data();
+    let mut display = MockDisplay::new();
+    display.set_allow_overdraw(true);
+    display.set_allow_out_of_bounds_drawing(true);
+    for y in 0..(PAGES * 8) as i32 {
+        for x in 0..WIDTH as i32 {
+            let byte = data[(y / 8) as usize * WIDTH as usize + x as usize];
+            let color = BinaryColor::from(byte & (1 << (y % 8)) != 0);
+            Pixel(Point::new(x, y), color).draw(&mut display).unwrap();
+        }
+    }
+
+    display
+}
+
+fn assert_pages_eq(data: &[u8], expected: impl Fn(Point) -> bool) {
+    for y in 0..(PAGES * 8) as i32 {
+        for x in 0..WIDTH as i32 {
+            let byte = data[(y / 8) as usize * WIDTH as usize + x as usize];
+            let result = byte & (1 << (y % 8)) != 0;
+            assert_eq!(result, expected(Point::new(x, y)), "\n  x, y: {x:?}, {y:?}");
+        }
+    }
+}
+
+macro_rules! test_blit_string {
+    (
+        $(
+            $fn_ident:ident, $text:expr, $position:expr, $mode:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let paged_font = mplus!(2, 500, 16, false, 1, paged(1), 'A'..='Z', 'a'..='z');
+                let bitmap_font = mplus!(2, 500, 16, false, 1, 1, 'A'..='Z', 'a'..='z');
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(BinaryColor::On)
+                    .background_color(BinaryColor::Off)
+                    .build();
+
+                let mut data = pattern_data();
+                let mut page_buffer = PageBuffer::new(&mut data, WIDTH);
+                let result =
+                    page_buffer.blit_string(&paged_font, $text, $position, Baseline::Top, $mode);
+
+                let mut display = match $mode {
+                    BlitMode::Or => {
+                        let mut display = MockDisplay::new();
+                        display.set_allow_overdraw(true);
+                        display.set_allow_out_of_bounds_drawing(true);
+                        display
+                    }
+                    BlitMode::Copy => pattern_display(),
+                };
+                let expected = style
+                    .draw_string($text, $position, Baseline::Top, &mut display)
+                    .unwrap();
+
+                assert_eq!(result, expected);
+
+                let pattern = pattern_display();
+                assert_pages_eq(page_buffer.data(), |point| {
+                    let is_on = display.get_pixel(point) == Some(BinaryColor::On);
+                    match $mode {
+                        BlitMode::Or => is_on || pattern.get_pixel(point) == Some(BinaryColor::On),
+                        BlitMode::Copy => is_on,
+                    }
+                });
+            }
+        )*
+    }
+}
+
+test_blit_string! {
+    blit_string_or_page_aligned, "Page", Point::new(2, 8), BlitMode::Or,
+    blit_string_or_unaligned, "Page", Point::new(2, 11), BlitMode::Or,
+    blit_string_or_clipped_right, "Pages", Point::new(41, 5), BlitMode::Or,
+    blit_string_copy_page_aligned, "Page", Point::new(2, 8), BlitMode::Copy,
+    blit_string_copy_unaligned, "Page", Point::new(2, 11), BlitMode::Copy,
+    blit_string_copy_clipped_right, "Pages", Point::new(41, 5), BlitMode::Copy,
+}
+
+#[test]
+fn draw_string_to_page_buffer() {
+    let bitmap_font = mplus!(2, 500, 16, false, 1, 1, 'A'..='Z', 'a'..='z');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(BinaryColor::On)
+        .background_color(BinaryColor::Off)
+        .build();
+
+    let mut data = pattern_data();
+    let mut page_buffer = PageBuffer::new(&mut data, WIDTH);
+    let position = Point::new(41, 13);
+    let result = style
+        .draw_string("Pages", position, Baseline::Top, &mut page_buffer)
+        .unwrap();
+
+    let mut display = pattern_display();
+    let expected = style
+        .draw_string("Pages", position, Baseline::Top, &mut display)
+        .unwrap();
+
+    assert_eq!(result, expected);
+    assert_pages_eq(page_buffer.data(), |point| {
+        display.get_pixel(point) == Some(BinaryColor::On)
+    });
+}
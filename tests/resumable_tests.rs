@@ -0,0 +1,107 @@
+mod common;
+
+use core::task::Poll;
+
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::mplus;
+use mplusfonts::style::{BitmapFontStyle, BitmapFontStyleBuilder, DrawLimit};
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR};
+
+fn new_display() -> MockDisplay<Rgb888> {
+    let mut display = common::new_display();
+    display.set_allow_out_of_bounds_drawing(true);
+    display
+}
+
+macro_rules! test_resumable_draw {
+    (
+        $(
+            $fn_ident:ident, $texts:expr, $limit:expr, $min_calls:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 16, false, 4, 4, kern('A'..='z', ["g̈́", "ï"]));
+                let new_style = || -> BitmapFontStyle<'_, '_, Rgb888, _, 4> {
+                    BitmapFontStyleBuilder::new()
+                        .font(&bitmap_font)
+                        .text_color(TEXT_COLOR)
+                        .background_color(BACKGROUND_COLOR)
+                        .build()
+                };
+
+                let style = new_style();
+                let mut expected = new_display();
+                let mut expected_position = Point::new(2, 20);
+                for text in $texts {
+                    expected_position = style
+                        .draw_string(text, expected_position, Baseline::Alphabetic, &mut expected)
+                        .unwrap();
+                }
+
+                let style = new_style();
+                let mut display = new_display();
+                let mut position = Point::new(2, 20);
+                let mut calls = 0;
+                for text in $texts {
+                    let mut resumable = style.resumable(text, position, Baseline::Alphabetic);
+                    position = loop {
+                        calls += 1;
+                        match resumable.draw($limit, &mut display).unwrap() {
+                            Poll::Ready(position) => break position,
+                            Poll::Pending => assert!(!resumable.is_complete()),
+                        }
+                    };
+
+                    assert!(resumable.is_complete());
+                }
+
+                let min_calls = $min_calls;
+                assert!(calls >= min_calls, "expected at least {min_calls} calls, found {calls}");
+                assert_eq!(position, expected_position);
+                display.assert_eq(&expected);
+            }
+        )*
+    }
+}
+
+test_resumable_draw! {
+    resumable_draw_clusters_1, ["Resume"], DrawLimit::Clusters(1), 6,
+    resumable_draw_clusters_2, ["Resume"], DrawLimit::Clusters(2), 3,
+    resumable_draw_pixels_1, ["Resume"], DrawLimit::Pixels(1), 6,
+    resumable_draw_pixels_150, ["Resume"], DrawLimit::Pixels(150), 2,
+    resumable_draw_overlapping_clusters_1, ["AVAWAY"], DrawLimit::Clusters(1), 6,
+    resumable_draw_overlapping_pixels_100, ["AVAWAY"], DrawLimit::Pixels(100), 2,
+    resumable_draw_combining_clusters_1, ["ag̈́ïg"], DrawLimit::Clusters(1), 4,
+    resumable_draw_combining_pixels_1, ["ag̈́ïg"], DrawLimit::Pixels(1), 4,
+    resumable_draw_carryover_clusters_1, ["naif", "jog", "Ty"], DrawLimit::Clusters(1), 9,
+    resumable_draw_carryover_pixels_100, ["naif", "jog", "Ty"], DrawLimit::Pixels(100), 3,
+}
+
+#[test]
+fn resumable_draw_after_ready() {
+    let bitmap_font = mplus!(1, 500, 16, false, 4, 4, 'A'..='z');
+    let style = BitmapFontStyle::new(&bitmap_font, Rgb888::WHITE);
+    let mut display = new_display();
+    let mut resumable = style.resumable("Done", Point::new(2, 20), Baseline::Alphabetic);
+    let position = loop {
+        let poll = resumable
+            .draw(DrawLimit::Clusters(1), &mut display)
+            .unwrap();
+        if let Poll::Ready(position) = poll {
+            break position;
+        }
+    };
+
+    let mut untouched_display = new_display();
+    let result = resumable.draw(DrawLimit::Clusters(1), &mut untouched_display);
+
+    assert_eq!(result, Ok(Poll::Ready(position)));
+    untouched_display.assert_eq(&new_display());
+}
@@ -0,0 +1,92 @@
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::style::BitmapFontStyle;
+use mplusfonts::{Scalar, mplus};
+
+macro_rules! test_advance_width {
+    (
+        $(
+            $fn_ident:ident, $text:expr, $x:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(2, 500, 17, false, 4, 4, kern(' '..='~', []));
+                let style = BitmapFontStyle::new(&bitmap_font, Rgb888::WHITE);
+                let position = Point::new($x, 20);
+                let metrics = style.measure_string($text, position, Baseline::Alphabetic);
+
+                let mut chars = $text.char_indices().peekable();
+                let mut expected = f64::from($x);
+                while let Some((index, _)) = chars.next() {
+                    let entry = bitmap_font.charmap.get(&$text[index..]);
+                    let next_key = chars.peek().map_or("", |&(index, _)| &$text[index..][..1]);
+                    let advance_width = (entry.advance_width_to)(next_key);
+                    expected += f64::from(advance_width.to_f32());
+                }
+
+                assert_eq!(metrics.next_position, Point::new(expected.floor() as i32, 20));
+            }
+        )*
+    }
+}
+
+test_advance_width! {
+    advance_width_pangram, "The quick brown fox jumps over the lazy dog.", 0,
+    advance_width_kerning_pairs, "AVAWAY To Ty Yo LT", 3,
+    advance_width_negative_x, "Wavy", -13,
+}
+
+#[test]
+fn scalar_from_bits() {
+    let scalar = Scalar::from_bits(0x0003_4000);
+
+    assert_eq!(scalar.to_bits(), 0x0003_4000);
+    assert_eq!(scalar.to_f32(), 3.25);
+    assert_eq!(scalar, Scalar::from_f32(3.25));
+    assert_eq!(scalar - Scalar::from_int(3), Scalar::from_f32(0.25));
+}
+
+#[test]
+fn scalar_metrics() {
+    let bitmap_font = mplus!(1, 500, 25, false, 1, 4, 'A'..='Z');
+    let metrics = bitmap_font.metrics;
+    let x_height = (25.0 * 0.52 * 65536f32).round() as i32;
+
+    assert_eq!(metrics.baseline, Scalar::ZERO);
+    assert_eq!(metrics.x_height.to_bits(), x_height);
+    assert_eq!(metrics.line_height(), 36);
+}
+
+#[test]
+fn scalar_saturating() {
+    let max = Scalar::from_int(i32::MAX);
+    let min = Scalar::from_int(i32::MIN);
+    let one = Scalar::from_int(1);
+
+    assert_eq!(max + one, max);
+    assert_eq!(min - one, min);
+    assert_eq!(-min, max);
+
+    let mut x = Scalar::from_int(40000);
+    x += Scalar::from_int(40000);
+    assert!(x.to_f32() >= 32767.0);
+    x -= -Scalar::from_int(40000);
+    assert!(x.to_f32() >= 32767.0);
+}
+
+#[test]
+fn scalar_saturating_position() {
+    let bitmap_font = mplus!(1, 500, 17, false, 4, 4, 'A'..='z');
+    let style = BitmapFontStyle::new(&bitmap_font, Rgb888::WHITE);
+    // Positions past the range of scalar values saturate instead of overflowing.
+    for x in [40000, i32::MAX - 10, i32::MIN + 10] {
+        let position = Point::new(x, 20);
+        let metrics = style.measure_string("Wavy", position, Baseline::Alphabetic);
+
+        assert_eq!(metrics.next_position.x.signum(), x.signum());
+    }
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use embedded_graphics::mock_display::MockDisplay;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::color::Gradient;
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR};
+
+fn new_display() -> MockDisplay<Rgb888> {
+    let mut display = common::new_display();
+    display.set_allow_out_of_bounds_drawing(true);
+    display
+}
+
+fn assert_translated_eq(display: &MockDisplay<Rgb888>, expected: &MockDisplay<Rgb888>, by: Point) {
+    for y in 0..64 {
+        for x in 0..64 {
+            let point = Point::new(x, y);
+            let Some(expected) = expected.get_pixel(point) else {
+                continue;
+            };
+            let point = point + by;
+            if point.x < 0 || point.x >= 64 || point.y < 0 || point.y >= 64 {
+                continue;
+            }
+
+            let result = display.get_pixel(point);
+            assert_eq!(result, Some(expected), "\n  x, y: {x:?}, {y:?}");
+        }
+    }
+}
+
+macro_rules! test_draw_string_translated {
+    (
+        $(
+            $fn_ident:ident, $text:expr, $position:expr, $by:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 17, false, 4, 4, kern('A'..='z', ["g̈́"]));
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color(BACKGROUND_COLOR)
+                    .build();
+
+                let position = $position;
+                let mut expected = new_display();
+                let expected_position = style
+                    .draw_string($text, position, Baseline::Alphabetic, &mut expected)
+                    .unwrap();
+
+                let by = $by;
+                let mut display = new_display();
+                let result = style
+                    .draw_string($text, position + by, Baseline::Alphabetic, &mut display)
+                    .unwrap();
+
+                assert_eq!(result, expected_position + by);
+                assert_translated_eq(&display, &expected, by);
+            }
+        )*
+    }
+}
+
+test_draw_string_translated! {
+    draw_string_translated_right, "Wavy", Point::new(1, 20), Point::new(17, 0),
+    draw_string_translated_to_negative_x, "fillitilli", Point::new(30, 20), Point::new(-33, 0),
+    draw_string_translated_past_zero_x, "fillitilli", Point::new(2, 20), Point::new(-17, 0),
+    draw_string_translated_to_negative_y, "ag̈́g̈́a", Point::new(4, 26), Point::new(0, -18),
+}
+
+macro_rules! test_draw_string_gradient_carryover {
+    (
+        $(
+            $fn_ident:ident, $text:expr, $next_text:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(1, 500, 17, false, 4, 4, kern('A'..='z', ["g̈́"]));
+                let red = Gradient::Lookup(|value| Rgb888::new(value, 0, 0));
+                let green = Gradient::Lookup(|value| Rgb888::new(0, value, 0));
+                let new_style = |gradient| {
+                    BitmapFontStyleBuilder::new()
+                        .font(&bitmap_font)
+                        .background_color(Rgb888::BLACK)
+                        .gradient(gradient)
+                        .build()
+                };
+
+                let position = Point::new(4, 20);
+                let mut expected = new_display();
+                let next_position = new_style(red)
+                    .draw_string($text, position, Baseline::Alphabetic, &mut expected)
+                    .unwrap();
+
+                let mut next_expected = new_display();
+                new_style(green)
+                    .draw_string($next_text, next_position, Baseline::Alphabetic, &mut next_expected)
+                    .unwrap();
+
+                let mut style = new_style(red);
+                let mut display = new_display();
+                style
+                    .draw_string($text, position, Baseline::Alphabetic, &mut display)
+                    .unwrap();
+
+                style.gradient = Some(green);
+                style
+                    .draw_string($next_text, next_position, Baseline::Alphabetic, &mut display)
+                    .unwrap();
+
+                // Where the next text run only draws the background, the glyph images carried over
+                // from the previous text run keep the colors of their own gradient.
+                let mut is_redrawn = false;
+                for point in expected.affected_area().points() {
+                    let Some(color) = expected.get_pixel(point) else {
+                        continue;
+                    };
+                    let next_color = next_expected.get_pixel(point);
+                    if next_color.is_none_or(|next_color| next_color == Rgb888::BLACK) {
+                        is_redrawn |= next_color.is_some() && color != Rgb888::BLACK;
+                        assert_eq!(display.get_pixel(point), Some(color), "\n  {point:?}");
+                    }
+                }
+
+                assert!(is_redrawn);
+            }
+        )*
+    }
+}
+
+test_draw_string_gradient_carryover! {
+    draw_string_gradient_carryover, "f", "j",
+    draw_string_gradient_carryover_descender, "y", "j",
+}
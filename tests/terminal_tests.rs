@@ -0,0 +1,176 @@
+mod common;
+
+use core::fmt::Write;
+
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Baseline;
+use embedded_graphics::text::renderer::TextRenderer;
+use mplusfonts::mplus;
+use mplusfonts::style::BitmapFontStyleBuilder;
+use mplusfonts::terminal::{AnsiColor, Cell, CellWidth, Terminal};
+
+use common::{BACKGROUND_COLOR, TEXT_COLOR, new_display};
+
+const POSITION: Point = Point::new(3, 2);
+
+macro_rules! test_terminal_draw {
+    (
+        $(
+            $fn_ident:ident, $input:expr, $lines:expr,
+        )*
+    ) => {
+        $(
+            #[test]
+            fn $fn_ident() {
+                let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~', ["漢"]);
+                let style = BitmapFontStyleBuilder::new()
+                    .font(&bitmap_font)
+                    .text_color(TEXT_COLOR)
+                    .background_color(BACKGROUND_COLOR)
+                    .build();
+
+                let mut cells = [Cell::BLANK; 12];
+                let mut terminal = Terminal::new(&mut cells, 6, POSITION, style.clone());
+                terminal.write_str($input).unwrap();
+
+                let mut display = new_display();
+                terminal.draw(&mut display).unwrap();
+
+                let mut expected = new_display();
+                let line_height = bitmap_font.metrics.line_height() as i32;
+                let lines: [&str; 2] = $lines;
+                for (row, line) in lines.into_iter().enumerate() {
+                    let position = POSITION + Point::new(0, line_height * row as i32);
+                    let next = style
+                        .draw_string(line, position, Baseline::Top, &mut expected)
+                        .unwrap();
+                    let width = (POSITION.x + 48 - next.x) as u32;
+                    style
+                        .draw_whitespace(width, next, Baseline::Top, &mut expected)
+                        .unwrap();
+                }
+
+                display.assert_eq(&expected);
+            }
+        )*
+    }
+}
+
+test_terminal_draw! {
+    terminal_draw_blank, "", ["", ""],
+    terminal_draw_text, "Term", ["Term", ""],
+    terminal_draw_line_feed, "ab\ncd", ["ab", "cd"],
+    terminal_draw_wrapped, "abcdefgh", ["abcdef", "gh"],
+    terminal_draw_cursor_position, "ab\x1B[2;4Hxy", ["ab", "   xy"],
+    terminal_draw_erase_line, "abcdef\x1B[1;3H\x1B[K", ["ab", ""],
+    terminal_draw_wide_character, "a漢b", ["a漢b", ""],
+}
+
+#[test]
+fn terminal_cell_size() {
+    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .build();
+
+    let mut cells = [Cell::BLANK; 12];
+    let terminal = Terminal::new(&mut cells, 4, POSITION, style);
+    let line_height = bitmap_font.metrics.line_height();
+
+    assert_eq!(terminal.columns(), 4);
+    assert_eq!(terminal.rows(), 3);
+    assert_eq!(terminal.cell_size(), Size::new(8, line_height));
+    assert_eq!(
+        terminal.bounding_box(),
+        Rectangle::new(POSITION, Size::new(32, line_height * 3))
+    );
+}
+
+#[test]
+fn terminal_wide_character_cells() {
+    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~', ["漢"]);
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .build();
+
+    let mut cells = [Cell::BLANK; 12];
+    let mut terminal = Terminal::new(&mut cells, 6, POSITION, style);
+    terminal.write_str("a漢b").unwrap();
+
+    let cell = |column| terminal.cell(column, 0).unwrap();
+    assert_eq!((cell(0).character, cell(0).width), ('a', CellWidth::Narrow));
+    assert_eq!((cell(1).character, cell(1).width), ('漢', CellWidth::Wide));
+    assert_eq!(cell(2).width, CellWidth::Continuation);
+    assert_eq!((cell(3).character, cell(3).width), ('b', CellWidth::Narrow));
+    assert_eq!(terminal.cursor(), (4, 0));
+}
+
+#[test]
+fn terminal_sgr_colors() {
+    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .background_color(BACKGROUND_COLOR)
+        .build();
+
+    let mut cells = [Cell::BLANK; 12];
+    let mut terminal = Terminal::new(&mut cells, 6, POSITION, style);
+    terminal.write_str("\x1B[31;44mH\x1B[0mH").unwrap();
+
+    let attributes = terminal.cell(0, 0).unwrap().attributes;
+    assert_eq!(attributes.foreground, AnsiColor::Indexed(1));
+    assert_eq!(attributes.background, AnsiColor::Indexed(4));
+    assert_eq!(terminal.cell(1, 0).unwrap().attributes, Default::default());
+
+    let mut display = new_display();
+    terminal.draw(&mut display).unwrap();
+
+    let cell_size = terminal.cell_size();
+    let first_cell = Rectangle::new(POSITION, cell_size);
+    let second_cell = Rectangle::new(POSITION + Point::new(8, 0), cell_size);
+    let colors = |cell: Rectangle| cell.points().filter_map(|point| display.get_pixel(point));
+    let (red, blue) = (Rgb888::new(205, 0, 0), Rgb888::new(0, 0, 238));
+    let corner_color = |cell: Rectangle| display.get_pixel(cell.top_left);
+
+    assert_eq!(corner_color(first_cell), Some(blue));
+    assert!(colors(first_cell).any(|color| color == red));
+    assert!(colors(first_cell).all(|color| color != TEXT_COLOR));
+    assert_eq!(corner_color(second_cell), Some(BACKGROUND_COLOR));
+    assert!(colors(second_cell).any(|color| color == TEXT_COLOR));
+}
+
+#[test]
+fn terminal_draw_dirty() {
+    let bitmap_font = mplus!(code(100), 500, 16, false, 1, 4, ' '..='~');
+    let style = BitmapFontStyleBuilder::new()
+        .font(&bitmap_font)
+        .text_color(TEXT_COLOR)
+        .background_color(BACKGROUND_COLOR)
+        .build();
+
+    let mut cells = [Cell::BLANK; 12];
+    let mut terminal = Terminal::new(&mut cells, 6, POSITION, style);
+    terminal.write_str("Dirty").unwrap();
+
+    let mut display = new_display();
+    terminal.draw_dirty(&mut display).unwrap();
+    assert_eq!(display.affected_area(), terminal.bounding_box());
+
+    terminal.write_str("\x1B[2;3Hz").unwrap();
+    let mut display = new_display();
+    terminal.draw_dirty(&mut display).unwrap();
+
+    let cell_size = terminal.cell_size();
+    let line_height = cell_size.height as i32;
+    let cell_box = Rectangle::new(POSITION + Point::new(16, line_height), cell_size);
+    assert_eq!(display.affected_area(), cell_box);
+
+    let mut display = new_display();
+    terminal.draw_dirty(&mut display).unwrap();
+    display.assert_eq(&new_display());
+}